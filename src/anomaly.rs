@@ -0,0 +1,311 @@
+//! Conversions between the true, eccentric, hyperbolic, parabolic and mean anomalies.
+//!
+//! Elliptic anomalies are returned on the domain [0, 2pi). Hyperbolic and parabolic anomalies are
+//! unbounded and keep the sign of the true anomaly, which for those orbits is taken on the domain
+//! (-pi, pi).
+
+use std::f64::{self, consts::PI};
+
+use crate::angle_ops::{self, wrap_0_2pi};
+
+/// Relative tolerance on the anomaly for the iterative Kepler solvers
+const KEPLER_TOL: f64 = 4. * f64::EPSILON;
+
+/// Iteration cap for the Kepler solvers. The bracketing fallback guarantees convergence well
+/// before this in double precision.
+const KEPLER_MAX_ITER: usize = 100;
+
+pub fn true_to_eccentric(true_anomaly: f64, eccentricity: f64) -> f64 {
+    let (sin_nu, cos_nu) = true_anomaly.sin_cos();
+    let sin_ecc = (1. - eccentricity.powi(2)).sqrt() * sin_nu;
+    let cos_ecc = eccentricity + cos_nu;
+    wrap_0_2pi(f64::atan2(sin_ecc, cos_ecc))
+}
+
+pub fn eccentric_to_true(eccentric_anomaly: f64, eccentricity: f64) -> f64 {
+    let (sin_ecc, cos_ecc) = eccentric_anomaly.sin_cos();
+    let sin_nu = (1. - eccentricity.powi(2)).sqrt() * sin_ecc;
+    let cos_nu = cos_ecc - eccentricity;
+    wrap_0_2pi(f64::atan2(sin_nu, cos_nu))
+}
+
+pub fn eccentric_to_mean(eccentric_anomaly: f64, eccentricity: f64) -> f64 {
+    wrap_0_2pi(eccentric_anomaly - eccentricity * eccentric_anomaly.sin())
+}
+
+/// Solves Kepler's equation `M = E - e sin(E)` for the eccentric anomaly.
+///
+/// Uses Halley iterations from a Danby starter, or a cubic starter for small anomalies near the
+/// parabolic limit. Any step that would leave the bracket `[M, M + e]` (mirrored for negative
+/// anomalies) is replaced by bisection, so the solver converges for any `0 <= e < 1`.
+pub fn mean_to_eccentric(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    let e = eccentricity;
+    if e == 0. {
+        return wrap_0_2pi(mean_anomaly);
+    }
+
+    // Solve on [0, pi] and mirror, since E - e sin(E) is odd
+    let signed_mean = angle_ops::wrap_negpi_pi(mean_anomaly);
+    let sign = signed_mean.signum();
+    let m = signed_mean.abs();
+
+    let (mut lo, mut hi) = (m, (m + e).min(PI));
+    let mut ecc = if e > 0.8 && m < 0.5 {
+        (6. * m).cbrt().clamp(lo, hi)
+    } else {
+        (m + 0.85 * e).clamp(lo, hi)
+    };
+
+    for _ in 0..KEPLER_MAX_ITER {
+        let (sin_ecc, cos_ecc) = ecc.sin_cos();
+        let f = ecc - e * sin_ecc - m;
+        if f == 0. {
+            break;
+        }
+        if f > 0. {
+            hi = ecc;
+        } else {
+            lo = ecc;
+        }
+
+        let df = 1. - e * cos_ecc;
+        let d2f = e * sin_ecc;
+        let mut next = ecc - 2. * f * df / (2. * df.powi(2) - f * d2f);
+        if !(lo..=hi).contains(&next) || !next.is_finite() {
+            next = 0.5 * (lo + hi);
+        }
+
+        let step = (next - ecc).abs();
+        ecc = next;
+        if step <= KEPLER_TOL * ecc.max(1.) || hi - lo <= KEPLER_TOL * hi.max(1.) {
+            break;
+        }
+    }
+    wrap_0_2pi(sign * ecc)
+}
+
+pub fn true_to_hyperbolic(true_anomaly: f64, eccentricity: f64) -> f64 {
+    let (sin_nu, cos_nu) = true_anomaly.sin_cos();
+    let sinh_hyp = (eccentricity.powi(2) - 1.).sqrt() * sin_nu / (1. + eccentricity * cos_nu);
+    sinh_hyp.asinh()
+}
+
+pub fn hyperbolic_to_true(hyperbolic_anomaly: f64, eccentricity: f64) -> f64 {
+    let sinh_hyp = hyperbolic_anomaly.sinh();
+    let cosh_hyp = hyperbolic_anomaly.cosh();
+    let sin_nu = (eccentricity.powi(2) - 1.).sqrt() * sinh_hyp;
+    let cos_nu = eccentricity - cosh_hyp;
+    f64::atan2(sin_nu, cos_nu)
+}
+
+pub fn hyperbolic_to_mean(hyperbolic_anomaly: f64, eccentricity: f64) -> f64 {
+    eccentricity * hyperbolic_anomaly.sinh() - hyperbolic_anomaly
+}
+
+/// Solves the hyperbolic Kepler equation `M = e sinh(H) - H` for the hyperbolic anomaly.
+///
+/// Uses Halley iterations safeguarded by the bracket `[asinh(M/e), asinh(M/(e-1))]`, which always
+/// contains the root for `e > 1`.
+pub fn mean_to_hyperbolic(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    let e = eccentricity;
+    let sign = mean_anomaly.signum();
+    let m = mean_anomaly.abs();
+    if m == 0. {
+        return 0.;
+    }
+
+    let (mut lo, mut hi) = ((m / e).asinh(), (m / (e - 1.)).asinh());
+    let mut hyp = (2. * m / e + 1.8).ln().clamp(lo, hi);
+
+    for _ in 0..KEPLER_MAX_ITER {
+        let sinh_hyp = hyp.sinh();
+        let f = e * sinh_hyp - hyp - m;
+        if f == 0. {
+            break;
+        }
+        if f > 0. {
+            hi = hyp;
+        } else {
+            lo = hyp;
+        }
+
+        let df = e * hyp.cosh() - 1.;
+        let d2f = e * sinh_hyp;
+        let mut next = hyp - 2. * f * df / (2. * df.powi(2) - f * d2f);
+        if !(lo..=hi).contains(&next) || !next.is_finite() {
+            next = 0.5 * (lo + hi);
+        }
+
+        let step = (next - hyp).abs();
+        hyp = next;
+        if step <= KEPLER_TOL * hyp.max(1.) || hi - lo <= KEPLER_TOL * hi.max(1.) {
+            break;
+        }
+    }
+    sign * hyp
+}
+
+/// Parabolic anomaly `D = tan(nu / 2)`
+pub fn true_to_parabolic(true_anomaly: f64) -> f64 {
+    (true_anomaly / 2.).tan()
+}
+
+pub fn parabolic_to_true(parabolic_anomaly: f64) -> f64 {
+    2. * parabolic_anomaly.atan()
+}
+
+/// Barker's equation, `M = D + D^3 / 3`, where the mean motion is `2 sqrt(mu / p^3)`
+pub fn parabolic_to_mean(parabolic_anomaly: f64) -> f64 {
+    parabolic_anomaly + parabolic_anomaly.powi(3) / 3.
+}
+
+/// Closed-form inverse of Barker's equation, which is odd, so it is solved for `|M|` to avoid
+/// cancellation for large negative `M`
+pub fn mean_to_parabolic(mean_anomaly: f64) -> f64 {
+    let b = 1.5 * mean_anomaly.abs();
+    let y = (b + (b.powi(2) + 1.).sqrt()).cbrt();
+    (y - 1. / y).copysign(mean_anomaly)
+}
+
+/// Converts true to mean anomaly for any conic, dispatching on the eccentricity
+pub fn true_to_mean(true_anomaly: f64, eccentricity: f64) -> f64 {
+    if eccentricity < 1. {
+        eccentric_to_mean(true_to_eccentric(true_anomaly, eccentricity), eccentricity)
+    } else if eccentricity > 1. {
+        hyperbolic_to_mean(true_to_hyperbolic(true_anomaly, eccentricity), eccentricity)
+    } else {
+        parabolic_to_mean(true_to_parabolic(true_anomaly))
+    }
+}
+
+/// Converts mean to true anomaly for any conic, dispatching on the eccentricity
+pub fn mean_to_true(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    if eccentricity < 1. {
+        eccentric_to_true(mean_to_eccentric(mean_anomaly, eccentricity), eccentricity)
+    } else if eccentricity > 1. {
+        hyperbolic_to_true(mean_to_hyperbolic(mean_anomaly, eccentricity), eccentricity)
+    } else {
+        parabolic_to_true(mean_to_parabolic(mean_anomaly))
+    }
+}
+
+#[cfg(test)]
+mod elliptic_tests {
+    use super::*;
+
+    #[test]
+    /// Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., example 2-1
+    fn test_kepler_vallado() {
+        let ecc = mean_to_eccentric(235.4_f64.to_radians(), 0.4);
+        assert_relative_eq!(ecc.to_degrees(), 220.512_074_767_522, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_circular() {
+        assert_eq!(mean_to_eccentric(1.2, 0.), 1.2);
+        assert_relative_eq!(true_to_eccentric(1.2, 0.), 1.2);
+        assert_relative_eq!(eccentric_to_true(1.2, 0.), 1.2);
+    }
+
+    #[test]
+    /// Kepler's equation is solved to machine precision over the whole domain, including close to
+    /// the parabolic limit
+    fn test_kepler_residual() {
+        for &e in &[0.01, 0.3, 0.7, 0.9, 0.99, 0.999_999, 1. - 1e-12] {
+            for i in 0..=72 {
+                let mean = f64::from(i) * 5_f64.to_radians();
+                let ecc = mean_to_eccentric(mean, e);
+                let residual = angle_ops::wrap_negpi_pi(ecc - e * ecc.sin() - mean);
+                assert!(
+                    residual.abs() < 1e-14,
+                    "e = {e}, M = {mean}, residual = {residual}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for &e in &[0.1, 0.5, 0.95] {
+            for i in 0..36 {
+                let nu = f64::from(i) * 10_f64.to_radians();
+                let mean = true_to_mean(nu, e);
+                let nu_round_trip = mean_to_true(mean, e);
+                assert_relative_eq!(
+                    angle_ops::wrap_negpi_pi(nu_round_trip - nu),
+                    0.,
+                    epsilon = 1e-12
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod hyperbolic_tests {
+    use super::*;
+
+    #[test]
+    /// Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., example 2-3
+    fn test_hyperbolic_kepler_vallado() {
+        let hyp = mean_to_hyperbolic(235.4_f64.to_radians(), 2.4);
+        assert_relative_eq!(hyp, 1.601_376_144, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_hyperbolic_kepler_residual() {
+        for &e in &[1. + 1e-9, 1.001, 1.5, 3., 30.] {
+            for &mean in &[-1e4, -50., -1., -1e-3, 0., 1e-6, 0.2, 3., 1e3, 1e6] {
+                let hyp = mean_to_hyperbolic(mean, e);
+                let residual = e * hyp.sinh() - hyp - mean;
+                assert!(
+                    residual.abs() < 1e-12 * mean.abs().max(1.),
+                    "e = {e}, M = {mean}, residual = {residual}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let e: f64 = 1.8;
+        let nu_max = (-1. / e).acos();
+        for i in -9..=9 {
+            let nu = f64::from(i) / 10. * nu_max;
+            let nu_round_trip = mean_to_true(true_to_mean(nu, e), e);
+            assert_relative_eq!(nu_round_trip, nu, epsilon = 1e-12);
+        }
+    }
+}
+
+#[cfg(test)]
+mod parabolic_tests {
+    use super::*;
+
+    #[test]
+    fn test_barker_round_trip() {
+        for i in -9..=9 {
+            let nu = f64::from(i) / 10. * PI;
+            let nu_round_trip = mean_to_true(true_to_mean(nu, 1.), 1.);
+            assert_relative_eq!(nu_round_trip, nu, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    /// Far from periapsis the inverse stays accurate on the way in as well as on the way out
+    fn test_barker_large_mean_anomaly() {
+        for mean_anomaly in [1e5, 1e7, 1e12] {
+            for sign in [1., -1.] {
+                let mean_anomaly = sign * mean_anomaly;
+                let round_trip = parabolic_to_mean(mean_to_parabolic(mean_anomaly));
+                assert_relative_eq!(round_trip, mean_anomaly, max_relative = 1e-14);
+            }
+        }
+    }
+
+    #[test]
+    /// At a true anomaly of 90 degrees, D = 1 and M = 4/3
+    fn test_barker_right_angle() {
+        assert_relative_eq!(true_to_mean(PI / 2., 1.), 4. / 3., epsilon = 1e-15);
+    }
+}
@@ -0,0 +1,179 @@
+//! Physical constants of the bodies an orbit can be centered on.
+//!
+//! Gravitational parameters follow the JPL DE440 planetary ephemeris, radii and rotation rates the
+//! IAU WGCCRE 2015 report, and zonal harmonics the most recent published gravity solutions
+//! (EGM2008 for the Earth), rescaled to the equatorial radius given here.
+
+use crate::constants;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CentralBody {
+    pub name: &'static str,
+    /// Standard gravitational parameter in m^3/s^2
+    pub mu: f64,
+    /// Equatorial radius in m, also the reference radius for `zonal_harmonics`
+    pub equatorial_radius: f64,
+    pub flattening: f64,
+    /// Unnormalized zonal harmonic coefficients, starting from J2
+    pub zonal_harmonics: &'static [f64],
+    /// Sidereal rotation rate in rad/s, negative for retrograde rotation
+    pub rotation_rate: f64,
+}
+
+impl CentralBody {
+    /// Unnormalized zonal coefficient `J_degree`, zero where it isn't known or for degrees below 2
+    pub fn zonal(&self, degree: usize) -> f64 {
+        degree
+            .checked_sub(2)
+            .and_then(|index| self.zonal_harmonics.get(index))
+            .copied()
+            .unwrap_or(0.)
+    }
+
+    pub fn polar_radius(&self) -> f64 {
+        self.equatorial_radius * (1. - self.flattening)
+    }
+}
+
+pub const SUN: CentralBody = CentralBody {
+    name: "Sun",
+    mu: 1.327_124_400_41e20,
+    equatorial_radius: 695_700_000.,
+    flattening: 0.,
+    zonal_harmonics: &[2.2e-7],
+    rotation_rate: 2.865_329_6e-6,
+};
+
+pub const MERCURY: CentralBody = CentralBody {
+    name: "Mercury",
+    mu: 2.203_186_855e13,
+    equatorial_radius: 2_440_530.,
+    flattening: 0.000_9,
+    zonal_harmonics: &[5.03e-5],
+    rotation_rate: 1.240_013_5e-6,
+};
+
+pub const VENUS: CentralBody = CentralBody {
+    name: "Venus",
+    mu: 3.248_585_92e14,
+    equatorial_radius: 6_051_800.,
+    flattening: 0.,
+    zonal_harmonics: &[4.458e-6],
+    rotation_rate: -2.992_44e-7,
+};
+
+pub const EARTH: CentralBody = CentralBody {
+    name: "Earth",
+    mu: constants::MU_EARTH,
+    equatorial_radius: 6_378_137.,
+    flattening: 1. / 298.257_223_563,
+    zonal_harmonics: &[
+        1.082_626_683_55e-3,
+        -2.532_656_485_33e-6,
+        -1.619_621_591_37e-6,
+        -2.272_960_828_79e-7,
+        5.406_812_391_07e-7,
+    ],
+    rotation_rate: 7.292_115_146_706_979e-5,
+};
+
+pub const MOON: CentralBody = CentralBody {
+    name: "Moon",
+    mu: 4.902_800_118e12,
+    equatorial_radius: 1_738_100.,
+    flattening: 0.001_2,
+    zonal_harmonics: &[2.033_0e-4, 8.476e-6],
+    rotation_rate: 2.661_699_5e-6,
+};
+
+pub const MARS: CentralBody = CentralBody {
+    name: "Mars",
+    mu: 4.282_837_362e13,
+    equatorial_radius: 3_396_190.,
+    flattening: 0.005_886,
+    zonal_harmonics: &[1.960_45e-3, 3.145e-5],
+    rotation_rate: 7.088_218_1e-5,
+};
+
+pub const JUPITER: CentralBody = CentralBody {
+    name: "Jupiter",
+    mu: 1.266_865_341_7e17,
+    equatorial_radius: 71_492_000.,
+    flattening: 0.064_87,
+    zonal_harmonics: &[1.469_65e-2, 0., -5.866e-4, 0., 3.42e-5],
+    rotation_rate: 1.758_531_9e-4,
+};
+
+pub const SATURN: CentralBody = CentralBody {
+    name: "Saturn",
+    mu: 3.793_120_615e16,
+    equatorial_radius: 60_268_000.,
+    flattening: 0.097_96,
+    zonal_harmonics: &[1.629_071e-2, 0., -9.368_3e-4, 0., 8.6e-5],
+    rotation_rate: 1.637_884_8e-4,
+};
+
+pub const URANUS: CentralBody = CentralBody {
+    name: "Uranus",
+    mu: 5.793_951_322e15,
+    equatorial_radius: 25_559_000.,
+    flattening: 0.022_93,
+    zonal_harmonics: &[3.510_7e-3, 0., -3.42e-5],
+    rotation_rate: -1.012_37e-4,
+};
+
+pub const NEPTUNE: CentralBody = CentralBody {
+    name: "Neptune",
+    mu: 6.835_099_97e15,
+    equatorial_radius: 24_764_000.,
+    flattening: 0.017_08,
+    zonal_harmonics: &[3.536_4e-3, 0., -3.6e-5],
+    rotation_rate: 1.083_382_0e-4,
+};
+
+pub const PLUTO: CentralBody = CentralBody {
+    name: "Pluto",
+    mu: 8.696_138_177e11,
+    equatorial_radius: 1_188_300.,
+    flattening: 0.,
+    zonal_harmonics: &[],
+    rotation_rate: -1.138_559_5e-5,
+};
+
+/// Every built-in body, for lookup by name
+pub const ALL: [CentralBody; 11] = [
+    SUN, MERCURY, VENUS, EARTH, MOON, MARS, JUPITER, SATURN, URANUS, NEPTUNE, PLUTO,
+];
+
+/// Finds a built-in body by its name, ignoring case
+pub fn by_name(name: &str) -> Option<CentralBody> {
+    ALL.iter()
+        .find(|body| body.name.eq_ignore_ascii_case(name))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zonal() {
+        assert_eq!(EARTH.zonal(0), 0.);
+        assert_eq!(EARTH.zonal(1), 0.);
+        assert_eq!(EARTH.zonal(2), EARTH.zonal_harmonics[0]);
+        assert_eq!(EARTH.zonal(6), EARTH.zonal_harmonics[4]);
+        assert_eq!(EARTH.zonal(7), 0.);
+        assert_eq!(PLUTO.zonal(2), 0.);
+    }
+
+    #[test]
+    fn test_polar_radius() {
+        assert_relative_eq!(EARTH.polar_radius(), 6_356_752.314_245, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_by_name() {
+        assert_eq!(by_name("mars"), Some(MARS));
+        assert_eq!(by_name("Vulcan"), None);
+    }
+}
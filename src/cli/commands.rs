@@ -0,0 +1,391 @@
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use serde::Deserialize;
+
+use orbitrs::maneuvers;
+use orbitrs::prelude::*;
+
+use super::input::{self, CliResult};
+use super::output::Table;
+
+/// Orbit representations which can be printed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Elements {
+    /// Classical elements sized by the semi-major axis
+    Coe,
+    /// Classical elements sized by the semi-latus rectum
+    CoeSlr,
+    /// Position and velocity
+    Cartesian,
+    /// Classical equinoctial elements
+    Eoe,
+    /// Modified equinoctial elements
+    Mee,
+}
+
+impl Elements {
+    fn columns(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Coe => &[
+                ("semi_major_axis", "m"),
+                ("eccentricity", ""),
+                ("inclination", "deg"),
+                ("arg_peri", "deg"),
+                ("raan", "deg"),
+                ("true_anomaly", "deg"),
+            ],
+            Self::CoeSlr => &[
+                ("semi_latus_rectum", "m"),
+                ("eccentricity", ""),
+                ("inclination", "deg"),
+                ("arg_peri", "deg"),
+                ("raan", "deg"),
+                ("true_anomaly", "deg"),
+            ],
+            Self::Cartesian => &[
+                ("x", "m"),
+                ("y", "m"),
+                ("z", "m"),
+                ("vx", "m/s"),
+                ("vy", "m/s"),
+                ("vz", "m/s"),
+            ],
+            Self::Eoe => &[
+                ("semi_major_axis", "m"),
+                ("f", ""),
+                ("g", ""),
+                ("h", ""),
+                ("k", ""),
+                ("true_longitude", "deg"),
+            ],
+            Self::Mee => &[
+                ("semi_latus_rectum", "m"),
+                ("f", ""),
+                ("g", ""),
+                ("h", ""),
+                ("k", ""),
+                ("true_longitude", "deg"),
+            ],
+        }
+    }
+
+    fn values(self, orbit: &Orbit) -> Vec<f64> {
+        match self {
+            Self::Coe => {
+                let coe = orbit.to_coe();
+                vec![
+                    coe.semi_major_axis,
+                    coe.eccentricity,
+                    coe.inclination.to_degrees(),
+                    coe.arg_peri.to_degrees(),
+                    coe.raan.to_degrees(),
+                    coe.true_anomaly.to_degrees(),
+                ]
+            }
+            Self::CoeSlr => {
+                let coe = COESlr::from(&to_mee(orbit));
+                vec![
+                    coe.semi_latus_rectum,
+                    coe.eccentricity,
+                    coe.inclination.to_degrees(),
+                    coe.arg_peri.to_degrees(),
+                    coe.raan.to_degrees(),
+                    coe.true_anomaly.to_degrees(),
+                ]
+            }
+            Self::Cartesian => {
+                let state = orbit.to_cartesian();
+                state
+                    .position
+                    .elem
+                    .into_iter()
+                    .chain(state.velocity.elem)
+                    .collect()
+            }
+            Self::Eoe => {
+                let eoe = match orbit {
+                    Orbit::EOE(eoe) => eoe.clone(),
+                    orbit => EOE::from(&to_mee(orbit)),
+                };
+                vec![
+                    eoe.semi_major_axis,
+                    eoe.f,
+                    eoe.g,
+                    eoe.h,
+                    eoe.k,
+                    eoe.true_longitude.to_degrees(),
+                ]
+            }
+            Self::Mee => {
+                let mee = to_mee(orbit);
+                vec![
+                    mee.semi_latus_rectum,
+                    mee.f,
+                    mee.g,
+                    mee.h,
+                    mee.k,
+                    mee.true_longitude.to_degrees(),
+                ]
+            }
+        }
+    }
+}
+
+/// Modified equinoctial elements of any orbit, which unlike `COE` hold parabolic orbits as well
+fn to_mee(orbit: &Orbit) -> MEE {
+    match orbit {
+        Orbit::MEE(mee) => mee.clone(),
+        Orbit::EOE(eoe) => MEE::from(eoe),
+        Orbit::Cartesian(state) => MEE::from(state),
+        Orbit::COE(coe) => MEE::from(coe),
+    }
+}
+
+pub fn convert(orbit: &Orbit, to: Elements) -> Table {
+    let mut table = Table::new(to.columns());
+    table.push(to.values(orbit));
+    table
+}
+
+/// States at each of the given times of flight
+pub fn propagate(orbit: &Orbit, times: &[f64], to: Elements) -> Table {
+    let columns: Vec<_> = [("time", "s")]
+        .iter()
+        .chain(to.columns())
+        .copied()
+        .collect();
+    let mut table = Table::new(&columns);
+    for &time in times {
+        let state = Keplerian.propagate(orbit, time);
+        table.push([time].into_iter().chain(to.values(&state)).collect());
+    }
+    table
+}
+
+/// Size, shape and energy of an orbit, along with the radius and speed where it currently is
+pub fn info(orbit: &Orbit) -> Table {
+    let coe = COESlr::from(&to_mee(orbit));
+    let mut table = Table::new(&[
+        ("semi_major_axis", "m"),
+        ("semi_latus_rectum", "m"),
+        ("eccentricity", ""),
+        ("inclination", "deg"),
+        ("periapsis_radius", "m"),
+        ("apoapsis_radius", "m"),
+        ("periapsis_altitude", "m"),
+        ("apoapsis_altitude", "m"),
+        ("period", "s"),
+        ("mean_motion", "rad/s"),
+        ("specific_energy", "J/kg"),
+        ("specific_angular_momentum", "m^2/s"),
+        ("radius", "m"),
+        ("speed", "m/s"),
+        ("flight_path_angle", "deg"),
+    ]);
+    table.push(vec![
+        coe.semi_major_axis(),
+        coe.semi_latus_rectum,
+        coe.eccentricity,
+        coe.inclination.to_degrees(),
+        coe.periapsis_radius(),
+        coe.apoapsis_radius(),
+        coe.periapsis_altitude(),
+        coe.apoapsis_altitude(),
+        coe.period(),
+        coe.mean_motion(),
+        coe.specific_energy(),
+        coe.specific_angular_momentum(),
+        coe.radius(coe.true_anomaly),
+        coe.vis_viva_speed(coe.true_anomaly),
+        coe.flight_path_angle(coe.true_anomaly).to_degrees(),
+    ]);
+    table
+}
+
+/// A transfer between two circular, coplanar orbits, given on the command line, in a JSON or TOML
+/// file, or both. Lengths are in metres.
+#[derive(Args, Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransferInput {
+    /// JSON or TOML file holding any of the options below, named in snake_case
+    #[arg(long, short)]
+    #[serde(skip)]
+    pub input: Option<PathBuf>,
+    /// Central body by name [default: Earth]
+    #[arg(long)]
+    pub body: Option<String>,
+    #[arg(long)]
+    pub initial_radius: Option<f64>,
+    #[arg(long)]
+    pub initial_altitude: Option<f64>,
+    #[arg(long)]
+    pub final_radius: Option<f64>,
+    #[arg(long)]
+    pub final_altitude: Option<f64>,
+    /// Apoapsis radius of a bi-elliptic transfer, which is a Hohmann transfer if not given
+    #[arg(long)]
+    pub bi_elliptic_radius: Option<f64>,
+}
+
+impl TransferInput {
+    pub fn load(&self) -> CliResult<Self> {
+        let Some(path) = &self.input else {
+            return Ok(self.clone());
+        };
+        let file: Self = input::read_file(path)?;
+        Ok(Self {
+            input: None,
+            body: self.body.clone().or(file.body),
+            initial_radius: self.initial_radius.or(file.initial_radius),
+            initial_altitude: self.initial_altitude.or(file.initial_altitude),
+            final_radius: self.final_radius.or(file.final_radius),
+            final_altitude: self.final_altitude.or(file.final_altitude),
+            bi_elliptic_radius: self.bi_elliptic_radius.or(file.bi_elliptic_radius),
+        })
+    }
+}
+
+pub fn transfer(input: &TransferInput) -> CliResult<Table> {
+    let central_body = input::central_body(input.body.as_deref())?;
+    let radius = |name, radius: Option<f64>, altitude: Option<f64>| -> CliResult<f64> {
+        let radius = match (radius, altitude) {
+            (Some(radius), None) => radius,
+            (None, Some(altitude)) => altitude + central_body.equatorial_radius,
+            _ => return Err(format!("give exactly one of the {name} radius and altitude").into()),
+        };
+        if radius > 0. && radius.is_finite() {
+            Ok(radius)
+        } else {
+            Err(format!("the {name} radius must be positive").into())
+        }
+    };
+    let initial = radius("initial", input.initial_radius, input.initial_altitude)?;
+    let final_ = radius("final", input.final_radius, input.final_altitude)?;
+
+    let circular = |radius| COE::new(radius, 0., 0., 0., 0., 0.).with_central_body(central_body);
+    let (initial, final_) = (circular(initial), circular(final_));
+    let Some(intermediate) = input.bi_elliptic_radius else {
+        let transfer = maneuvers::hohmann(&initial, &final_)?;
+        let mut table = Table::new(&[
+            ("delta_v_1", "m/s"),
+            ("delta_v_2", "m/s"),
+            ("delta_v_total", "m/s"),
+            ("transfer_time", "s"),
+        ]);
+        let [first, second] = [0, 1].map(|index| transfer.burns[index].magnitude());
+        table.push(vec![first, second, transfer.delta_v(), transfer.duration()]);
+        return Ok(table);
+    };
+    let transfer = maneuvers::bi_elliptic(&initial, intermediate, &final_)?;
+    let mut table = Table::new(&[
+        ("delta_v_1", "m/s"),
+        ("delta_v_2", "m/s"),
+        ("delta_v_3", "m/s"),
+        ("delta_v_total", "m/s"),
+        ("transfer_time", "s"),
+    ]);
+    let [first, second, third] = [0, 1, 2].map(|index| transfer.burns[index].magnitude());
+    table.push(vec![
+        first,
+        second,
+        third,
+        transfer.delta_v(),
+        transfer.duration(),
+    ]);
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transfer_input_test() {
+        let input = TransferInput {
+            initial_altitude: Some(400_000.),
+            final_radius: Some(42_164_000.),
+            ..Default::default()
+        };
+        assert!(transfer(&input).is_ok());
+
+        let input = TransferInput {
+            initial_altitude: Some(400_000.),
+            initial_radius: Some(6_778_000.),
+            final_radius: Some(42_164_000.),
+            ..Default::default()
+        };
+        assert!(transfer(&input).is_err());
+
+        let input = TransferInput {
+            initial_radius: Some(7_000_000.),
+            final_radius: Some(42_164_000.),
+            bi_elliptic_radius: Some(20_000_000.),
+            ..Default::default()
+        };
+        assert!(transfer(&input).is_err());
+    }
+
+    #[test]
+    /// A state vector at exactly the escape speed prints as finite modified equinoctial elements
+    fn parabolic_mee_test() {
+        let mu = bodies::EARTH.mu;
+        let state = CartesianState::new(Vector3::new([mu, 0., 0.]), Vector3::new([1., 1., 0.]));
+        let values = Elements::Mee.values(&Orbit::Cartesian(state));
+        assert!(values.iter().all(|value| value.is_finite()));
+        assert_relative_eq!(values[0], mu, max_relative = 1e-15);
+        assert_relative_eq!(values[1].hypot(values[2]), 1., epsilon = 1e-15);
+    }
+
+    #[test]
+    /// Every representation printed reads back in as the same state
+    fn elements_round_trip_test() {
+        let orbit = Orbit::COE(COE::new(7_000_000., 0.1, 1., 2., 3., 4.));
+        let expected = Elements::Cartesian.values(&orbit);
+        for elements in [
+            Elements::Coe,
+            Elements::CoeSlr,
+            Elements::Cartesian,
+            Elements::Eoe,
+            Elements::Mee,
+        ] {
+            let values = elements.values(&orbit);
+            assert_eq!(values.len(), elements.columns().len());
+            let angles = || input::OrbitInput {
+                eccentricity: Some(values[1]),
+                inclination: Some(values[2]),
+                arg_peri: Some(values[3]),
+                raan: Some(values[4]),
+                true_anomaly: Some(values[5]),
+                ..Default::default()
+            };
+            let input = match elements {
+                Elements::Coe => input::OrbitInput {
+                    semi_major_axis: Some(values[0]),
+                    ..angles()
+                },
+                Elements::CoeSlr => input::OrbitInput {
+                    semi_latus_rectum: Some(values[0]),
+                    ..angles()
+                },
+                Elements::Cartesian => input::OrbitInput {
+                    position: Some(values[..3].to_vec()),
+                    velocity: Some(values[3..].to_vec()),
+                    ..Default::default()
+                },
+                Elements::Eoe => input::OrbitInput {
+                    eoe: Some(values.clone()),
+                    ..Default::default()
+                },
+                Elements::Mee => input::OrbitInput {
+                    mee: Some(values.clone()),
+                    ..Default::default()
+                },
+            };
+            let state = Elements::Cartesian.values(&input.to_orbit().unwrap());
+            for (value, expected) in state.iter().zip(&expected) {
+                assert_relative_eq!(value, expected, max_relative = 1e-9);
+            }
+        }
+    }
+}
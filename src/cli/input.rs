@@ -0,0 +1,275 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use orbitrs::prelude::*;
+
+pub type CliResult<T> = Result<T, Box<dyn Error>>;
+
+type Setter = fn(&mut Builder, f64) -> &mut Builder;
+
+/// An orbit given on the command line, in a JSON or TOML file, or both.
+///
+/// Lengths are in metres, times in seconds and angles in degrees. Values given on the command line
+/// take precedence over the file.
+#[derive(Args, Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OrbitInput {
+    /// JSON or TOML file holding any of the options below, named in snake_case
+    #[arg(long, short)]
+    #[serde(skip)]
+    pub input: Option<PathBuf>,
+    /// Central body by name [default: Earth]
+    #[arg(long)]
+    pub body: Option<String>,
+    #[arg(long, visible_alias = "sma", allow_negative_numbers = true)]
+    pub semi_major_axis: Option<f64>,
+    #[arg(long, visible_alias = "ecc")]
+    pub eccentricity: Option<f64>,
+    #[arg(long, visible_alias = "inc")]
+    pub inclination: Option<f64>,
+    #[arg(long, visible_alias = "argp", allow_negative_numbers = true)]
+    pub arg_peri: Option<f64>,
+    #[arg(long, allow_negative_numbers = true)]
+    pub raan: Option<f64>,
+    #[arg(long, visible_alias = "nu", allow_negative_numbers = true)]
+    pub true_anomaly: Option<f64>,
+    #[arg(long, allow_negative_numbers = true)]
+    pub mean_anomaly: Option<f64>,
+    #[arg(long, visible_alias = "slr")]
+    pub semi_latus_rectum: Option<f64>,
+    #[arg(long)]
+    pub period: Option<f64>,
+    #[arg(long)]
+    pub periapsis_radius: Option<f64>,
+    #[arg(long)]
+    pub apoapsis_radius: Option<f64>,
+    #[arg(long)]
+    pub periapsis_altitude: Option<f64>,
+    #[arg(long)]
+    pub apoapsis_altitude: Option<f64>,
+    /// Position as x,y,z
+    #[arg(long, value_delimiter = ',', allow_hyphen_values = true)]
+    pub position: Option<Vec<f64>>,
+    /// Velocity as vx,vy,vz
+    #[arg(long, value_delimiter = ',', allow_hyphen_values = true)]
+    pub velocity: Option<Vec<f64>>,
+    /// Classical equinoctial elements as a,f,g,h,k,L
+    #[arg(long, value_delimiter = ',', allow_hyphen_values = true)]
+    pub eoe: Option<Vec<f64>>,
+    /// Modified equinoctial elements as p,f,g,h,k,L
+    #[arg(long, value_delimiter = ',', allow_hyphen_values = true)]
+    pub mee: Option<Vec<f64>>,
+}
+
+impl OrbitInput {
+    /// Combines the command line with the input file, if one was given
+    pub fn load(&self) -> CliResult<Self> {
+        let Some(path) = &self.input else {
+            return Ok(self.clone());
+        };
+        let file: Self = read_file(path)?;
+        Ok(Self {
+            input: None,
+            body: self.body.clone().or(file.body),
+            semi_major_axis: self.semi_major_axis.or(file.semi_major_axis),
+            eccentricity: self.eccentricity.or(file.eccentricity),
+            inclination: self.inclination.or(file.inclination),
+            arg_peri: self.arg_peri.or(file.arg_peri),
+            raan: self.raan.or(file.raan),
+            true_anomaly: self.true_anomaly.or(file.true_anomaly),
+            mean_anomaly: self.mean_anomaly.or(file.mean_anomaly),
+            semi_latus_rectum: self.semi_latus_rectum.or(file.semi_latus_rectum),
+            period: self.period.or(file.period),
+            periapsis_radius: self.periapsis_radius.or(file.periapsis_radius),
+            apoapsis_radius: self.apoapsis_radius.or(file.apoapsis_radius),
+            periapsis_altitude: self.periapsis_altitude.or(file.periapsis_altitude),
+            apoapsis_altitude: self.apoapsis_altitude.or(file.apoapsis_altitude),
+            position: self.position.clone().or(file.position),
+            velocity: self.velocity.clone().or(file.velocity),
+            eoe: self.eoe.clone().or(file.eoe),
+            mee: self.mee.clone().or(file.mee),
+        })
+    }
+
+    pub fn to_orbit(&self) -> CliResult<Orbit> {
+        let central_body = central_body(self.body.as_deref())?;
+        let equinoctial = match (&self.eoe, &self.mee) {
+            (Some(_), Some(_)) => return Err("give only one of --eoe and --mee".into()),
+            (Some(values), None) => Some(("--eoe", values)),
+            (None, Some(values)) => Some(("--mee", values)),
+            (None, None) => None,
+        };
+        if let Some((name, values)) = equinoctial {
+            if self.has_builder_parameters() {
+                return Err(format!("{name} can't be combined with other elements").into());
+            }
+            let [size, f, g, h, k, true_longitude] = components::<6>(name, values)?;
+            let true_longitude = true_longitude.to_radians();
+            return Ok(if self.eoe.is_some() {
+                Orbit::EOE(
+                    EOE::new(size, f, g, h, k, true_longitude).with_central_body(central_body),
+                )
+            } else {
+                Orbit::MEE(
+                    MEE::new(size, f, g, h, k, true_longitude).with_central_body(central_body),
+                )
+            });
+        }
+
+        let mut builder = Builder::new();
+        builder.central_body(central_body);
+        let values: [(Option<f64>, Setter); 8] = [
+            (self.semi_major_axis, Builder::semi_major_axis),
+            (self.eccentricity, Builder::eccentricity),
+            (self.semi_latus_rectum, Builder::semi_latus_rectum),
+            (self.period, Builder::period),
+            (self.periapsis_radius, Builder::periapsis_radius),
+            (self.apoapsis_radius, Builder::apoapsis_radius),
+            (self.periapsis_altitude, Builder::periapsis_altitude),
+            (self.apoapsis_altitude, Builder::apoapsis_altitude),
+        ];
+        let angles: [(Option<f64>, Setter); 5] = [
+            (self.inclination, Builder::inclination),
+            (self.arg_peri, Builder::arg_peri),
+            (self.raan, Builder::raan),
+            (self.true_anomaly, Builder::true_anomaly),
+            (self.mean_anomaly, Builder::mean_anomaly),
+        ];
+        for (value, setter) in values {
+            if let Some(value) = value {
+                setter(&mut builder, value);
+            }
+        }
+        for (value, setter) in angles {
+            if let Some(value) = value {
+                setter(&mut builder, value.to_radians());
+            }
+        }
+        if let Some(position) = &self.position {
+            builder.position(components::<3>("--position", position)?.into());
+        }
+        if let Some(velocity) = &self.velocity {
+            builder.velocity(components::<3>("--velocity", velocity)?.into());
+        }
+        Ok(builder.build()?)
+    }
+
+    fn has_builder_parameters(&self) -> bool {
+        let scalars = [
+            self.semi_major_axis,
+            self.eccentricity,
+            self.inclination,
+            self.arg_peri,
+            self.raan,
+            self.true_anomaly,
+            self.mean_anomaly,
+            self.semi_latus_rectum,
+            self.period,
+            self.periapsis_radius,
+            self.apoapsis_radius,
+            self.periapsis_altitude,
+            self.apoapsis_altitude,
+        ];
+        scalars.iter().any(Option::is_some) || self.position.is_some() || self.velocity.is_some()
+    }
+}
+
+/// Looks up a central body by name, the Earth if none is given
+pub fn central_body(name: Option<&str>) -> CliResult<CentralBody> {
+    match name {
+        None => Ok(bodies::EARTH),
+        Some(name) => {
+            bodies::by_name(name).ok_or_else(|| format!("unknown central body '{name}'").into())
+        }
+    }
+}
+
+/// Reads a JSON or TOML file, chosen by its extension
+pub fn read_file<T: DeserializeOwned>(path: &Path) -> CliResult<T> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("couldn't read {}: {err}", path.display()))?;
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+    parse(&contents, extension).map_err(|err| format!("{}: {err}", path.display()).into())
+}
+
+fn parse<T: DeserializeOwned>(contents: &str, extension: &str) -> CliResult<T> {
+    match extension.to_ascii_lowercase().as_str() {
+        "json" => Ok(serde_json::from_str(contents)?),
+        "toml" => Ok(toml::from_str(contents)?),
+        _ => Err("input files must end in .json or .toml".into()),
+    }
+}
+
+fn components<const N: usize>(name: &str, values: &[f64]) -> CliResult<[f64; N]> {
+    values
+        .try_into()
+        .map_err(|_| format!("{name} takes {N} comma-separated values").into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let toml: OrbitInput = parse(
+            "body = \"mars\"\nsemi_major_axis = 4e6\ninclination = 30\n",
+            "toml",
+        )
+        .unwrap();
+        let json: OrbitInput = parse(
+            r#"{"body": "mars", "semi_major_axis": 4e6, "inclination": 30}"#,
+            "json",
+        )
+        .unwrap();
+        for input in [toml, json] {
+            assert_eq!(input.body.as_deref(), Some("mars"));
+            assert_eq!(input.semi_major_axis, Some(4e6));
+            assert_eq!(input.inclination, Some(30.));
+            let coe = input.to_orbit().unwrap().to_coe();
+            assert_eq!(coe.central_body, bodies::MARS);
+            assert_relative_eq!(coe.inclination, 30_f64.to_radians());
+        }
+
+        assert!(parse::<OrbitInput>("sma = 4e6", "toml").is_err());
+        assert!(parse::<OrbitInput>("", "yaml").is_err());
+    }
+
+    #[test]
+    fn equinoctial_test() {
+        let input = OrbitInput {
+            mee: Some(vec![7e6, 0.1, 0., 0., 0., 90.]),
+            body: Some("moon".to_string()),
+            ..Default::default()
+        };
+        let Orbit::MEE(mee) = input.to_orbit().unwrap() else {
+            panic!("Got the wrong orbit type");
+        };
+        assert_eq!(mee.central_body, bodies::MOON);
+        assert_relative_eq!(mee.true_longitude, std::f64::consts::FRAC_PI_2);
+
+        let input = OrbitInput {
+            eoe: Some(vec![7e6, 0.1, 0., 0., 0.]),
+            ..Default::default()
+        };
+        assert!(input.to_orbit().is_err());
+
+        let input = OrbitInput {
+            eoe: Some(vec![7e6, 0.1, 0., 0., 0., 0.]),
+            eccentricity: Some(0.1),
+            ..Default::default()
+        };
+        assert!(input.to_orbit().is_err());
+    }
+
+    #[test]
+    fn unknown_body_test() {
+        assert!(central_body(Some("Vulcan")).is_err());
+        assert_eq!(central_body(None).unwrap(), bodies::EARTH);
+    }
+}
@@ -0,0 +1,181 @@
+//! Command-line interface to the library, for orbit calculations without writing Rust
+
+mod commands;
+mod input;
+mod output;
+
+use clap::{Parser, Subcommand};
+
+use commands::{Elements, TransferInput};
+use input::{CliResult, OrbitInput};
+use output::Format;
+
+/// Everyday orbit calculations. Lengths are in metres, times in seconds and angles in degrees.
+#[derive(Debug, Parser)]
+#[command(version)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Output format
+    #[arg(long, global = true, value_enum, default_value_t)]
+    format: Format,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Convert an orbit to another representation
+    Convert {
+        #[command(flatten)]
+        orbit: OrbitInput,
+        /// Representation to convert to
+        #[arg(long, value_enum)]
+        to: Elements,
+    },
+    /// State after a time of flight, or an ephemeris table over a span of time
+    Propagate {
+        #[command(flatten)]
+        orbit: OrbitInput,
+        /// Time of flight for a single state
+        #[arg(long, short, allow_negative_numbers = true, conflicts_with = "step")]
+        time: Option<f64>,
+        /// Time between the rows of an ephemeris table
+        #[arg(long, requires = "duration")]
+        step: Option<f64>,
+        /// Time span of an ephemeris table
+        #[arg(long, requires = "step")]
+        duration: Option<f64>,
+        /// Representation of the propagated states
+        #[arg(long, value_enum, default_value = "cartesian")]
+        to: Elements,
+    },
+    /// Period, apsides, energy and other quantities of an orbit
+    Info {
+        #[command(flatten)]
+        orbit: OrbitInput,
+    },
+    /// Delta-v and time of flight of a Hohmann or bi-elliptic transfer between circular orbits
+    Transfer {
+        #[command(flatten)]
+        transfer: TransferInput,
+    },
+}
+
+impl Cli {
+    /// Runs the command, returning what it prints
+    pub fn run(&self) -> CliResult<String> {
+        let table = match &self.command {
+            Command::Convert { orbit, to } => commands::convert(&orbit.load()?.to_orbit()?, *to),
+            Command::Propagate {
+                orbit,
+                time,
+                step,
+                duration,
+                to,
+            } => {
+                let times = match (time, step, duration) {
+                    (Some(time), _, _) => vec![*time],
+                    (None, Some(step), Some(duration)) => ephemeris_times(*step, *duration)?,
+                    _ => return Err("give either --time or --step and --duration".into()),
+                };
+                commands::propagate(&orbit.load()?.to_orbit()?, &times, *to)
+            }
+            Command::Info { orbit } => commands::info(&orbit.load()?.to_orbit()?),
+            Command::Transfer { transfer } => commands::transfer(&transfer.load()?)?,
+        };
+        Ok(table.render(self.format))
+    }
+}
+
+/// Times from zero to `duration` inclusive, `step` apart
+fn ephemeris_times(step: f64, duration: f64) -> CliResult<Vec<f64>> {
+    if !(step > 0. && duration >= 0. && duration.is_finite()) {
+        return Err("--step must be positive and --duration not negative".into());
+    }
+    // Allow for rounding so a duration which is a multiple of the step includes its end
+    let count = (duration / step * (1. + 1e-12)).floor() as usize + 1;
+    Ok((0..count).map(|i| i as f64 * step).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(args: &[&str]) -> CliResult<String> {
+        let args = ["orbitrs"].iter().chain(args);
+        Cli::try_parse_from(args)?.run()
+    }
+
+    #[test]
+    fn convert_test() {
+        let output = run(&[
+            "convert", "--sma", "7000000", "--ecc", "0.1", "--to", "coe-slr", "--format", "csv",
+        ])
+        .unwrap();
+        let mut lines = output.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "semi_latus_rectum [m],eccentricity,inclination [deg],arg_peri [deg],raan [deg],\
+                 true_anomaly [deg]"
+            )
+        );
+        assert_eq!(lines.next(), Some("6930000,0.1,0,0,0,0"));
+    }
+
+    #[test]
+    fn propagate_test() {
+        let args = [
+            "propagate",
+            "--sma=-20000000",
+            "--ecc",
+            "1.5",
+            "--nu",
+            "-30",
+        ];
+        let single = run(&[&args[..], &["--time", "600"]].concat()).unwrap();
+        assert!(single.starts_with("time  600.0000000 s\n"));
+
+        let table = run(&[&args[..], &["--step", "60", "--duration", "600"]].concat()).unwrap();
+        assert_eq!(table.lines().count(), 12);
+
+        assert!(run(&args).is_err());
+        assert!(run(&[&args[..], &["--time", "6", "--step", "60"]].concat()).is_err());
+    }
+
+    #[test]
+    fn info_test() {
+        let output = run(&[
+            "info",
+            "--periapsis-altitude",
+            "400e3",
+            "--apoapsis-altitude",
+            "4000e3",
+        ])
+        .unwrap();
+        assert!(output.contains("apoapsis_altitude          4000000.000 m\n"));
+        assert!(run(&["info", "--ecc", "0.1"]).is_err());
+    }
+
+    #[test]
+    fn transfer_test() {
+        let output = run(&[
+            "transfer",
+            "--initial-altitude",
+            "400e3",
+            "--final-radius",
+            "42164e3",
+            "--format",
+            "csv",
+        ])
+        .unwrap();
+        assert!(output.starts_with("delta_v_1 [m/s],delta_v_2 [m/s],delta_v_total [m/s]"));
+    }
+
+    #[test]
+    fn ephemeris_times_test() {
+        assert_eq!(ephemeris_times(60., 180.).unwrap(), [0., 60., 120., 180.]);
+        assert_eq!(ephemeris_times(0.1, 0.3).unwrap().len(), 4);
+        assert_eq!(ephemeris_times(60., 0.).unwrap(), [0.]);
+        assert!(ephemeris_times(0., 60.).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt::Write;
+
+use clap::ValueEnum;
+
+/// Significant digits of the numbers in human-readable output
+const TEXT_DIGITS: usize = 10;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Aligned, human-readable text
+    #[default]
+    Text,
+    /// Comma-separated values with a header row, at full precision
+    Csv,
+}
+
+/// Named, unit-annotated columns of numbers, which render as text or CSV
+#[derive(Debug)]
+pub struct Table {
+    columns: Vec<(&'static str, &'static str)>,
+    rows: Vec<Vec<f64>>,
+}
+
+impl Table {
+    /// Empty table with the given `(name, unit)` columns, where dimensionless columns have an
+    /// empty unit
+    pub fn new(columns: &[(&'static str, &'static str)]) -> Self {
+        Self {
+            columns: columns.to_vec(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<f64>) {
+        debug_assert_eq!(row.len(), self.columns.len());
+        self.rows.push(row);
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Text if self.rows.len() == 1 => self.render_record(),
+            Format::Text => self.render_columns(),
+            Format::Csv => self.render_csv(),
+        }
+    }
+
+    /// A single row as one `name value unit` line per column
+    fn render_record(&self) -> String {
+        let width = self.columns.iter().map(|(name, _)| name.len()).max();
+        let mut output = String::new();
+        for ((name, unit), value) in self.columns.iter().zip(&self.rows[0]) {
+            let value = significant(*value);
+            let line = format!("{name:<width$}  {value} {unit}", width = width.unwrap_or(0));
+            writeln!(output, "{}", line.trim_end()).unwrap();
+        }
+        output
+    }
+
+    fn render_columns(&self) -> String {
+        let headers: Vec<_> = self.columns.iter().map(header).collect();
+        let cells: Vec<Vec<_>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|value| significant(*value)).collect())
+            .collect();
+        let widths: Vec<_> = headers
+            .iter()
+            .enumerate()
+            .map(|(i, header)| {
+                cells
+                    .iter()
+                    .map(|row| row[i].len())
+                    .chain([header.len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let mut output = String::new();
+        for line in [&headers].into_iter().chain(&cells) {
+            let padded: Vec<_> = line
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:>width$}"))
+                .collect();
+            writeln!(output, "{}", padded.join("  ")).unwrap();
+        }
+        output
+    }
+
+    fn render_csv(&self) -> String {
+        let headers: Vec<_> = self.columns.iter().map(header).collect();
+        let mut output = headers.join(",") + "\n";
+        for row in &self.rows {
+            let cells: Vec<_> = row.iter().map(f64::to_string).collect();
+            writeln!(output, "{}", cells.join(",")).unwrap();
+        }
+        output
+    }
+}
+
+fn header((name, unit): &(&'static str, &'static str)) -> String {
+    if unit.is_empty() {
+        name.to_string()
+    } else {
+        format!("{name} [{unit}]")
+    }
+}
+
+/// Formats a number to `TEXT_DIGITS` significant digits, in scientific notation if it is very
+/// large or small
+fn significant(value: f64) -> String {
+    if value == 0. || !value.is_finite() {
+        return value.to_string();
+    }
+    let exponent = value.abs().log10().floor() as i32;
+    if (-4..12).contains(&exponent) {
+        let decimals = (TEXT_DIGITS as i32 - 1 - exponent).max(0) as usize;
+        format!("{value:.decimals$}")
+    } else {
+        format!("{value:.prec$e}", prec = TEXT_DIGITS - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn significant_test() {
+        assert_eq!(significant(0.), "0");
+        assert_eq!(significant(7_000_000.), "7000000.000");
+        assert_eq!(significant(-0.012_345), "-0.01234500000");
+        assert_eq!(significant(1.5e-9), "1.500000000e-9");
+        assert_eq!(significant(f64::INFINITY), "inf");
+    }
+
+    #[test]
+    fn render_test() {
+        let mut table = Table::new(&[("time", "s"), ("eccentricity", "")]);
+        table.push(vec![0., 0.1]);
+        assert_eq!(
+            table.render(Format::Text),
+            "time          0 s\neccentricity  0.1000000000\n"
+        );
+        table.push(vec![60., 0.2]);
+        assert_eq!(
+            table.render(Format::Text),
+            concat!(
+                "   time [s]  eccentricity\n",
+                "          0  0.1000000000\n",
+                "60.00000000  0.2000000000\n",
+            )
+        );
+        assert_eq!(
+            table.render(Format::Csv),
+            "time [s],eccentricity\n0,0.1\n60,0.2\n"
+        );
+    }
+}
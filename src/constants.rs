@@ -4,3 +4,15 @@ pub const MU_EARTH: f64 = 3.986_004_418e14;
 pub const X_AXIS: [f64; 3] = [1., 0., 0.];
 pub const Y_AXIS: [f64; 3] = [0., 1., 0.];
 pub const Z_AXIS: [f64; 3] = [0., 0., 1.];
+
+/// Astronomical unit in m, IAU 2012 resolution B2
+pub const AU: f64 = 149_597_870_700.;
+
+/// Seconds in a Julian day
+pub const SECONDS_PER_DAY: f64 = 86_400.;
+
+/// Speed of light in vacuum in m/s
+pub const SPEED_OF_LIGHT: f64 = 299_792_458.;
+
+/// Total solar irradiance at 1 AU in W/m^2, IAU 2015 resolution B3
+pub const SOLAR_IRRADIANCE: f64 = 1_361.;
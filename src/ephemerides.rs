@@ -0,0 +1,140 @@
+//! Low-precision analytic positions of solar system bodies, for force models which need to know
+//! roughly where they are without external ephemeris files.
+//!
+//! Times are TT seconds since the J2000 epoch, 2000-01-01 12:00:00 TT. Positions are geocentric, in
+//! m, on the axes of the mean equator and equinox of date, which drift from GCRF by the precession
+//! of about 0.014° a year.
+
+use crate::constants::{AU, SECONDS_PER_DAY};
+use crate::vector::Vector3;
+
+/// Geocentric position of the Sun, from the Astronomical Almanac's low-precision formulas, good
+/// to about 0.01° between 1950 and 2050
+pub fn sun_position(seconds_since_j2000: f64) -> Vector3 {
+    let days = seconds_since_j2000 / SECONDS_PER_DAY;
+    let mean_longitude = (280.460 + 0.985_647_4 * days).to_radians();
+    let mean_anomaly = (357.528 + 0.985_600_3 * days).to_radians();
+    let longitude = mean_longitude
+        + 1.915_f64.to_radians() * mean_anomaly.sin()
+        + 0.020_f64.to_radians() * (2. * mean_anomaly).sin();
+    let obliquity = (23.439 - 0.000_000_4 * days).to_radians();
+    let distance =
+        AU * (1.000_14 - 0.016_71 * mean_anomaly.cos() - 0.000_14 * (2. * mean_anomaly).cos());
+
+    ecliptic_to_equatorial(longitude, 0., obliquity) * distance
+}
+
+/// Geocentric position of the Moon, from the truncated lunar theory in Montenbruck and Gill,
+/// Satellite Orbits, section 3.3.2, good to a few arcminutes and a few hundred km
+pub fn moon_position(seconds_since_j2000: f64) -> Vector3 {
+    let centuries = seconds_since_j2000 / (SECONDS_PER_DAY * 36_525.);
+    let mean_longitude = 218.316_17 + 481_267.880_88 * centuries;
+    // Mean anomalies of the Moon and the Sun, mean argument of latitude of the Moon, and mean
+    // elongation of the Moon from the Sun
+    let l = (134.962_92 + 477_198.867_53 * centuries).to_radians();
+    let l_sun = (357.525_43 + 35_999.049_44 * centuries).to_radians();
+    let f = (93.272_83 + 483_202.018_73 * centuries).to_radians();
+    let d = (297.850_27 + 445_267.111_35 * centuries).to_radians();
+
+    let arcseconds = |value: f64| (value / 3_600.).to_radians();
+    let longitude_terms = 22_640. * l.sin() + 769. * (2. * l).sin() - 4_586. * (l - 2. * d).sin()
+        + 2_370. * (2. * d).sin()
+        - 668. * l_sun.sin()
+        - 412. * (2. * f).sin()
+        - 212. * (2. * l - 2. * d).sin()
+        - 206. * (l + l_sun - 2. * d).sin()
+        + 192. * (l + 2. * d).sin()
+        - 165. * (l_sun - 2. * d).sin()
+        + 148. * (l - l_sun).sin()
+        - 125. * d.sin()
+        - 110. * (l + l_sun).sin()
+        - 55. * (2. * f - 2. * d).sin();
+    let longitude = mean_longitude.to_radians() + arcseconds(longitude_terms);
+    let latitude = arcseconds(
+        18_520.
+            * (f + arcseconds(longitude_terms + 412. * (2. * f).sin() + 541. * l_sun.sin())).sin()
+            - 526. * (f - 2. * d).sin()
+            + 44. * (l + f - 2. * d).sin()
+            - 31. * (-l + f - 2. * d).sin()
+            - 25. * (-2. * l + f).sin()
+            - 23. * (l_sun + f - 2. * d).sin()
+            + 21. * (-l + f).sin()
+            + 11. * (-l_sun + f - 2. * d).sin(),
+    );
+    let distance = 1e3
+        * (385_000.
+            - 20_905. * l.cos()
+            - 3_699. * (2. * d - l).cos()
+            - 2_956. * (2. * d).cos()
+            - 570. * (2. * l).cos()
+            + 246. * (2. * l - 2. * d).cos()
+            - 205. * (l_sun - 2. * d).cos()
+            - 171. * (l + 2. * d).cos()
+            - 152. * (l + l_sun - 2. * d).cos());
+
+    let obliquity = (23.439 - 0.013 * centuries).to_radians();
+    ecliptic_to_equatorial(longitude, latitude, obliquity) * distance
+}
+
+/// Unit vector at the given ecliptic longitude and latitude, on equatorial axes
+fn ecliptic_to_equatorial(longitude: f64, latitude: f64, obliquity: f64) -> Vector3 {
+    let (sin_longitude, cos_longitude) = longitude.sin_cos();
+    let (sin_latitude, cos_latitude) = latitude.sin_cos();
+    let (sin_obliquity, cos_obliquity) = obliquity.sin_cos();
+    let [x, y, z] = [
+        cos_latitude * cos_longitude,
+        cos_latitude * sin_longitude,
+        sin_latitude,
+    ];
+    Vector3::new([
+        x,
+        cos_obliquity * y - sin_obliquity * z,
+        sin_obliquity * y + cos_obliquity * z,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::angle_ops::wrap_0_2pi;
+
+    #[test]
+    /// Meeus, Astronomical Algorithms, 2nd ed., example 25.a: on 1992 October 13.0 TD the Sun's
+    /// true longitude is 199.90988° and its distance 0.99766 AU
+    fn sun_test() {
+        let seconds = (2_448_908.5 - 2_451_545.) * SECONDS_PER_DAY;
+        let position = sun_position(seconds);
+        let [x, y, z] = position.elem;
+        let obliquity = 23.440_23_f64.to_radians();
+        let longitude = f64::atan2(y * obliquity.cos() + z * obliquity.sin(), x);
+        assert_relative_eq!(
+            wrap_0_2pi(longitude).to_degrees(),
+            199.909_88,
+            epsilon = 0.01
+        );
+        assert_relative_eq!(position.norm() / AU, 0.997_66, epsilon = 1e-4);
+        // The Sun stays on the ecliptic
+        let latitude = (z * obliquity.cos() - y * obliquity.sin()) / position.norm();
+        assert!(latitude.abs() < 1e-5);
+    }
+
+    #[test]
+    /// Meeus, Astronomical Algorithms, 2nd ed., example 47.a: on 1992 April 12.0 TD the Moon is at
+    /// longitude 133.162655°, latitude -3.229126° and 368409.7 km
+    fn moon_test() {
+        let seconds = (2_448_724.5 - 2_451_545.) * SECONDS_PER_DAY;
+        let position = moon_position(seconds);
+        let [x, y, z] = position.elem;
+        let obliquity = 23.440_636_f64.to_radians();
+        let longitude = f64::atan2(y * obliquity.cos() + z * obliquity.sin(), x);
+        let latitude = ((z * obliquity.cos() - y * obliquity.sin()) / position.norm()).asin();
+        assert_relative_eq!(
+            wrap_0_2pi(longitude).to_degrees(),
+            133.162_655,
+            epsilon = 0.02
+        );
+        assert_relative_eq!(latitude.to_degrees(), -3.229_126, epsilon = 0.02);
+        assert_relative_eq!(position.norm(), 368_409.7e3, epsilon = 200e3);
+    }
+}
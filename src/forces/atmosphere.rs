@@ -0,0 +1,273 @@
+use crate::ephemerides;
+
+use super::*;
+
+/// Model of the density of a central body's atmosphere, for `Drag`. Models driven by space
+/// weather, such as NRLMSISE-00, which isn't included, plug in by implementing this trait and
+/// taking their inputs from a [`SpaceWeatherData`] series.
+pub trait Atmosphere {
+    /// Density in kg/m^3 at a position in the inertial frame, at a time in TT seconds since J2000
+    fn density(
+        &self,
+        seconds_since_j2000: f64,
+        position: &Vector3,
+        central_body: &CentralBody,
+    ) -> f64;
+}
+
+impl<F> Atmosphere for F
+where
+    F: Fn(f64, &Vector3, &CentralBody) -> f64,
+{
+    fn density(
+        &self,
+        seconds_since_j2000: f64,
+        position: &Vector3,
+        central_body: &CentralBody,
+    ) -> f64 {
+        self(seconds_since_j2000, position, central_body)
+    }
+}
+
+/// Static exponential model of the Earth's atmosphere, fitted to CIRA-72 piecewise between base
+/// altitudes (Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., table 8-4). It
+/// ignores the time of day and solar activity.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Exponential;
+
+/// Base altitude in km, density at the base in kg/m^3 and scale height in km
+const EXPONENTIAL_TABLE: [(f64, f64, f64); 28] = [
+    (0., 1.225, 7.249),
+    (25., 3.899e-2, 6.349),
+    (30., 1.774e-2, 6.682),
+    (40., 3.972e-3, 7.554),
+    (50., 1.057e-3, 8.382),
+    (60., 3.206e-4, 7.714),
+    (70., 8.770e-5, 6.549),
+    (80., 1.905e-5, 5.799),
+    (90., 3.396e-6, 5.382),
+    (100., 5.297e-7, 5.877),
+    (110., 9.661e-8, 7.263),
+    (120., 2.438e-8, 9.473),
+    (130., 8.484e-9, 12.636),
+    (140., 3.845e-9, 16.149),
+    (150., 2.070e-9, 22.523),
+    (180., 5.464e-10, 29.740),
+    (200., 2.789e-10, 37.105),
+    (250., 7.248e-11, 45.546),
+    (300., 2.418e-11, 53.628),
+    (350., 9.518e-12, 53.298),
+    (400., 3.725e-12, 58.515),
+    (450., 1.585e-12, 60.828),
+    (500., 6.967e-13, 63.822),
+    (600., 1.454e-13, 71.835),
+    (700., 3.614e-14, 88.667),
+    (800., 1.170e-14, 124.64),
+    (900., 5.245e-15, 181.05),
+    (1_000., 3.019e-15, 268.),
+];
+
+impl Atmosphere for Exponential {
+    fn density(&self, _: f64, position: &Vector3, central_body: &CentralBody) -> f64 {
+        let altitude = altitude(position, central_body) / 1e3;
+        let index = EXPONENTIAL_TABLE
+            .partition_point(|(base, ..)| *base <= altitude)
+            .max(1)
+            - 1;
+        let (base, density, scale_height) = EXPONENTIAL_TABLE[index];
+        density * ((base - altitude) / scale_height).exp()
+    }
+}
+
+/// Harris–Priester model of the Earth's upper atmosphere between 100 and 1000 km, which follows
+/// the diurnal bulge lagging 30° behind the Sun. Densities are tabulated for mean solar activity
+/// (Montenbruck and Gill, Satellite Orbits, table 3.8); outside the table the density is zero.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HarrisPriester {
+    exponent: f64,
+}
+
+/// Altitude in km, and minimum and maximum density in kg/m^3
+const HARRIS_PRIESTER_TABLE: [(f64, f64, f64); 50] = [
+    (100., 4.974e-7, 4.974e-7),
+    (120., 2.490e-8, 2.490e-8),
+    (130., 8.377e-9, 8.710e-9),
+    (140., 3.899e-9, 4.059e-9),
+    (150., 2.122e-9, 2.215e-9),
+    (160., 1.263e-9, 1.344e-9),
+    (170., 8.008e-10, 8.758e-10),
+    (180., 5.283e-10, 6.010e-10),
+    (190., 3.617e-10, 4.297e-10),
+    (200., 2.557e-10, 3.162e-10),
+    (210., 1.839e-10, 2.396e-10),
+    (220., 1.341e-10, 1.853e-10),
+    (230., 9.949e-11, 1.455e-10),
+    (240., 7.488e-11, 1.157e-10),
+    (250., 5.709e-11, 9.308e-11),
+    (260., 4.403e-11, 7.555e-11),
+    (270., 3.430e-11, 6.182e-11),
+    (280., 2.697e-11, 5.095e-11),
+    (290., 2.139e-11, 4.226e-11),
+    (300., 1.708e-11, 3.526e-11),
+    (320., 1.099e-11, 2.511e-11),
+    (340., 7.214e-12, 1.819e-11),
+    (360., 4.824e-12, 1.337e-11),
+    (380., 3.274e-12, 9.955e-12),
+    (400., 2.249e-12, 7.492e-12),
+    (420., 1.558e-12, 5.684e-12),
+    (440., 1.091e-12, 4.355e-12),
+    (460., 7.701e-13, 3.362e-12),
+    (480., 5.474e-13, 2.612e-12),
+    (500., 3.916e-13, 2.042e-12),
+    (520., 2.819e-13, 1.605e-12),
+    (540., 2.042e-13, 1.267e-12),
+    (560., 1.488e-13, 1.005e-12),
+    (580., 1.092e-13, 7.997e-13),
+    (600., 8.070e-14, 6.390e-13),
+    (620., 6.012e-14, 5.123e-13),
+    (640., 4.519e-14, 4.121e-13),
+    (660., 3.430e-14, 3.325e-13),
+    (680., 2.632e-14, 2.691e-13),
+    (700., 2.043e-14, 2.185e-13),
+    (720., 1.607e-14, 1.779e-13),
+    (740., 1.281e-14, 1.452e-13),
+    (760., 1.036e-14, 1.190e-13),
+    (780., 8.496e-15, 9.776e-14),
+    (800., 7.069e-15, 8.059e-14),
+    (840., 4.680e-15, 5.741e-14),
+    (880., 3.200e-15, 4.210e-14),
+    (920., 2.210e-15, 3.130e-14),
+    (960., 1.560e-15, 2.360e-14),
+    (1_000., 1.150e-15, 1.810e-14),
+];
+
+/// Lag of the apex of the diurnal bulge behind the Sun in right ascension
+const BULGE_LAG: f64 = 30_f64.to_radians();
+
+impl HarrisPriester {
+    /// Model with the exponent of the bulge's shape suggested for the orbit's inclination, from 2
+    /// for equatorial orbits to 6 for polar ones
+    pub fn new(inclination: f64) -> Self {
+        Self {
+            exponent: 2. + 4. * inclination.sin().abs(),
+        }
+    }
+
+    pub fn with_exponent(exponent: f64) -> Self {
+        Self { exponent }
+    }
+}
+
+impl Atmosphere for HarrisPriester {
+    fn density(
+        &self,
+        seconds_since_j2000: f64,
+        position: &Vector3,
+        central_body: &CentralBody,
+    ) -> f64 {
+        let altitude = altitude(position, central_body) / 1e3;
+        let (first, last) = (HARRIS_PRIESTER_TABLE[0].0, HARRIS_PRIESTER_TABLE[49].0);
+        if !(first..=last).contains(&altitude) {
+            return 0.;
+        }
+        let index = HARRIS_PRIESTER_TABLE
+            .partition_point(|(base, ..)| *base <= altitude)
+            .clamp(1, HARRIS_PRIESTER_TABLE.len() - 1)
+            - 1;
+        let (base, min_base, max_base) = HARRIS_PRIESTER_TABLE[index];
+        let (top, min_top, max_top) = HARRIS_PRIESTER_TABLE[index + 1];
+        // Exponential interpolation between the tabulated altitudes
+        let fraction = (altitude - base) / (top - base);
+        let min_density = min_base * (min_top / min_base).powf(fraction);
+        let max_density = max_base * (max_top / max_base).powf(fraction);
+
+        let sun = ephemerides::sun_position(seconds_since_j2000);
+        let [x, y, z] = sun.elem;
+        let (sin_lag, cos_lag) = BULGE_LAG.sin_cos();
+        let bulge = Vector3::new([x * cos_lag - y * sin_lag, x * sin_lag + y * cos_lag, z]);
+        let cos_angle = position.dot(&bulge) / (position.norm() * bulge.norm());
+        let shape = ((1. + cos_angle) / 2.).powf(self.exponent / 2.);
+        min_density + (max_density - min_density) * shape
+    }
+}
+
+/// Approximate height above the reference ellipsoid of the central body, good to a few hundred
+/// metres for the Earth
+fn altitude(position: &Vector3, central_body: &CentralBody) -> f64 {
+    let r = position.norm();
+    let sin_latitude_2 = (position.elem[2] / r).powi(2);
+    r - central_body.equatorial_radius * (1. - central_body.flattening * sin_latitude_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::bodies;
+
+    fn above_equator(altitude: f64) -> Vector3 {
+        Vector3::new([bodies::EARTH.equatorial_radius + altitude, 0., 0.])
+    }
+
+    #[test]
+    fn exponential_test() {
+        let body = bodies::EARTH;
+        assert_relative_eq!(
+            Exponential.density(0., &above_equator(400e3), &body),
+            3.725e-12
+        );
+        assert_relative_eq!(
+            Exponential.density(0., &above_equator(425e3), &body),
+            3.725e-12 * (-25. / 58.515_f64).exp(),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(Exponential.density(0., &above_equator(0.), &body), 1.225);
+        assert_relative_eq!(
+            Exponential.density(0., &above_equator(1_100e3), &body),
+            3.019e-15 * (-100. / 268_f64).exp(),
+            max_relative = 1e-12
+        );
+        // Polar altitudes are measured from the polar radius
+        let pole = Vector3::new([0., 0., body.polar_radius() + 400e3]);
+        assert_relative_eq!(
+            Exponential.density(0., &pole, &body),
+            3.725e-12,
+            max_relative = 1e-9
+        );
+    }
+
+    #[test]
+    /// The density peaks at the bulge, 30° east of the Sun, bottoms out opposite it, and
+    /// interpolates exponentially between tabulated altitudes
+    fn harris_priester_test() {
+        let body = bodies::EARTH;
+        let model = HarrisPriester::with_exponent(4.);
+        let time = 1e8;
+        let sun = ephemerides::sun_position(time);
+        let sin_latitude_2 = (sun.elem[2] / sun.norm()).powi(2);
+        let surface = body.equatorial_radius * (1. - body.flattening * sin_latitude_2);
+        // Point at the Sun's declination, `angle` east of it
+        let at = |angle: f64, altitude: f64| {
+            let [x, y, z] = sun.elem;
+            let (sin, cos) = angle.sin_cos();
+            let mut position = Vector3::new([x * cos - y * sin, x * sin + y * cos, z]);
+            position.safe_normalize();
+            position * (surface + altitude)
+        };
+
+        let bulge = model.density(time, &at(BULGE_LAG, 400e3), &body);
+        let antipode = model.density(time, &-at(BULGE_LAG, 400e3), &body);
+        assert_relative_eq!(bulge, 7.492e-12, max_relative = 1e-6);
+        assert_relative_eq!(antipode, 2.249e-12, max_relative = 1e-6);
+        let sunward = model.density(time, &at(0., 400e3), &body);
+        assert!(antipode < sunward && sunward < bulge);
+
+        let between = model.density(time, &-at(BULGE_LAG, 410e3), &body);
+        assert_relative_eq!(
+            between,
+            (2.249e-12 * 1.558e-12_f64).sqrt(),
+            max_relative = 1e-6
+        );
+        assert_eq!(model.density(time, &above_equator(1_500e3), &body), 0.);
+    }
+}
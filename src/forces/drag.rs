@@ -0,0 +1,118 @@
+use super::*;
+
+/// Aerodynamic drag on a spacecraft moving through an atmosphere which turns with the central
+/// body, `a = -1/2 rho |v_rel| v_rel / B` for a ballistic coefficient `B = m / (C_D A)`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Drag<A> {
+    /// Mass over drag coefficient times area, in kg/m^2
+    pub ballistic_coefficient: f64,
+    pub atmosphere: A,
+    /// Instant at time zero of the integration, which overrides the epoch of the state when set
+    pub epoch: Option<Epoch>,
+}
+
+impl<A: Atmosphere> Drag<A> {
+    pub fn new(ballistic_coefficient: f64, atmosphere: A) -> Self {
+        Self {
+            ballistic_coefficient,
+            atmosphere,
+            epoch: None,
+        }
+    }
+
+    /// Drag on a spacecraft of the given mass in kg, drag coefficient and cross-sectional area in
+    /// m^2
+    pub fn from_spacecraft(mass: f64, drag_coefficient: f64, area: f64, atmosphere: A) -> Self {
+        Self::new(mass / (drag_coefficient * area), atmosphere)
+    }
+
+    pub fn with_epoch(self, epoch: Epoch) -> Self {
+        Self {
+            epoch: Some(epoch),
+            ..self
+        }
+    }
+
+    /// Velocity of the spacecraft relative to the co-rotating atmosphere
+    fn relative_velocity(state: &CartesianState) -> Vector3 {
+        let rotation = Vector3::new([0., 0., state.central_body.rotation_rate]);
+        state.velocity.clone() - rotation.cross(&state.position)
+    }
+}
+
+impl<A: Atmosphere> ForceModel for Drag<A> {
+    fn acceleration(&self, time: f64, state: &CartesianState) -> Vector3 {
+        let density = self.atmosphere.density(
+            tt_seconds(self.epoch, time, state),
+            &state.position,
+            &state.central_body,
+        );
+        if density == 0. {
+            return Vector3::new([0.; 3]);
+        }
+        let velocity = Self::relative_velocity(state);
+        let speed = velocity.norm();
+        velocity * (-0.5 * density * speed / self.ballistic_coefficient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::bodies;
+    use crate::integrators::{AdaptiveRungeKutta, Integrator, OrbitDynamics};
+    use crate::orbit::structs::COE;
+    use crate::orbit::Scaled;
+    use crate::testing::assert_array_eq_atol;
+
+    #[test]
+    /// An equatorial spacecraft moving with the atmosphere feels no drag, and one standing still
+    /// feels it along the wind
+    fn co_rotation_test() {
+        let body = bodies::EARTH;
+        let drag = Drag::new(50., Exponential);
+        let r = body.equatorial_radius + 300e3;
+        let co_rotating =
+            CartesianState::new([r, 0., 0.].into(), [0., r * body.rotation_rate, 0.].into());
+        assert_eq!(drag.acceleration(0., &co_rotating).norm(), 0.);
+
+        let still = CartesianState::new([r, 0., 0.].into(), [0., 0., 0.].into());
+        let wind = r * body.rotation_rate;
+        let density = Exponential.density(0., &still.position, &body);
+        assert_array_eq_atol(
+            &drag.acceleration(0., &still).elem,
+            &[0., 0.5 * density * wind.powi(2) / 50., 0.],
+            1e-18,
+        );
+        assert_eq!(
+            Drag::from_spacecraft(100., 2.2, 1., Exponential).ballistic_coefficient,
+            100. / 2.2
+        );
+    }
+
+    #[test]
+    /// In a uniform, still atmosphere a circular orbit decays at `da/dt = -rho sqrt(mu a) / B`
+    fn decay_test() {
+        let body = bodies::CentralBody {
+            rotation_rate: 0.,
+            ..bodies::EARTH
+        };
+        let density = 1e-11;
+        let ballistic_coefficient = 40.;
+        let uniform = move |_: f64, _: &Vector3, _: &bodies::CentralBody| density;
+        let dynamics = OrbitDynamics::new().with_force(Drag::new(ballistic_coefficient, uniform));
+        let coe = COE::new(6_700e3, 0., 0.9, 0., 0., 0.).with_central_body(body);
+        let time_of_flight = 3. * coe.period();
+        let solution = AdaptiveRungeKutta::dormand_prince_87(1e-6, 1e-12)
+            .integrate(&dynamics, 0., &CartesianState::from(&coe), time_of_flight)
+            .unwrap();
+        let decayed = COE::from(&solution.state);
+        let rate = -density * (body.mu * coe.semi_major_axis).sqrt() / ballistic_coefficient;
+        assert_relative_eq!(
+            decayed.semi_major_axis - coe.semi_major_axis,
+            rate * time_of_flight,
+            max_relative = 1e-3
+        );
+    }
+}
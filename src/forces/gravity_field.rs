@@ -0,0 +1,795 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::ops;
+use std::path::Path;
+
+use crate::bodies;
+use crate::frames;
+
+use super::*;
+
+/// Gravity field of the central body expanded in spherical harmonics, from fully normalized
+/// `C_nm` and `S_nm` coefficients such as those of EGM96 or EGM2008.
+///
+/// Only the non-spherical part of the field is evaluated, from degree 2 up, since
+/// `OrbitDynamics` already accounts for the point mass. The field turns with the central body
+/// about the z axis. About the Earth, for a state with an epoch, it is turned by the Earth rotation
+/// angle at that epoch; otherwise it turns at the body's `rotation_rate`, starting from
+/// `rotation_angle` at time zero. Precession, nutation and polar motion are ignored.
+///
+/// The solid harmonics are evaluated with the normalized form of Cunningham's recursions
+/// (Montenbruck and Gill, Satellite Orbits, section 3.2.4), which stays accurate to degrees in the
+/// thousands.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GravityField {
+    pub name: String,
+    /// Gravitational parameter the coefficients were fitted with, in m^3/s^2
+    pub mu: f64,
+    /// Reference radius of the coefficients, in m
+    pub radius: f64,
+    /// Angle in rad between the body-fixed and inertial x axes at time zero, for states without
+    /// an epoch or about bodies other than the Earth
+    pub rotation_angle: f64,
+    /// Degree and order the field is evaluated to
+    degree: usize,
+    order: usize,
+    /// Fully normalized coefficients, indexed by `triangle(n, m)`
+    cosine: Vec<f64>,
+    sine: Vec<f64>,
+}
+
+impl GravityField {
+    /// Field of the given maximum degree with every coefficient zero, to be filled in with
+    /// `set_coefficients`
+    pub fn new(name: impl Into<String>, mu: f64, radius: f64, max_degree: usize) -> Self {
+        let size = triangle(max_degree + 1, 0);
+        Self {
+            name: name.into(),
+            mu,
+            radius,
+            rotation_angle: 0.,
+            degree: max_degree,
+            order: max_degree,
+            cosine: vec![0.; size],
+            sine: vec![0.; size],
+        }
+    }
+
+    /// Reads a field in the ICGEM `.gfc` format. Time-variable terms are ignored, leaving the field
+    /// at its reference epoch.
+    pub fn read_gfc(reader: impl BufRead) -> Result<Self, GravityFieldError> {
+        let mut lines = reader.lines().enumerate();
+        let mut name = String::new();
+        let (mut mu, mut radius) = (None, None);
+        let mut normalized = true;
+        for (index, line) in lines.by_ref() {
+            let line = line?;
+            let mut words = line.split_whitespace();
+            let (Some(key), value) = (words.next(), words.next()) else {
+                continue;
+            };
+            match (key, value) {
+                ("end_of_head", _) => break,
+                ("modelname", Some(value)) => name = value.to_string(),
+                ("earth_gravity_constant" | "gravity_constant", Some(value)) => {
+                    mu = Some(parse_number(value, index)?);
+                }
+                ("radius", Some(value)) => radius = Some(parse_number(value, index)?),
+                ("norm", Some(value)) => normalized = value != "unnormalized",
+                _ => (),
+            }
+        }
+        let mu = mu.ok_or(GravityFieldError::MissingHeader("earth_gravity_constant"))?;
+        let radius = radius.ok_or(GravityFieldError::MissingHeader("radius"))?;
+
+        let mut coefficients = Vec::new();
+        for (index, line) in lines {
+            let line = line?;
+            let mut words = line.split_whitespace();
+            match words.next() {
+                Some("gfc" | "gfct") => coefficients.push(parse_coefficients(words, index)?),
+                _ => continue,
+            }
+        }
+        let mut field = Self::from_coefficients(name, mu, radius, &coefficients);
+        if !normalized {
+            field.normalize();
+        }
+        Ok(field)
+    }
+
+    /// Reads a field from a plain table of fully normalized coefficients, one `n m C S` row per
+    /// line with any further columns ignored, as EGM96 and EGM2008 are distributed by the NGA.
+    /// Exponents may be written with `D` as in Fortran.
+    pub fn read_table(
+        name: impl Into<String>,
+        mu: f64,
+        radius: f64,
+        reader: impl BufRead,
+    ) -> Result<Self, GravityFieldError> {
+        let mut coefficients = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                coefficients.push(parse_coefficients(line.split_whitespace(), index)?);
+            }
+        }
+        Ok(Self::from_coefficients(name, mu, radius, &coefficients))
+    }
+
+    /// Loads an ICGEM `.gfc` file, see `read_gfc`
+    pub fn load_gfc(path: impl AsRef<Path>) -> Result<Self, GravityFieldError> {
+        Self::read_gfc(BufReader::new(File::open(path)?))
+    }
+
+    /// Loads a plain coefficient table such as the NGA's, see `read_table`
+    pub fn load_table(
+        name: impl Into<String>,
+        mu: f64,
+        radius: f64,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, GravityFieldError> {
+        Self::read_table(name, mu, radius, BufReader::new(File::open(path)?))
+    }
+
+    fn from_coefficients(
+        name: impl Into<String>,
+        mu: f64,
+        radius: f64,
+        coefficients: &[(usize, usize, f64, f64)],
+    ) -> Self {
+        let max_degree = coefficients.iter().map(|(n, ..)| *n).max().unwrap_or(0);
+        let mut field = Self::new(name, mu, radius, max_degree);
+        for &(degree, order, cosine, sine) in coefficients {
+            field.set_coefficients(degree, order, cosine, sine);
+        }
+        field
+    }
+
+    /// Converts coefficients read as unnormalized to fully normalized ones
+    fn normalize(&mut self) {
+        for degree in 0..=self.max_degree() {
+            for order in 0..=degree {
+                let index = triangle(degree, order);
+                let normalization = normalization(degree, order);
+                self.cosine[index] /= normalization;
+                self.sine[index] /= normalization;
+            }
+        }
+    }
+
+    /// Highest degree of the coefficients held
+    pub fn max_degree(&self) -> usize {
+        triangle_degree(self.cosine.len())
+    }
+
+    /// Evaluates the field only up to the given degree and order, which are capped at
+    /// `max_degree`
+    pub fn truncated(self, degree: usize, order: usize) -> Self {
+        let degree = degree.min(self.max_degree());
+        Self {
+            degree,
+            order: order.min(degree),
+            ..self
+        }
+    }
+
+    pub fn with_rotation_angle(self, rotation_angle: f64) -> Self {
+        Self {
+            rotation_angle,
+            ..self
+        }
+    }
+
+    /// Fully normalized `(C_nm, S_nm)`
+    pub fn coefficients(&self, degree: usize, order: usize) -> (f64, f64) {
+        assert!(order <= degree && degree <= self.max_degree());
+        let index = triangle(degree, order);
+        (self.cosine[index], self.sine[index])
+    }
+
+    pub fn set_coefficients(&mut self, degree: usize, order: usize, cosine: f64, sine: f64) {
+        assert!(order <= degree && degree <= self.max_degree());
+        let index = triangle(degree, order);
+        self.cosine[index] = cosine;
+        self.sine[index] = sine;
+    }
+
+    /// Gravitational potential of the non-spherical part of the field at a body-fixed position,
+    /// in m^2/s^2, positive so the acceleration is its gradient
+    pub fn potential(&self, position: &Vector3) -> f64 {
+        let harmonics = self.solid_harmonics(position, 0);
+        self.sum(|degree, order, coefficient| (coefficient * harmonics[triangle(degree, order)]).re)
+    }
+
+    /// Acceleration due to the non-spherical part of the field at a body-fixed position
+    pub fn body_fixed_acceleration(&self, position: &Vector3) -> Vector3 {
+        let harmonics = self.solid_harmonics(position, 1);
+        let acceleration = AXES.map(|axis| {
+            self.sum(|degree, order, coefficient| {
+                Term::new(degree, order)
+                    .derivative(axis)
+                    .map(|term| term.evaluate(coefficient, &harmonics))
+                    .sum::<f64>()
+            })
+        });
+        Vector3::new(acceleration) * (1. / self.radius)
+    }
+
+    /// Gradient of `body_fixed_acceleration`, whose element `[i][j]` is the derivative of the
+    /// acceleration along axis `i` with respect to position along axis `j`
+    pub fn body_fixed_gradient(&self, position: &Vector3) -> [[f64; 3]; 3] {
+        let harmonics = self.solid_harmonics(position, 2);
+        let mut gradient = [[0.; 3]; 3];
+        for i in 0..3 {
+            for j in i..3 {
+                gradient[i][j] = self.sum(|degree, order, coefficient| {
+                    Term::new(degree, order)
+                        .derivative(AXES[i])
+                        .flat_map(|term| term.derivative(AXES[j]))
+                        .map(|term| term.evaluate(coefficient, &harmonics))
+                        .sum::<f64>()
+                }) / self.radius.powi(2);
+                gradient[j][i] = gradient[i][j];
+            }
+        }
+        gradient
+    }
+
+    /// `mu / R * sum(f(n, m, C_nm - i S_nm))` over the coefficients being evaluated
+    fn sum(&self, f: impl Fn(usize, usize, Complex) -> f64) -> f64 {
+        let mut total = 0.;
+        for degree in 2..=self.degree {
+            for order in 0..=degree.min(self.order) {
+                let index = triangle(degree, order);
+                let coefficient = Complex::new(self.cosine[index], -self.sine[index]);
+                if coefficient != Complex::default() {
+                    total += f(degree, order, coefficient);
+                }
+            }
+        }
+        self.mu / self.radius * total
+    }
+
+    /// Normalized solid harmonics `(R/r)^(n+1) P_nm(sin(latitude)) exp(i m longitude)` up to
+    /// `extra` degrees and orders beyond those being evaluated, as needed for derivatives
+    fn solid_harmonics(&self, position: &Vector3, extra: usize) -> Vec<Complex> {
+        let max_degree = self.degree + extra;
+        let max_order = self.order + extra;
+        let r2 = position.dot(position);
+        let [x, y, z] = position.elem.map(|elem| elem * self.radius / r2);
+        let ratio2 = self.radius.powi(2) / r2;
+
+        let mut harmonics = vec![Complex::default(); triangle(max_degree + 1, 0)];
+        harmonics[0] = Complex::new(self.radius / r2.sqrt(), 0.);
+        for order in 0..=max_order {
+            let m = order as f64;
+            if order > 0 {
+                let scale =
+                    ((2. * m + 1.) / (2. * m)).sqrt() * if order == 1 { 2_f64.sqrt() } else { 1. };
+                harmonics[triangle(order, order)] =
+                    Complex::new(x * scale, y * scale) * harmonics[triangle(order - 1, order - 1)];
+            }
+            for degree in order + 1..=max_degree {
+                let n = degree as f64;
+                let a = ((2. * n + 1.) * (2. * n - 1.) / ((n - m) * (n + m))).sqrt();
+                let mut harmonic = harmonics[triangle(degree - 1, order)] * (a * z);
+                if degree >= order + 2 {
+                    let b = ((2. * n + 1.) * (n + m - 1.) * (n - m - 1.)
+                        / ((2. * n - 3.) * (n + m) * (n - m)))
+                        .sqrt();
+                    harmonic = harmonic - harmonics[triangle(degree - 2, order)] * (b * ratio2);
+                }
+                harmonics[triangle(degree, order)] = harmonic;
+            }
+        }
+        harmonics
+    }
+
+    /// Rotation from inertial to body-fixed axes for `state` at `time`, as `(cos, sin)` of the
+    /// angle
+    fn rotation(&self, time: f64, state: &CartesianState) -> (f64, f64) {
+        let angle = match state.epoch {
+            Some(epoch) if state.central_body.name == bodies::EARTH.name => {
+                frames::earth_rotation_angle(epoch)
+            }
+            _ => self.rotation_angle + state.central_body.rotation_rate * time,
+        };
+        let (sin, cos) = angle.sin_cos();
+        (cos, sin)
+    }
+}
+
+impl ForceModel for GravityField {
+    fn acceleration(&self, time: f64, state: &CartesianState) -> Vector3 {
+        let (cos, sin) = self.rotation(time, state);
+        let [x, y, z] = state.position.elem;
+        let body_fixed = Vector3::new([cos * x + sin * y, cos * y - sin * x, z]);
+        let [ax, ay, az] = self.body_fixed_acceleration(&body_fixed).elem;
+        Vector3::new([cos * ax - sin * ay, sin * ax + cos * ay, az])
+    }
+}
+
+#[derive(Debug)]
+pub enum GravityFieldError {
+    Io(io::Error),
+    /// A malformed line, numbered from 1
+    Parse {
+        line: usize,
+        reason: &'static str,
+    },
+    /// A required header keyword of a `.gfc` file is missing
+    MissingHeader(&'static str),
+}
+
+impl fmt::Display for GravityFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "reading gravity field: {err}"),
+            Self::Parse { line, reason } => write!(f, "gravity field line {line}: {reason}"),
+            Self::MissingHeader(keyword) => write!(f, "gravity field header lacks {keyword}"),
+        }
+    }
+}
+
+impl Error for GravityFieldError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for GravityFieldError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+fn parse_number(word: &str, index: usize) -> Result<f64, GravityFieldError> {
+    word.replace(['D', 'd'], "E")
+        .parse()
+        .map_err(|_| GravityFieldError::Parse {
+            line: index + 1,
+            reason: "invalid number",
+        })
+}
+
+/// `n m C S` from the start of a line
+fn parse_coefficients<'a>(
+    mut words: impl Iterator<Item = &'a str>,
+    index: usize,
+) -> Result<(usize, usize, f64, f64), GravityFieldError> {
+    let error = |reason| GravityFieldError::Parse {
+        line: index + 1,
+        reason,
+    };
+    let mut next = || words.next().ok_or(error("expected n m C S"));
+    let degree = next()?.parse().map_err(|_| error("invalid degree"))?;
+    let order = next()?.parse().map_err(|_| error("invalid order"))?;
+    let cosine = parse_number(next()?, index)?;
+    let sine = parse_number(next()?, index)?;
+    if order > degree {
+        return Err(error("order exceeds degree"));
+    }
+    Ok((degree, order, cosine, sine))
+}
+
+/// Index of degree `n` and order `m` in a triangular array stored by degree
+fn triangle(degree: usize, order: usize) -> usize {
+    degree * (degree + 1) / 2 + order
+}
+
+/// Highest degree a triangular array of `len` elements holds
+fn triangle_degree(len: usize) -> usize {
+    (0..).find(|degree| triangle(degree + 1, 0) >= len).unwrap()
+}
+
+/// Factor relating unnormalized to fully normalized coefficients, `C_nm = N_nm * C_nm_normalized`
+fn normalization(degree: usize, order: usize) -> f64 {
+    let factorial_ratio: f64 = (degree - order + 1..=degree + order)
+        .map(|k| 1. / k as f64)
+        .product();
+    let kronecker = if order == 0 { 1. } else { 2. };
+    (kronecker * (2 * degree + 1) as f64 * factorial_ratio).sqrt()
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+}
+
+impl ops::Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl ops::Mul<f64> for Complex {
+    type Output = Self;
+
+    fn mul(self, scale: f64) -> Self {
+        Self::new(self.re * scale, self.im * scale)
+    }
+}
+
+impl ops::Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+const AXES: [Axis; 3] = [Axis::X, Axis::Y, Axis::Z];
+
+/// A multiple of a normalized solid harmonic, or of its conjugate, in the derivatives of another
+#[derive(Clone, Copy, Debug)]
+struct Term {
+    scale: Complex,
+    degree: usize,
+    order: usize,
+    conjugate: bool,
+}
+
+impl Term {
+    fn new(degree: usize, order: usize) -> Self {
+        Self {
+            scale: Complex::new(1., 0.),
+            degree,
+            order,
+            conjugate: false,
+        }
+    }
+
+    /// Derivative along `axis` times the reference radius, which is a combination of at most two
+    /// harmonics of the next degree. These are the recurrences behind Cunningham's acceleration
+    /// formulas, with the normalization folded in.
+    fn derivative(self, axis: Axis) -> impl Iterator<Item = Self> {
+        let (n, m) = (self.degree as f64, self.order as f64);
+        let ratio = (2. * n + 1.) / (2. * n + 3.);
+        let term = |scale: Complex, order, conjugate: bool| {
+            // The derivative of a conjugate is the conjugate of the derivative
+            let scale = if self.conjugate { scale.conj() } else { scale };
+            Self {
+                scale: self.scale * scale,
+                degree: self.degree + 1,
+                order,
+                conjugate: self.conjugate != conjugate,
+            }
+        };
+        let (up, down) = match axis {
+            Axis::Z => {
+                let scale = -(ratio * (n + m + 1.) * (n - m + 1.)).sqrt();
+                (term(Complex::new(scale, 0.), self.order, false), None)
+            }
+            Axis::X | Axis::Y => {
+                let kronecker = if self.order == 0 { 1. } else { 2. };
+                let up = 0.5 * (0.5 * kronecker * ratio * (n + m + 1.) * (n + m + 2.)).sqrt();
+                let (up, down) = if self.order == 0 {
+                    // Reflecting the negative order gives the conjugate of the up term
+                    (up, -up)
+                } else {
+                    let kronecker = if self.order == 1 { 1. } else { 2. };
+                    let down = 0.5 * (2. / kronecker * ratio * (n - m + 1.) * (n - m + 2.)).sqrt();
+                    (up, down)
+                };
+                let (up, down) = match axis {
+                    Axis::X => (Complex::new(-up, 0.), Complex::new(down, 0.)),
+                    _ => (Complex::new(0., up), Complex::new(0., down)),
+                };
+                let down = if self.order == 0 {
+                    term(down, 1, true)
+                } else {
+                    term(down, self.order - 1, false)
+                };
+                (term(up, self.order + 1, false), Some(down))
+            }
+        };
+        std::iter::once(up).chain(down)
+    }
+
+    /// `Re(coefficient * term)`
+    fn evaluate(&self, coefficient: Complex, harmonics: &[Complex]) -> f64 {
+        let harmonic = harmonics[triangle(self.degree, self.order)];
+        let harmonic = if self.conjugate {
+            harmonic.conj()
+        } else {
+            harmonic
+        };
+        (coefficient * self.scale * harmonic).re
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::bodies;
+    use crate::forces::ZonalHarmonics;
+    use crate::testing::assert_array_eq_atol;
+
+    /// The start of EGM2008 in ICGEM format, tide free
+    const EGM2008_GFC: &str = "\
+product_type              gravity_field
+modelname                 EGM2008
+earth_gravity_constant    0.3986004415E+15
+radius                    0.63781363E+07
+max_degree                  2190
+errors                    calibrated
+norm                      fully_normalized
+tide_system               tide_free
+
+key    L    M             C                  S                sigma C        sigma S
+end_of_head ==============================================================================
+gfc    0    0  1.000000000000E+00  0.000000000000E+00  0.0000E+00  0.0000E+00
+gfc    2    0 -0.484165143790815E-03  0.000000000000E+00  0.7481239490E-11  0.0000E+00
+gfc    2    1 -0.206615509074176E-09  0.138441389137979E-08  0.7063781502E-11  0.7348463310E-11
+gfc    2    2  0.243938357328313E-05 -0.140027370385934E-05  0.7220430357E-11  0.7325191240E-11
+gfc    3    0  0.957161207093473E-06  0.000000000000E+00  0.5731430751E-11  0.0000E+00
+gfc    3    1  0.203046201047864E-05  0.248200415856872E-06  0.5787487930E-11  0.5891346472E-11
+gfc    3    2  0.904787894809528E-06 -0.619005475177618E-06  0.6077095702E-11  0.6152227742E-11
+gfc    3    3  0.721321757121568E-06  0.141434926192941E-05  0.5783378856E-11  0.5808778658E-11
+";
+
+    fn egm2008() -> GravityField {
+        GravityField::read_gfc(EGM2008_GFC.as_bytes()).unwrap()
+    }
+
+    /// A field with every coefficient up to degree 8 nonzero, to exercise every branch
+    fn dense_field() -> GravityField {
+        let mut field = GravityField::new("dense", bodies::EARTH.mu, 6_378_137., 8);
+        for degree in 2..=8 {
+            for order in 0..=degree {
+                let cosine = 1e-6 * ((degree * 7 + order * 3) as f64).sin();
+                let sine = if order == 0 {
+                    0.
+                } else {
+                    1e-6 * ((degree * 5 + order * 11) as f64).cos()
+                };
+                field.set_coefficients(degree, order, cosine, sine);
+            }
+        }
+        field
+    }
+
+    fn position() -> Vector3 {
+        Vector3::new([4_100_000., -3_200_000., 4_700_000.])
+    }
+
+    #[test]
+    fn read_gfc_test() {
+        let field = egm2008();
+        assert_eq!(field.name, "EGM2008");
+        assert_eq!(field.mu, 3.986_004_415e14);
+        assert_eq!(field.radius, 6_378_136.3);
+        assert_eq!(field.max_degree(), 3);
+        assert_eq!(
+            field.coefficients(2, 2),
+            (0.243_938_357_328_313e-5, -0.140_027_370_385_934e-5)
+        );
+
+        let missing = GravityField::read_gfc("radius 1\nend_of_head\n".as_bytes());
+        assert!(matches!(
+            missing,
+            Err(GravityFieldError::MissingHeader("earth_gravity_constant"))
+        ));
+        let malformed = EGM2008_GFC.replace("gfc    3    3", "gfc    3    4");
+        assert!(matches!(
+            GravityField::read_gfc(malformed.as_bytes()),
+            Err(GravityFieldError::Parse { line: 19, .. })
+        ));
+    }
+
+    #[test]
+    /// The NGA's tables use Fortran exponents, and unnormalized `.gfc` files are normalized on
+    /// reading
+    fn read_table_test() {
+        let table = "    2    0 -0.484165143790815D-03  0.000000000000D+00\n\n\
+                     2    2  0.243938357328313D-05 -0.140027370385934D-05\n";
+        let field =
+            GravityField::read_table("EGM2008", 3.986e14, 6_378_136.3, table.as_bytes()).unwrap();
+        assert_eq!(field.coefficients(2, 0), (-0.484_165_143_790_815e-3, 0.));
+        assert_eq!(field.coefficients(2, 1), (0., 0.));
+
+        let j2 = bodies::EARTH.zonal(2);
+        let unnormalized = format!(
+            "earth_gravity_constant 3.986e14\nradius 6378137\nnorm unnormalized\nend_of_head\n\
+             gfc 2 0 {} 0\ngfc 2 2 1e-6 -2e-6\n",
+            -j2
+        );
+        let field = GravityField::read_gfc(unnormalized.as_bytes()).unwrap();
+        assert_relative_eq!(field.coefficients(2, 0).0, -j2 / 5_f64.sqrt());
+        let (cosine, sine) = field.coefficients(2, 2);
+        assert_relative_eq!(
+            cosine,
+            1e-6 * 12_f64.sqrt() / 5_f64.sqrt(),
+            max_relative = 1e-14
+        );
+        assert_relative_eq!(sine, -2. * cosine, max_relative = 1e-14);
+    }
+
+    #[test]
+    /// Both formats load from disk as they read from memory
+    fn load_test() {
+        let directory =
+            std::env::temp_dir().join(format!("orbitrs-gravity-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let gfc = directory.join("EGM2008.gfc");
+        std::fs::write(&gfc, EGM2008_GFC).unwrap();
+        assert_eq!(GravityField::load_gfc(&gfc).unwrap(), egm2008());
+
+        let table = directory.join("EGM2008_to2190_TideFree");
+        std::fs::write(
+            &table,
+            "    2    0 -0.484165143790815D-03  0.000000000000D+00\n",
+        )
+        .unwrap();
+        let field = GravityField::load_table("EGM2008", 3.986e14, 6_378_136.3, &table).unwrap();
+        assert_eq!(field.coefficients(2, 0), (-0.484_165_143_790_815e-3, 0.));
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert!(matches!(
+            GravityField::load_table("EGM2008", 3.986e14, 6_378_136.3, &table),
+            Err(GravityFieldError::Io(_))
+        ));
+    }
+
+    #[test]
+    /// The C22 potential has the closed form `mu/R (R/r)^3 C22 N22 3 cos^2(latitude) cos(2 lon)`
+    fn potential_test() {
+        let mut field = GravityField::new("C22", 3.986e14, 6_378_137., 2);
+        field.set_coefficients(2, 2, 2.4e-6, 0.);
+        let position = position();
+        let r = position.norm();
+        let [x, y, z] = position.elem;
+        let cos_latitude_2 = 1. - (z / r).powi(2);
+        let cos_2_longitude = (x * x - y * y) / (x * x + y * y);
+        let expected = field.mu / field.radius
+            * (field.radius / r).powi(3)
+            * 2.4e-6
+            * (5. / 12_f64).sqrt()
+            * 3.
+            * cos_latitude_2
+            * cos_2_longitude;
+        assert_relative_eq!(field.potential(&position), expected, max_relative = 1e-13);
+    }
+
+    #[test]
+    /// A purely zonal field matches the zonal harmonics force, given `J_n = -sqrt(2n + 1) C_n0`
+    fn zonal_test() {
+        let body = bodies::EARTH;
+        let mut field = GravityField::new("zonal", body.mu, body.equatorial_radius, 6);
+        for degree in 2..=6 {
+            let cosine = -body.zonal(degree) / ((2 * degree + 1) as f64).sqrt();
+            field.set_coefficients(degree, 0, cosine, 0.);
+        }
+        let state = CartesianState::new(position(), [0., 7_000., 0.].into());
+        assert_array_eq_atol(
+            &field.acceleration(0., &state).elem,
+            &ZonalHarmonics::j2_to_j6().acceleration(0., &state).elem,
+            1e-17,
+        );
+    }
+
+    #[test]
+    /// The acceleration is the gradient of the potential, and the gradient the derivative of the
+    /// acceleration, checked by central differences
+    fn derivatives_test() {
+        let field = dense_field();
+        let position = position();
+        let delta = 1.;
+        let shifted = |axis: usize, sign: f64| {
+            let mut elem = position.elem;
+            elem[axis] += sign * delta;
+            Vector3::new(elem)
+        };
+
+        let acceleration = field.body_fixed_acceleration(&position);
+        let gradient = field.body_fixed_gradient(&position);
+        for axis in 0..3 {
+            let (plus, minus) = (shifted(axis, 1.), shifted(axis, -1.));
+            let potential_slope = (field.potential(&plus) - field.potential(&minus)) / (2. * delta);
+            assert_relative_eq!(
+                acceleration.elem[axis],
+                potential_slope,
+                max_relative = 1e-6
+            );
+            let plus = field.body_fixed_acceleration(&plus);
+            let minus = field.body_fixed_acceleration(&minus);
+            for (row, derivatives) in gradient.iter().enumerate() {
+                let slope = (plus.elem[row] - minus.elem[row]) / (2. * delta);
+                assert_relative_eq!(derivatives[axis], slope, max_relative = 1e-5);
+            }
+        }
+        // Outside the masses the potential is harmonic, so the gradient is traceless
+        let trace = gradient[0][0] + gradient[1][1] + gradient[2][2];
+        assert!(trace.abs() < 1e-12 * gradient[2][2].abs());
+    }
+
+    #[test]
+    /// Turning the body a quarter turn is the same as moving the satellite a quarter turn back
+    fn rotation_test() {
+        let field = egm2008().with_rotation_angle(0.3);
+        let body = bodies::EARTH;
+        let time = (std::f64::consts::FRAC_PI_2 - 0.3) / body.rotation_rate;
+        let state = CartesianState::new([7e6, 0., 1e6].into(), [0., 7_000., 0.].into());
+        let [ax, ay, az] = field.acceleration(time, &state).elem;
+        let body_fixed = field.body_fixed_acceleration(&Vector3::new([0., -7e6, 1e6]));
+        assert_array_eq_atol(&[ay, -ax, az], &body_fixed.elem, 1e-15);
+    }
+
+    #[test]
+    /// About the Earth, a state with an epoch sees the field turned by the Earth rotation angle
+    /// at that epoch, whatever the integration time and `rotation_angle`
+    fn epoch_test() {
+        let field = egm2008().with_rotation_angle(0.3);
+        let epoch: Epoch = "2024-03-20T03:06:00Z".parse().unwrap();
+        let angle = frames::earth_rotation_angle(epoch);
+        let state =
+            CartesianState::new([7e6, 0., 1e6].into(), [0., 7_000., 0.].into()).with_epoch(epoch);
+        let timed = field.acceleration(1_234., &state);
+        let untimed = CartesianState {
+            epoch: None,
+            ..state.clone()
+        };
+        let expected = field
+            .clone()
+            .with_rotation_angle(angle)
+            .acceleration(0., &untimed);
+        assert_array_eq_atol(&timed.elem, &expected.elem, 1e-18);
+        let later = state.clone().with_epoch(epoch + 3_600.);
+        assert!((field.acceleration(1_234., &later) - timed).norm() > 1e-9);
+    }
+
+    #[test]
+    /// Truncating at degree 2 and order 0 keeps only J2, and high degrees stay finite
+    fn truncation_test() {
+        let field = egm2008().truncated(2, 0);
+        let state = CartesianState::new(position(), [0., 7_000., 0.].into());
+        let j2 = bodies::CentralBody {
+            mu: field.mu,
+            equatorial_radius: field.radius,
+            zonal_harmonics: &[0.484_165_143_790_815e-3 * 2.236_067_977_499_79],
+            ..bodies::EARTH
+        };
+        assert_array_eq_atol(
+            &field.acceleration(0., &state).elem,
+            &J2.acceleration(0., &state.clone().with_central_body(j2))
+                .elem,
+            1e-15,
+        );
+
+        let mut deep = GravityField::new("deep", 3.986e14, 6_378_137., 1_000);
+        deep.set_coefficients(1_000, 1_000, 1e-9, 1e-9);
+        deep.set_coefficients(1_000, 3, 1e-9, 1e-9);
+        let acceleration = deep.body_fixed_acceleration(&Vector3::new([6.4e6, 1e5, 1e5]));
+        assert!(acceleration.elem.iter().all(|elem| elem.is_finite()));
+    }
+}
@@ -0,0 +1,48 @@
+//! Perturbing forces for numerical propagation with [`OrbitDynamics`], and the analytic secular
+//! rates of the orbital elements they cause.
+//!
+//! Force models act on `CartesianState`s expressed in an inertial frame whose z axis is the spin
+//! axis of the central body. They take the gravitational parameter, radius and harmonics from the
+//! state's `central_body`, except for a [`GravityField`], which carries its own coefficients.
+//! Forces which depend on the date, such as drag and third bodies, take it from the state's
+//! `epoch` unless given an epoch of their own for time zero.
+//!
+//! [`OrbitDynamics`]: crate::integrators::OrbitDynamics
+
+use crate::bodies::CentralBody;
+use crate::integrators::ForceModel;
+use crate::orbit::structs::CartesianState;
+use crate::time::{Epoch, TimeScale};
+use crate::vector::Vector3;
+
+mod atmosphere;
+mod drag;
+mod gravity_field;
+mod radiation;
+mod secular;
+mod space_weather;
+mod third_body;
+mod zonal;
+
+pub use atmosphere::{Atmosphere, Exponential, HarrisPriester};
+pub use drag::Drag;
+pub use gravity_field::{GravityField, GravityFieldError};
+pub use radiation::{
+    shadow_factor, Attitude, Cannonball, Plate, Plates, RadiationGeometry, Shadow,
+    SolarRadiationPressure, SOLAR_PRESSURE,
+};
+pub use secular::{arg_peri_rate, mean_anomaly_rate, raan_rate};
+pub use space_weather::{SpaceWeather, SpaceWeatherData, SpaceWeatherError};
+pub use third_body::{third_body_acceleration, ThirdBody};
+pub use zonal::{ZonalHarmonics, J2};
+
+/// TT seconds since J2000 at `time` seconds into an integration of `state`, which is the state's
+/// own epoch unless `epoch` overrides it as the instant at time zero. A state without an epoch
+/// starts at J2000.
+fn tt_seconds(epoch: Option<Epoch>, time: f64, state: &CartesianState) -> f64 {
+    match (epoch, state.epoch) {
+        (Some(epoch), _) => epoch.seconds_since_j2000(TimeScale::TT) + time,
+        (None, Some(epoch)) => epoch.seconds_since_j2000(TimeScale::TT),
+        (None, None) => time,
+    }
+}
@@ -0,0 +1,385 @@
+use crate::bodies;
+use crate::constants::{AU, SOLAR_IRRADIANCE, SPEED_OF_LIGHT};
+use crate::ephemerides;
+use crate::quaternions::Quaternion;
+
+use super::*;
+
+/// Radiation pressure of sunlight at 1 AU, in N/m^2
+pub const SOLAR_PRESSURE: f64 = SOLAR_IRRADIANCE / SPEED_OF_LIGHT;
+
+/// Solar radiation pressure on a spacecraft of some `RadiationGeometry`, scaled with the inverse
+/// square of its distance from the Sun and by how much of the Sun the central body hides.
+///
+/// The Sun's position comes from `ephemerides::sun_position`, which is geocentric, so the central
+/// body must be the Earth.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolarRadiationPressure<G> {
+    pub geometry: G,
+    pub shadow: Shadow,
+    /// Instant at time zero of the integration, which overrides the epoch of the state when set
+    pub epoch: Option<Epoch>,
+}
+
+impl<G: RadiationGeometry> SolarRadiationPressure<G> {
+    /// Radiation pressure with a conical shadow model
+    pub fn new(geometry: G) -> Self {
+        Self {
+            geometry,
+            shadow: Shadow::Conical,
+            epoch: None,
+        }
+    }
+
+    pub fn with_shadow(self, shadow: Shadow) -> Self {
+        Self { shadow, ..self }
+    }
+
+    pub fn with_epoch(self, epoch: Epoch) -> Self {
+        Self {
+            epoch: Some(epoch),
+            ..self
+        }
+    }
+}
+
+impl<G: RadiationGeometry> ForceModel for SolarRadiationPressure<G> {
+    fn acceleration(&self, time: f64, state: &CartesianState) -> Vector3 {
+        let sun = ephemerides::sun_position(tt_seconds(self.epoch, time, state));
+        let illumination = shadow_factor(self.shadow, &state.position, &sun, &state.central_body);
+        if illumination == 0. {
+            return Vector3::new([0.; 3]);
+        }
+        let mut direction = sun - state.position.clone();
+        let distance = direction.norm();
+        direction.safe_normalize();
+        let pressure = illumination * SOLAR_PRESSURE * (AU / distance).powi(2);
+        self.geometry
+            .acceleration(time, state, &direction, pressure)
+    }
+}
+
+/// Shape and surface properties of a spacecraft, which set the acceleration sunlight gives it
+pub trait RadiationGeometry {
+    /// Acceleration under a radiation `pressure` in N/m^2 coming from the unit `sun_direction`
+    fn acceleration(
+        &self,
+        time: f64,
+        state: &CartesianState,
+        sun_direction: &Vector3,
+        pressure: f64,
+    ) -> Vector3;
+}
+
+/// A sphere, or a spacecraft treated as one, which is pushed straight away from the Sun
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cannonball {
+    /// Radiation pressure coefficient, from 1 for a perfect absorber to 2 for a perfect mirror
+    pub reflectivity: f64,
+    /// Cross-sectional area over mass, in m^2/kg
+    pub area_to_mass: f64,
+}
+
+impl Cannonball {
+    pub fn new(reflectivity: f64, area_to_mass: f64) -> Self {
+        Self {
+            reflectivity,
+            area_to_mass,
+        }
+    }
+}
+
+impl RadiationGeometry for Cannonball {
+    fn acceleration(
+        &self,
+        _: f64,
+        _: &CartesianState,
+        sun_direction: &Vector3,
+        pressure: f64,
+    ) -> Vector3 {
+        sun_direction.clone() * (-pressure * self.reflectivity * self.area_to_mass)
+    }
+}
+
+/// Flat surface of a spacecraft, lit only from the side its normal points to
+#[derive(Clone, Debug, PartialEq)]
+pub struct Plate {
+    /// Area in m^2
+    pub area: f64,
+    /// Outward unit normal in the body frame
+    pub normal: Vector3,
+    /// Fractions of the incoming light reflected specularly and diffusely, the rest being
+    /// absorbed
+    pub specular: f64,
+    pub diffuse: f64,
+}
+
+impl Plate {
+    /// Plate of the given area facing along `normal`, which is normalized
+    pub fn new(area: f64, mut normal: Vector3, specular: f64, diffuse: f64) -> Self {
+        normal.safe_normalize();
+        Self {
+            area,
+            normal,
+            specular,
+            diffuse,
+        }
+    }
+}
+
+/// Orientation of a spacecraft's body frame over time
+pub trait Attitude {
+    /// Rotation taking body-frame vectors to the inertial frame with `Quaternion::rotated_vec_alibi`
+    fn attitude(&self, time: f64, state: &CartesianState) -> Quaternion;
+}
+
+/// A fixed orientation in inertial space
+impl Attitude for Quaternion {
+    fn attitude(&self, _: f64, _: &CartesianState) -> Quaternion {
+        self.clone()
+    }
+}
+
+impl<F> Attitude for F
+where
+    F: Fn(f64, &CartesianState) -> Quaternion,
+{
+    fn attitude(&self, time: f64, state: &CartesianState) -> Quaternion {
+        self(time, state)
+    }
+}
+
+/// A spacecraft modelled as a set of flat plates, oriented by its attitude. Plates don't shadow
+/// each other.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Plates<A> {
+    pub plates: Vec<Plate>,
+    /// Mass in kg
+    pub mass: f64,
+    pub attitude: A,
+}
+
+impl<A: Attitude> Plates<A> {
+    pub fn new(mass: f64, attitude: A) -> Self {
+        Self {
+            plates: Vec::new(),
+            mass,
+            attitude,
+        }
+    }
+
+    pub fn with_plate(mut self, plate: Plate) -> Self {
+        self.plates.push(plate);
+        self
+    }
+}
+
+impl<A: Attitude> RadiationGeometry for Plates<A> {
+    /// Montenbruck and Gill, Satellite Orbits, eq. 3.75, summed over the lit plates
+    fn acceleration(
+        &self,
+        time: f64,
+        state: &CartesianState,
+        sun_direction: &Vector3,
+        pressure: f64,
+    ) -> Vector3 {
+        let attitude = self.attitude.attitude(time, state);
+        let mut force = Vector3::new([0.; 3]);
+        for plate in &self.plates {
+            let normal = attitude.rotated_vec_alibi(&plate.normal);
+            let cos_incidence = normal.dot(sun_direction);
+            if cos_incidence <= 0. {
+                continue;
+            }
+            let along_sun = sun_direction.clone() * (1. - plate.specular);
+            let along_normal =
+                normal * (2. * (plate.specular * cos_incidence + plate.diffuse / 3.));
+            force = force + (along_sun + along_normal) * (-pressure * plate.area * cos_incidence);
+        }
+        force * (1. / self.mass)
+    }
+}
+
+/// How the shadow of the central body is modelled
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Shadow {
+    /// Always in sunlight
+    None,
+    /// Sharp-edged shadow of a cylinder as wide as the body, behind it
+    Cylindrical,
+    /// Umbra and penumbra of a spherical body lit by the disc of the Sun
+    #[default]
+    Conical,
+}
+
+/// Fraction of the Sun's disc visible from `position`, both positions being relative to the
+/// central body. The conical model follows Montenbruck and Gill, Satellite Orbits, section 3.4.2.
+pub fn shadow_factor(
+    shadow: Shadow,
+    position: &Vector3,
+    sun: &Vector3,
+    central_body: &CentralBody,
+) -> f64 {
+    let radius = central_body.equatorial_radius;
+    match shadow {
+        Shadow::None => 1.,
+        Shadow::Cylindrical => {
+            let mut sun_direction = sun.clone();
+            sun_direction.safe_normalize();
+            let along = position.dot(&sun_direction);
+            let across = (position.clone() - sun_direction * along).norm();
+            if along < 0. && across < radius {
+                0.
+            } else {
+                1.
+            }
+        }
+        Shadow::Conical => {
+            let to_sun = sun.clone() - position.clone();
+            let sun_distance = to_sun.norm();
+            let distance = position.norm();
+            // Apparent radii of the Sun and the body, and the angle between their centres
+            let a = (bodies::SUN.equatorial_radius / sun_distance).asin();
+            let b = (radius / distance).min(1.).asin();
+            let c = (-position.dot(&to_sun) / (distance * sun_distance))
+                .clamp(-1., 1.)
+                .acos();
+            if c >= a + b {
+                1.
+            } else if c <= b - a {
+                0.
+            } else if c <= a - b {
+                // Annular eclipse, with the body wholly inside the Sun's disc
+                1. - (b / a).powi(2)
+            } else {
+                let x = (c.powi(2) + a.powi(2) - b.powi(2)) / (2. * c);
+                let y = (a.powi(2) - x.powi(2)).sqrt();
+                let overlap = a.powi(2) * (x / a).acos() + b.powi(2) * ((c - x) / b).acos() - c * y;
+                1. - overlap / (std::f64::consts::PI * a.powi(2))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f64::consts::FRAC_PI_2;
+
+    use crate::testing::assert_array_eq_atol;
+
+    fn sun() -> Vector3 {
+        Vector3::new([AU, 0., 0.])
+    }
+
+    fn state(position: [f64; 3]) -> CartesianState {
+        CartesianState::new(position.into(), [0., 7_500., 0.].into())
+    }
+
+    #[test]
+    /// Behind the Earth is dark and in front of it is lit, and the conical shadow fades through
+    /// the penumbra between them
+    fn shadow_test() {
+        let body = bodies::EARTH;
+        let r = 7e6;
+        let behind = Vector3::new([-r, 0., 0.]);
+        let in_front = Vector3::new([r, 0., 0.]);
+        let beside = Vector3::new([0., r, 0.]);
+        for shadow in [Shadow::Cylindrical, Shadow::Conical] {
+            assert_eq!(shadow_factor(shadow, &behind, &sun(), &body), 0.);
+            assert_eq!(shadow_factor(shadow, &in_front, &sun(), &body), 1.);
+            assert_eq!(shadow_factor(shadow, &beside, &sun(), &body), 1.);
+        }
+        assert_eq!(shadow_factor(Shadow::None, &behind, &sun(), &body), 1.);
+
+        // Just at the edge of the cylinder the conical shadow is partial
+        let edge = Vector3::new([-r, body.equatorial_radius, 0.]);
+        let partial = shadow_factor(Shadow::Conical, &edge, &sun(), &body);
+        assert!(partial > 0.2 && partial < 0.8, "{partial}");
+
+        // Far enough behind, the Earth only covers part of the Sun
+        let far = Vector3::new([-1e10, 0., 0.]);
+        let annular = shadow_factor(Shadow::Conical, &far, &sun(), &body);
+        let sun_radius = bodies::SUN.equatorial_radius / (AU + 1e10);
+        let earth_radius = body.equatorial_radius / 1e10;
+        assert_relative_eq!(
+            annular,
+            1. - (earth_radius / sun_radius).powi(2),
+            max_relative = 1e-6
+        );
+    }
+
+    #[test]
+    fn cannonball_test() {
+        let cannonball = Cannonball::new(1.3, 0.02);
+        let acceleration =
+            cannonball.acceleration(0., &state([0.; 3]), &Vector3::new([0., 1., 0.]), 4.5e-6);
+        assert_array_eq_atol(&acceleration.elem, &[0., -4.5e-6 * 1.3 * 0.02, 0.], 1e-20);
+    }
+
+    #[test]
+    /// An absorbing plate is pushed along the light, a mirror along its normal, and a plate facing
+    /// away isn't pushed at all
+    fn plates_test() {
+        let incidence = 0.5_f64;
+        let sun_direction = Vector3::new([incidence.cos(), incidence.sin(), 0.]);
+        let state = state([7e6, 0., 0.]);
+        let pressure = 4.5e-6;
+        let absorber = Plates::new(10., Quaternion::identity()).with_plate(Plate::new(
+            2.,
+            [1., 0., 0.].into(),
+            0.,
+            0.,
+        ));
+        let expected = sun_direction.clone() * (-pressure * 2. * incidence.cos() / 10.);
+        assert_array_eq_atol(
+            &absorber
+                .acceleration(0., &state, &sun_direction, pressure)
+                .elem,
+            &expected.elem,
+            1e-20,
+        );
+
+        // A quarter turn about z points the -y mirror along +x, towards the light, and the +y one
+        // away from it
+        let turned = Quaternion::from_angle_axis(FRAC_PI_2, &[0., 0., 1.]);
+        let mirror = Plates::new(10., turned)
+            .with_plate(Plate::new(2., [0., 1., 0.].into(), 1., 0.))
+            .with_plate(Plate::new(2., [0., -1., 0.].into(), 1., 0.));
+        let magnitude = 2. * pressure * 2. * incidence.cos().powi(2) / 10.;
+        assert_array_eq_atol(
+            &mirror
+                .acceleration(0., &state, &sun_direction, pressure)
+                .elem,
+            &[-magnitude, 0., 0.],
+            1e-20,
+        );
+    }
+
+    #[test]
+    /// At 1 AU a cannonball feels the solar pressure, and in the Earth's shadow it feels nothing
+    fn solar_radiation_pressure_test() {
+        let srp = SolarRadiationPressure::new(Cannonball::new(1., 1.))
+            .with_epoch(Epoch::from_seconds_since_j2000(1e8, TimeScale::TT));
+        let sun = ephemerides::sun_position(1e8);
+        let mut direction = sun.clone();
+        direction.safe_normalize();
+        let lit = state((direction.clone() * 7e6).elem);
+        let acceleration = srp.acceleration(0., &lit);
+        let distance = (sun - lit.position.clone()).norm();
+        assert_relative_eq!(
+            acceleration.norm(),
+            SOLAR_PRESSURE * (AU / distance).powi(2),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            acceleration.dot(&direction),
+            -acceleration.norm(),
+            max_relative = 1e-9
+        );
+        let dark = state((direction * -7e6).elem);
+        assert_eq!(srp.acceleration(0., &dark).norm(), 0.);
+        assert_relative_eq!(SOLAR_PRESSURE, 4.54e-6, max_relative = 1e-3);
+    }
+}
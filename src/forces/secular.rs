@@ -0,0 +1,98 @@
+//! First-order secular rates of the orbital elements due to the J2 harmonic of the central body,
+//! from Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., eq. 9-41. They hold for
+//! closed orbits only.
+
+use crate::orbit::{Oriented, Shaped};
+
+/// `3/2 n J2 (R/p)^2`, common to every rate
+fn j2_factor(orbit: &impl Shaped) -> f64 {
+    let body = orbit.central_body();
+    let ratio = body.equatorial_radius / orbit.semi_latus_rectum();
+    1.5 * orbit.mean_motion() * body.zonal(2) * ratio.powi(2)
+}
+
+/// Nodal regression rate in rad/s, negative for prograde orbits
+pub fn raan_rate(orbit: &(impl Shaped + Oriented)) -> f64 {
+    -j2_factor(orbit) * orbit.inclination().cos()
+}
+
+/// Apsidal rotation rate in rad/s, which vanishes at the critical inclinations of about 63.4° and
+/// 116.6°
+pub fn arg_peri_rate(orbit: &(impl Shaped + Oriented)) -> f64 {
+    j2_factor(orbit) * (2. - 2.5 * orbit.inclination().sin().powi(2))
+}
+
+/// Rate of the mean anomaly in rad/s, the mean motion plus its J2 correction
+pub fn mean_anomaly_rate(orbit: &(impl Shaped + Oriented)) -> f64 {
+    let eccentricity_factor = (1. - orbit.eccentricity().powi(2)).sqrt();
+    let inclination_factor = 1. - 1.5 * orbit.inclination().sin().powi(2);
+    orbit.mean_motion() + j2_factor(orbit) * eccentricity_factor * inclination_factor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f64::consts::PI;
+
+    use crate::bodies;
+    use crate::forces::J2;
+    use crate::integrators::{AdaptiveRungeKutta, Integrator, OrbitDynamics};
+    use crate::orbit::structs::{CartesianState, COE};
+    use crate::orbit::Scaled;
+
+    #[test]
+    /// A sun-synchronous orbit at 800 km altitude needs an inclination of about 98.6° to keep its
+    /// node turning once a year
+    fn sun_synchronous_test() {
+        let coe = COE::new(
+            bodies::EARTH.equatorial_radius + 800e3,
+            0.,
+            98.6_f64.to_radians(),
+            0.,
+            0.,
+            0.,
+        );
+        let year = 365.242_2 * 86_400.;
+        assert_relative_eq!(raan_rate(&coe), 2. * PI / year, max_relative = 5e-3);
+    }
+
+    #[test]
+    /// Periapsis stays put at the critical inclination, as Molniya orbits rely on, and the node
+    /// stays put for polar orbits
+    fn critical_inclination_test() {
+        let critical = 0.8_f64.sqrt().asin();
+        let coe = COE::new(26_560e3, 0.74, critical, 4.7, 1., 0.);
+        assert_relative_eq!(arg_peri_rate(&coe), 0., epsilon = 1e-20);
+        assert_relative_eq!(
+            mean_anomaly_rate(&coe),
+            coe.mean_motion() - 0.2 * j2_factor(&coe) * (1. - 0.74_f64.powi(2)).sqrt(),
+            max_relative = 1e-14
+        );
+        let polar = COE {
+            inclination: PI / 2.,
+            ..coe
+        };
+        assert_relative_eq!(raan_rate(&polar), 0., epsilon = 1e-20);
+    }
+
+    #[test]
+    /// Numerical propagation under J2 drifts the node at the secular rate, when compared after
+    /// whole orbits so the short-period terms mostly cancel
+    fn propagated_test() {
+        let coe = COE::new(7_200e3, 0.01, 1., 0.5, 2., 0.);
+        let orbits = 10.;
+        let time_of_flight = orbits * coe.period();
+        let solution = AdaptiveRungeKutta::dormand_prince_87(1e-6, 1e-12)
+            .integrate(
+                &OrbitDynamics::new().with_force(J2),
+                0.,
+                &CartesianState::from(&coe),
+                time_of_flight,
+            )
+            .unwrap();
+        let propagated = COE::from(&solution.state);
+        let drift = propagated.raan - coe.raan;
+        assert_relative_eq!(drift, raan_rate(&coe) * time_of_flight, max_relative = 2e-2);
+    }
+}
@@ -0,0 +1,218 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use super::*;
+
+/// Solar and geomagnetic activity over one UTC day, which drives thermosphere models such as
+/// NRLMSISE-00
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SpaceWeather {
+    /// Observed 10.7 cm solar radio flux, in solar flux units of 1e-22 W/m^2/Hz
+    pub f107: f64,
+    /// Observed flux averaged over the 81 days centred on this one
+    pub f107_average: f64,
+    /// Daily planetary geomagnetic index
+    pub ap: f64,
+    /// Planetary index over each three hours of the day from 00:00
+    pub ap_3_hour: [f64; 8],
+}
+
+/// Daily series of space weather, as published by CelesTrak
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpaceWeatherData {
+    /// UTC modified Julian dates of the start of each day, in order, and the activity that day
+    entries: Vec<(f64, SpaceWeather)>,
+}
+
+/// Columns of `SW-All.csv` read for each day
+const COLUMNS: [&str; 12] = [
+    "DATE",
+    "AP1",
+    "AP2",
+    "AP3",
+    "AP4",
+    "AP5",
+    "AP6",
+    "AP7",
+    "AP8",
+    "AP_AVG",
+    "F10.7_OBS",
+    "F10.7_OBS_CENTER81",
+];
+
+impl SpaceWeatherData {
+    /// Series from `(UTC MJD, activity)` pairs, which are sorted by date. A series needs at least
+    /// one entry.
+    pub fn new(mut entries: Vec<(f64, SpaceWeather)>) -> Result<Self, SpaceWeatherError> {
+        if entries.is_empty() {
+            return Err(SpaceWeatherError::Empty);
+        }
+        entries.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(Self { entries })
+    }
+
+    /// Reads CelesTrak's `SW-All.csv`, whose header names the columns. Days missing any of the
+    /// values needed, such as the monthly predictions at the end, are skipped.
+    pub fn read_celestrak(reader: impl BufRead) -> Result<Self, SpaceWeatherError> {
+        let mut lines = reader.lines();
+        let header = lines.next().ok_or(SpaceWeatherError::Empty)??;
+        let names: Vec<_> = header.split(',').map(str::trim).collect();
+        let indices = COLUMNS
+            .iter()
+            .map(|column| names.iter().position(|name| name == column))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(SpaceWeatherError::Parse { line: 1 })?;
+
+        let mut entries = Vec::new();
+        for (index, line) in lines.enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let error = || SpaceWeatherError::Parse { line: index + 2 };
+            let fields: Vec<_> = line.split(',').map(str::trim).collect();
+            let values = indices
+                .iter()
+                .map(|index| fields.get(*index).copied().ok_or_else(error))
+                .collect::<Result<Vec<_>, _>>()?;
+            if values.iter().any(|value| value.is_empty()) {
+                continue;
+            }
+            let mjd = date_mjd(values[0]).ok_or_else(error)?;
+            let numbers = values[1..]
+                .iter()
+                .map(|value| value.parse::<f64>().map_err(|_| error()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut ap_3_hour = [0.; 8];
+            ap_3_hour.copy_from_slice(&numbers[..8]);
+            entries.push((
+                mjd,
+                SpaceWeather {
+                    f107: numbers[9],
+                    f107_average: numbers[10],
+                    ap: numbers[8],
+                    ap_3_hour,
+                },
+            ));
+        }
+        Self::new(entries)
+    }
+
+    /// Loads a CelesTrak `SW-All.csv` file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SpaceWeatherError> {
+        Self::read_celestrak(BufReader::new(File::open(path)?))
+    }
+
+    /// Activity over the latest day of the series at or before the UTC day containing an epoch,
+    /// so a day missing from the series takes the one before it, or `None` before the series
+    /// starts
+    pub fn at(&self, epoch: Epoch) -> Option<SpaceWeather> {
+        let day = epoch.to_mjd(TimeScale::UTC).floor();
+        let count = self.entries.partition_point(|(mjd, _)| *mjd <= day);
+        Some(self.entries.get(count.checked_sub(1)?)?.1)
+    }
+}
+
+/// MJD of a `YYYY-MM-DD` date
+fn date_mjd(date: &str) -> Option<f64> {
+    let mut parts = date.splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    let midnight = Epoch::from_gregorian(year, month, day, 0, 0, 0., TimeScale::TT).ok()?;
+    Some(midnight.to_mjd(TimeScale::TT).round())
+}
+
+#[derive(Debug)]
+pub enum SpaceWeatherError {
+    Io(io::Error),
+    /// A malformed line, numbered from 1, or a header without the columns needed
+    Parse {
+        line: usize,
+    },
+    /// No entries were found
+    Empty,
+}
+
+impl fmt::Display for SpaceWeatherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "reading space weather: {err}"),
+            Self::Parse { line } => write!(f, "space weather line {line} is malformed"),
+            Self::Empty => write!(f, "no space weather found"),
+        }
+    }
+}
+
+impl Error for SpaceWeatherError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SpaceWeatherError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SW_ALL: &str = "\
+DATE,BSRN,ND,KP1,KP2,KP3,KP4,KP5,KP6,KP7,KP8,KP_SUM,AP1,AP2,AP3,AP4,AP5,AP6,AP7,AP8,AP_AVG,CP,C9,ISN,F10.7_OBS,F10.7_ADJ,F10.7_DATA_TYPE,F10.7_OBS_CENTER81,F10.7_OBS_LAST81,F10.7_ADJ_CENTER81,F10.7_ADJ_LAST81
+2003-10-29,2324,9,57,87,90,83,77,80,77,90,640,67,300,400,236,179,207,179,400,246,2.5,9,167,279.0,275.7,OBS,162.8,145.6,160.8,143.8
+2003-10-30,2324,10,37,30,40,53,77,83,90,90,500,22,15,27,56,179,236,400,400,167,2.3,9,193,271.4,268.2,OBS,163.6,147.4,161.7,145.6
+2030-01-01,2615,0,,,,,,,,,,,,,,,,,,,,,,105.2,103.5,PRM,105.8,,104.1,
+";
+
+    #[test]
+    /// Days are read by column name, the monthly predictions without a geomagnetic index are
+    /// skipped, and an epoch after the last day takes that day
+    fn celestrak_test() {
+        let data = SpaceWeatherData::read_celestrak(SW_ALL.as_bytes()).unwrap();
+        assert_eq!(data.entries.len(), 2);
+        let halloween: Epoch = "2003-10-30T12:00:00Z".parse().unwrap();
+        let weather = data.at(halloween).unwrap();
+        assert_eq!(
+            weather,
+            SpaceWeather {
+                f107: 271.4,
+                f107_average: 163.6,
+                ap: 167.,
+                ap_3_hour: [22., 15., 27., 56., 179., 236., 400., 400.],
+            }
+        );
+        assert_eq!(data.entries[0].0, 52_941.);
+        assert_eq!(data.at(halloween + 86_400.), Some(weather));
+        assert_eq!(data.at(halloween - 86_400.).unwrap().ap, 246.);
+        assert_eq!(data.at(halloween - 2. * 86_400.), None);
+
+        let quiet = SpaceWeather { ap: 4., ..weather };
+        let gapped = SpaceWeatherData::new(vec![(52_943., quiet), (52_941., weather)]).unwrap();
+        assert_eq!(gapped.at(halloween), Some(weather));
+        assert_eq!(gapped.at(halloween + 86_400.), Some(quiet));
+
+        assert!(matches!(
+            SpaceWeatherData::read_celestrak("DATE,AP1\n".as_bytes()),
+            Err(SpaceWeatherError::Parse { line: 1 })
+        ));
+        let header = SW_ALL.lines().next().unwrap();
+        let malformed = format!("{header}\n2003-10-29,2324,9,57,87,90,x\n");
+        assert!(matches!(
+            SpaceWeatherData::read_celestrak(malformed.as_bytes()),
+            Err(SpaceWeatherError::Parse { line: 2 })
+        ));
+        assert!(matches!(
+            SpaceWeatherData::read_celestrak(format!("{header}\n").as_bytes()),
+            Err(SpaceWeatherError::Empty)
+        ));
+    }
+}
@@ -0,0 +1,164 @@
+use crate::bodies;
+use crate::ephemerides;
+
+use super::*;
+
+/// Point-mass gravity of a third body such as the Sun or the Moon, as the difference between its
+/// pull on the spacecraft and on the central body.
+///
+/// That difference is evaluated with Battin's formulation (An Introduction to the Mathematics and
+/// Methods of Astrodynamics, section 8.4), which doesn't lose precision when the third body is far
+/// away. Its position comes from an ephemeris relative to the central body.
+#[derive(Clone, Copy, Debug)]
+pub struct ThirdBody {
+    /// Gravitational parameter of the third body, in m^3/s^2
+    pub mu: f64,
+    /// Position of the third body relative to the central body, at TT seconds since J2000
+    pub ephemeris: fn(f64) -> Vector3,
+    /// Instant at time zero of the integration, which overrides the epoch of the state when set
+    pub epoch: Option<Epoch>,
+}
+
+impl ThirdBody {
+    pub fn new(mu: f64, ephemeris: fn(f64) -> Vector3) -> Self {
+        Self {
+            mu,
+            ephemeris,
+            epoch: None,
+        }
+    }
+
+    /// The Sun, seen from the Earth
+    pub fn sun() -> Self {
+        Self::new(bodies::SUN.mu, ephemerides::sun_position)
+    }
+
+    /// The Moon, seen from the Earth
+    pub fn moon() -> Self {
+        Self::new(bodies::MOON.mu, ephemerides::moon_position)
+    }
+
+    pub fn with_epoch(self, epoch: Epoch) -> Self {
+        Self {
+            epoch: Some(epoch),
+            ..self
+        }
+    }
+}
+
+impl ForceModel for ThirdBody {
+    fn acceleration(&self, time: f64, state: &CartesianState) -> Vector3 {
+        let third_body = (self.ephemeris)(tt_seconds(self.epoch, time, state));
+        third_body_acceleration(self.mu, &state.position, &third_body)
+    }
+}
+
+/// Acceleration of a spacecraft at `position` relative to the central body, due to a third body
+/// of gravitational parameter `mu` at `third_body`
+pub fn third_body_acceleration(mu: f64, position: &Vector3, third_body: &Vector3) -> Vector3 {
+    let relative = position.clone() - third_body.clone();
+    let twice_minus = position.clone() - third_body.clone() * 2.;
+    let q = position.dot(&twice_minus) / third_body.dot(third_body);
+    let f = q * (3. + 3. * q + q * q) / (1. + (1. + q).powf(1.5));
+    (position.clone() + third_body.clone() * f) * (-mu / relative.norm().powi(3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::integrators::{AdaptiveRungeKutta, Integrator, OrbitDynamics};
+    use crate::testing::assert_array_eq_atol;
+
+    /// The textbook difference of the third body's pull on the spacecraft and the central body
+    fn direct(mu: f64, position: &Vector3, third_body: &Vector3) -> Vector3 {
+        let to_third_body = third_body.clone() - position.clone();
+        to_third_body.clone() * (mu / to_third_body.norm().powi(3))
+            - third_body.clone() * (mu / third_body.norm().powi(3))
+    }
+
+    #[test]
+    /// Battin's form agrees with the direct difference, and at GEO the Moon's tide is about
+    /// `2 mu r / d^3` along the Earth–Moon line
+    fn battin_test() {
+        let mu = bodies::MOON.mu;
+        let moon = Vector3::new([384_400e3, 0., 0.]);
+        let geo = Vector3::new([42_164e3, 0., 0.]);
+        let battin = third_body_acceleration(mu, &geo, &moon);
+        let expected = direct(mu, &geo, &moon);
+        assert_array_eq_atol(&battin.elem, &expected.elem, 1e-18);
+        assert_relative_eq!(
+            battin.elem[0],
+            2. * mu * 42_164e3 / 384_400e3_f64.powi(3),
+            max_relative = 0.2
+        );
+
+        let sideways = Vector3::new([1e7, -3e7, 2e7]);
+        assert_array_eq_atol(
+            &third_body_acceleration(mu, &sideways, &moon).elem,
+            &direct(mu, &sideways, &moon).elem,
+            1e-18,
+        );
+    }
+
+    #[test]
+    /// Battin's form keeps the tide of a very distant third body, where the direct difference
+    /// would cancel to noise
+    fn distant_test() {
+        let mu = 1e30;
+        let distance = 1e16;
+        let third_body = Vector3::new([distance, 0., 0.]);
+        let position = Vector3::new([7e6, 0., 0.]);
+        let tide = 2. * mu * 7e6 / distance.powi(3);
+        let battin = third_body_acceleration(mu, &position, &third_body);
+        assert_relative_eq!(battin.elem[0], tide, max_relative = 1e-8);
+    }
+
+    #[test]
+    fn ephemeris_test() {
+        let sun = ThirdBody::sun().with_epoch(Epoch::from_seconds_since_j2000(1e8, TimeScale::TT));
+        let state = CartesianState::new([42_164e3, 0., 0.].into(), [0., 3_075., 0.].into());
+        let expected = third_body_acceleration(
+            bodies::SUN.mu,
+            &state.position,
+            &ephemerides::sun_position(1e8 + 60.),
+        );
+        assert_eq!(sun.acceleration(60., &state), expected);
+        // The tide is between `mu r / d^3` and twice that, depending on the geometry
+        let r = state.position.norm();
+        let moon = ThirdBody::moon().acceleration(0., &state).norm();
+        let (perigee, apogee) = (356e6_f64, 407e6_f64);
+        assert!(moon > 0.9 * bodies::MOON.mu * r / apogee.powi(3));
+        assert!(moon < 2.1 * bodies::MOON.mu * r / perigee.powi(3));
+    }
+
+    #[test]
+    /// Without an epoch of its own the force follows the epoch of the state, so integrating from
+    /// a state with an epoch matches giving the force that epoch
+    fn state_epoch_test() {
+        let epoch = Epoch::from_seconds_since_j2000(1e8, TimeScale::TT);
+        let state = CartesianState::new([42_164e3, 0., 0.].into(), [0., 3_075., 0.].into());
+        let timed = state.clone().with_epoch(epoch);
+        assert_eq!(
+            ThirdBody::moon().acceleration(0., &timed),
+            ThirdBody::moon().with_epoch(epoch).acceleration(0., &state)
+        );
+
+        let integrator = AdaptiveRungeKutta::dormand_prince_87(1e-6, 1e-12);
+        let integrate = |sun: ThirdBody, state: &CartesianState| {
+            let dynamics = OrbitDynamics::new().with_force(sun);
+            integrator
+                .integrate(&dynamics, 0., state, 86_400.)
+                .unwrap()
+                .state
+        };
+        let overridden = integrate(ThirdBody::sun().with_epoch(epoch), &state);
+        let followed = integrate(ThirdBody::sun(), &timed);
+        assert_array_eq_atol(&followed.position.elem, &overridden.position.elem, 1e-6);
+        assert_array_eq_atol(&followed.velocity.elem, &overridden.velocity.elem, 1e-9);
+        assert_eq!(followed.epoch, Some(epoch + 86_400.));
+        // At J2000 the Sun is somewhere else entirely
+        let at_j2000 = integrate(ThirdBody::sun(), &state);
+        assert!((at_j2000.position - overridden.position).norm() > 1.);
+    }
+}
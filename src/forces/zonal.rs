@@ -0,0 +1,147 @@
+use super::*;
+
+/// Oblateness of the central body: the acceleration due to its J2 zonal harmonic
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct J2;
+
+impl ForceModel for J2 {
+    fn acceleration(&self, _time: f64, state: &CartesianState) -> Vector3 {
+        let body = &state.central_body;
+        let r = state.position.norm();
+        let [x, y, z] = state.position.elem;
+        let sin_latitude_2 = (z / r).powi(2);
+        let scale = -1.5 * body.zonal(2) * body.mu * body.equatorial_radius.powi(2) / r.powi(5);
+        Vector3::new([
+            scale * x * (1. - 5. * sin_latitude_2),
+            scale * y * (1. - 5. * sin_latitude_2),
+            scale * z * (3. - 5. * sin_latitude_2),
+        ])
+    }
+}
+
+/// Acceleration due to the zonal harmonics of the central body from J2 up to `J_max_degree`.
+/// Degrees the body has no coefficient for contribute nothing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ZonalHarmonics {
+    max_degree: usize,
+}
+
+impl ZonalHarmonics {
+    pub fn new(max_degree: usize) -> Self {
+        Self { max_degree }
+    }
+
+    /// J2 to J6, every zonal harmonic the built-in Earth model has
+    pub fn j2_to_j6() -> Self {
+        Self::new(6)
+    }
+}
+
+impl ForceModel for ZonalHarmonics {
+    /// The gradient of `-mu/r J_n (R/r)^n P_n(sin(latitude))` is
+    /// `mu/r^2 J_n (R/r)^n (((n + 1) P_n + u P_n') r_hat - P_n' z_hat)`, where `u = sin(latitude)`
+    fn acceleration(&self, _time: f64, state: &CartesianState) -> Vector3 {
+        let body = &state.central_body;
+        let r = state.position.norm();
+        let u = state.position.elem[2] / r;
+        let ratio = body.equatorial_radius / r;
+
+        // Legendre polynomials and their derivatives, of the previous two degrees
+        let (mut legendre, mut previous_legendre) = (u, 1.);
+        let mut derivative = 1.;
+        let (mut radial, mut polar) = (0., 0.);
+        for degree in 2..=self.max_degree {
+            let n = degree as f64;
+            let next = ((2. * n - 1.) * u * legendre - (n - 1.) * previous_legendre) / n;
+            derivative = n * legendre + u * derivative;
+            previous_legendre = legendre;
+            legendre = next;
+
+            let coefficient = body.zonal(degree) * ratio.powi(degree as i32);
+            radial += coefficient * ((n + 1.) * legendre + u * derivative);
+            polar += coefficient * derivative;
+        }
+
+        let scale = body.mu / r.powi(2);
+        state.position.clone() * (scale * radial / r) - Vector3::new([0., 0., scale * polar])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::bodies;
+    use crate::testing::assert_array_eq_atol;
+
+    fn state() -> CartesianState {
+        CartesianState::new(
+            [4_000_000., -3_000_000., 5_000_000.].into(),
+            [1_000., 6_000., 2_000.].into(),
+        )
+    }
+
+    #[test]
+    /// Over the pole the J2 acceleration points up, and at the equator it points down with half
+    /// the magnitude
+    fn j2_test() {
+        let body = bodies::EARTH;
+        let r = 7_000_000_f64;
+        let magnitude = 1.5 * body.zonal(2) * body.mu * body.equatorial_radius.powi(2) / r.powi(4);
+        let polar = CartesianState::new([0., 0., r].into(), [7_500., 0., 0.].into());
+        assert_array_eq_atol(
+            &J2.acceleration(0., &polar).elem,
+            &[0., 0., 2. * magnitude],
+            1e-15,
+        );
+        let equatorial = CartesianState::new([0., r, 0.].into(), [7_500., 0., 0.].into());
+        assert_array_eq_atol(
+            &J2.acceleration(0., &equatorial).elem,
+            &[0., -magnitude, 0.],
+            1e-15,
+        );
+    }
+
+    #[test]
+    /// The general zonal expansion agrees with the closed-form J2 acceleration, and higher
+    /// degrees are small corrections on top of it
+    fn zonal_test() {
+        let state = state();
+        let j2 = J2.acceleration(0., &state);
+        assert_array_eq_atol(
+            &ZonalHarmonics::new(2).acceleration(0., &state).elem,
+            &j2.elem,
+            1e-16,
+        );
+        let all = ZonalHarmonics::j2_to_j6().acceleration(0., &state);
+        let difference = (all - j2).norm();
+        assert!(difference > 0. && difference < 1e-2 * J2.acceleration(0., &state).norm());
+        assert_eq!(ZonalHarmonics::new(1).acceleration(0., &state).norm(), 0.);
+    }
+
+    #[test]
+    /// The J3 term matches the closed form in Vallado, Fundamentals of Astrodynamics and
+    /// Applications, 4th ed., eq. 8-30
+    fn j3_test() {
+        let body = bodies::CentralBody {
+            zonal_harmonics: &[0., -2.532_656_485_33e-6],
+            ..bodies::EARTH
+        };
+        let state = state().with_central_body(body);
+        let [x, y, z] = state.position.elem;
+        let r = state.position.norm();
+        let (j3, radius) = (body.zonal(3), body.equatorial_radius);
+        let scale = -2.5 * j3 * body.mu * radius.powi(3) / r.powi(7);
+        let horizontal = 3. * z - 7. * z.powi(3) / r.powi(2);
+        let expected = [
+            scale * x * horizontal,
+            scale * y * horizontal,
+            scale * (6. * z.powi(2) - 7. * z.powi(4) / r.powi(2) - 0.6 * r.powi(2)),
+        ];
+        assert_array_eq_atol(
+            &ZonalHarmonics::new(3).acceleration(0., &state).elem,
+            &expected,
+            1e-18,
+        );
+    }
+}
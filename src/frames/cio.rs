@@ -0,0 +1,146 @@
+//! The IAU-2006 reduction, which places the celestial intermediate pole with the IAU 2006
+//! precession and the IAU 2000B nutation, and turns the Earth about it by the Earth rotation angle
+//! from the celestial intermediate origin (IERS Conventions 2010, chapter 5).
+
+use super::*;
+
+/// Rotation from the mean equator and equinox of J2000 to those of date, from the IAU 2006
+/// precession angles (Capitaine et al. 2003), at `T` Julian centuries of TT since J2000
+fn precession(centuries: f64) -> Quaternion {
+    let polynomial = |coefficients: [f64; 6]| {
+        coefficients
+            .iter()
+            .rev()
+            .fold(0., |sum, coefficient| sum * centuries + coefficient)
+            * ARCSECOND
+    };
+    let zeta = polynomial([
+        2.650_545,
+        2_306.083_227,
+        0.298_849_9,
+        0.018_018_28,
+        -0.000_005_971,
+        -0.000_000_317_3,
+    ]);
+    let theta = polynomial([
+        0.,
+        2_004.191_903,
+        -0.429_493_4,
+        -0.041_822_64,
+        -0.000_007_089,
+        -0.000_000_127_4,
+    ]);
+    let z = polynomial([
+        -2.650_545,
+        2_306.077_181,
+        1.092_734_8,
+        0.018_268_37,
+        -0.000_028_596,
+        -0.000_000_290_4,
+    ]);
+    rotation(Z_AXIS, -zeta) * rotation(Y_AXIS, theta) * rotation(Z_AXIS, -z)
+}
+
+/// Mean obliquity of the ecliptic in radians, from the IAU 2006 precession
+fn mean_obliquity(centuries: f64) -> f64 {
+    let coefficients = [
+        84_381.406,
+        -46.836_769,
+        -0.000_183_1,
+        0.002_003_40,
+        -0.000_000_576,
+        -0.000_000_043_4,
+    ];
+    let arcseconds = coefficients
+        .iter()
+        .rev()
+        .fold(0., |sum, coefficient| sum * centuries + coefficient);
+    arcseconds * ARCSECOND
+}
+
+/// Coordinates `X` and `Y` of the celestial intermediate pole in GCRS, which are the first two
+/// components of its unit vector
+fn pole(centuries: f64) -> (f64, f64) {
+    let (longitude, obliquity) = nutation::iau_2000b(centuries);
+    let mean_obliquity = mean_obliquity(centuries);
+    let nutation = rotation(X_AXIS, mean_obliquity)
+        * rotation(Z_AXIS, -longitude)
+        * rotation(X_AXIS, -(mean_obliquity + obliquity));
+    let to_true_of_date = fk5::frame_bias() * precession(centuries) * nutation;
+    let [x, y, _] = to_true_of_date
+        .rotated_vec_alibi(&Vector3::from(Z_AXIS))
+        .elem;
+    (x, y)
+}
+
+/// The CIO locator `s`, which places the celestial intermediate origin on the equator of the
+/// pole, keeping its polynomial and largest periodic terms
+fn cio_locator(centuries: f64, x: f64, y: f64) -> f64 {
+    let node = nutation::delaunay_arguments(centuries)[4];
+    let polynomial = [94., 3_808.65, -122.68, -72_574.11, 27.98, 15.62]
+        .iter()
+        .rev()
+        .fold(0., |sum, coefficient| sum * centuries + coefficient);
+    let microarcseconds = polynomial - 2_640.73 * node.sin() - 63.53 * (2. * node).sin();
+    microarcseconds * 1e-6 * ARCSECOND - x * y / 2.
+}
+
+/// Rotation from GCRF to the celestial intermediate reference system, at `T` Julian centuries of
+/// TT since J2000
+pub(super) fn celestial_to_intermediate(centuries: f64) -> Quaternion {
+    let (x, y) = pole(centuries);
+    let s = cio_locator(centuries, x, y);
+    let azimuth = y.atan2(x);
+    let tilt = ((x.powi(2) + y.powi(2)) / (1. - x.powi(2) - y.powi(2)))
+        .sqrt()
+        .atan();
+    rotation(Z_AXIS, azimuth) * rotation(Y_AXIS, tilt) * rotation(Z_AXIS, -(azimuth + s))
+}
+
+/// Earth rotation angle in radians, at UT1 seconds since 2000-01-01 12:00:00 UT1
+pub(super) fn earth_rotation_angle(ut1_seconds: f64) -> f64 {
+    let days = ut1_seconds / SECONDS_PER_DAY;
+    // Whole days don't change the angle, so they're left out to keep precision
+    let turns = 0.779_057_273_264 + 0.002_737_811_911_354_48 * days + days.rem_euclid(1.);
+    turns.rem_euclid(1.) * 2. * PI
+}
+
+/// The TIO locator `s'`, which places the terrestrial intermediate origin, in radians
+pub(super) fn tio_locator(centuries: f64) -> f64 {
+    -47e-6 * centuries * ARCSECOND
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// At J2000 the pole sits at the frame bias, X = -16.617 mas and Y = -6.819 mas, shifted by
+    /// the nutation in longitude and obliquity
+    fn pole_test() {
+        let (longitude, obliquity) = nutation::iau_2000b(0.);
+        let mean_obliquity = mean_obliquity(0.);
+        let (x, y) = pole(0.);
+        let expected_x = -0.016_617 * ARCSECOND + longitude * mean_obliquity.sin();
+        let expected_y = -0.006_819_2 * ARCSECOND + obliquity;
+        assert_relative_eq!(x, expected_x, epsilon = 1e-3 * ARCSECOND);
+        assert_relative_eq!(y, expected_y, epsilon = 1e-3 * ARCSECOND);
+    }
+
+    #[test]
+    /// IERS Conventions 2010, equation 5.15: the Earth rotation angle is 0.7790572732640 turns at
+    /// J2000 UT1 and gains a turn every sidereal day
+    fn rotation_angle_test() {
+        assert_relative_eq!(
+            earth_rotation_angle(0.),
+            0.779_057_273_264 * 2. * PI,
+            epsilon = 1e-15
+        );
+        let sidereal_day = SECONDS_PER_DAY / 1.002_737_811_911_354_6;
+        assert_relative_eq!(
+            earth_rotation_angle(1e4 * sidereal_day),
+            0.779_057_273_264 * 2. * PI,
+            epsilon = 1e-9
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use crate::time::{self, LeapSeconds};
+
+use super::*;
+
+/// The series in use by every frame transformation, if any
+static INSTALLED: RwLock<Option<Arc<EopData>>> = RwLock::new(None);
+
+/// Earth orientation parameters at one instant, as published by the IERS
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EarthOrientation {
+    /// Polar motion, the position of the celestial intermediate pole in the ITRF, in radians
+    pub x_pole: f64,
+    pub y_pole: f64,
+    /// `UT1 - UTC` in seconds
+    pub ut1_minus_utc: f64,
+    /// Excess of the length of day over 86400 s, in seconds
+    pub length_of_day: f64,
+}
+
+impl EarthOrientation {
+    /// Parameters from the installed series, or zero if none is installed
+    pub fn at(epoch: Epoch) -> Self {
+        let installed = INSTALLED.read().unwrap().clone();
+        installed.map_or_else(Self::default, |data| data.interpolate(epoch))
+    }
+
+    /// Rotation rate of the Earth in rad/s
+    pub fn rotation_rate(&self) -> f64 {
+        bodies::EARTH.rotation_rate * (1. - self.length_of_day / SECONDS_PER_DAY)
+    }
+}
+
+/// Daily series of Earth orientation parameters, read from IERS files.
+///
+/// Parameters are interpolated linearly between days. Before the first day and after the last,
+/// the nearest day's values are held. Celestial pole offsets are not read, which leaves errors of
+/// up to about 0.1" with IAU-76/FK5 and under 1 mas with IAU-2006.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EopData {
+    /// UTC modified Julian dates, in order, and the parameters at their start
+    entries: Vec<(f64, EarthOrientation)>,
+}
+
+impl EopData {
+    /// Series from `(UTC MJD, parameters)` pairs, which are sorted by date. A series needs at
+    /// least one entry.
+    pub fn new(mut entries: Vec<(f64, EarthOrientation)>) -> Result<Self, EopError> {
+        if entries.is_empty() {
+            return Err(EopError::Empty);
+        }
+        entries.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(Self { entries })
+    }
+
+    /// Reads the fixed-width IERS `finals` format of Bulletin A, as in `finals2000A.all` or
+    /// `finals.daily`. Days without polar motion, at the end of the predictions, are skipped.
+    pub fn read_finals(reader: impl BufRead) -> Result<Self, EopError> {
+        let mut entries = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let error = || EopError::Parse { line: index + 1 };
+            let field =
+                |start: usize, end: usize| line.get(start..end.min(line.len())).map(str::trim);
+            let number = |start, end| match field(start, end) {
+                None | Some("") => Ok(None),
+                Some(text) => text.parse::<f64>().map(Some).map_err(|_| error()),
+            };
+            let (Some(mjd), Some(x_pole), Some(y_pole), Some(ut1_minus_utc)) = (
+                number(7, 15)?,
+                number(18, 27)?,
+                number(37, 46)?,
+                number(58, 68)?,
+            ) else {
+                if line.trim().is_empty() || number(7, 15)?.is_some() {
+                    continue;
+                }
+                return Err(error());
+            };
+            let length_of_day = number(79, 86)?.unwrap_or(0.);
+            entries.push((
+                mjd,
+                EarthOrientation {
+                    x_pole: x_pole * ARCSECOND,
+                    y_pole: y_pole * ARCSECOND,
+                    ut1_minus_utc,
+                    length_of_day: length_of_day * 1e-3,
+                },
+            ));
+        }
+        Self::new(entries)
+    }
+
+    /// Reads the IERS EOP C04 series, whose lines hold the date, MJD, polar motion in arcseconds,
+    /// `UT1 - UTC` and the length of day in seconds, separated by whitespace. Lines which don't
+    /// start with a year are skipped as headers.
+    pub fn read_c04(reader: impl BufRead) -> Result<Self, EopError> {
+        let mut entries = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let error = || EopError::Parse { line: index + 1 };
+            let words: Vec<_> = line.split_whitespace().collect();
+            if words
+                .first()
+                .is_none_or(|year| year.parse::<u16>().is_err())
+            {
+                continue;
+            }
+            let numbers = words
+                .iter()
+                .map(|word| word.parse::<f64>().map_err(|_| error()))
+                .collect::<Result<Vec<_>, _>>()?;
+            // The 14 C04 series gives the date as year, month and day and the length of day after
+            // `UT1 - UTC`. The 20 C04 series adds the hour, and puts the celestial pole offsets and
+            // polar motion rates before the length of day.
+            let start = numbers
+                .iter()
+                .position(|number| *number > 10_000.)
+                .ok_or_else(error)?;
+            let length_of_day_index = if start == 3 { 4 } else { 8 };
+            let (Some(&[mjd, x_pole, y_pole, ut1_minus_utc]), Some(&length_of_day)) = (
+                numbers.get(start..start + 4),
+                numbers.get(start + length_of_day_index),
+            ) else {
+                return Err(error());
+            };
+            entries.push((
+                mjd,
+                EarthOrientation {
+                    x_pole: x_pole * ARCSECOND,
+                    y_pole: y_pole * ARCSECOND,
+                    ut1_minus_utc,
+                    length_of_day,
+                },
+            ));
+        }
+        Self::new(entries)
+    }
+
+    /// Loads an EOP C04 file if its first data line starts with a four-digit year, and the
+    /// `finals` format otherwise
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EopError> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut text = String::new();
+        io::Read::read_to_string(&mut reader, &mut text)?;
+        let c04 = text.lines().any(|line| {
+            line.split_whitespace()
+                .next()
+                .is_some_and(|year| year.len() == 4 && year.parse::<u16>().is_ok())
+        });
+        if c04 {
+            Self::read_c04(text.as_bytes())
+        } else {
+            Self::read_finals(text.as_bytes())
+        }
+    }
+
+    /// Makes this the series used by every frame transformation and every conversion to UT1
+    pub fn install(self) {
+        let data = Arc::new(self);
+        let for_time = Arc::clone(&data);
+        time::set_ut1_minus_utc(move |mjd| for_time.ut1_minus_utc(mjd));
+        *INSTALLED.write().unwrap() = Some(data);
+    }
+
+    /// Goes back to zero polar motion, nominal rotation and UT1 taken as UTC
+    pub fn uninstall() {
+        *INSTALLED.write().unwrap() = None;
+        time::clear_ut1_minus_utc();
+    }
+
+    /// The series in use, if any
+    pub fn installed() -> Option<Arc<Self>> {
+        INSTALLED.read().unwrap().clone()
+    }
+
+    /// Parameters at an epoch
+    pub fn interpolate(&self, epoch: Epoch) -> EarthOrientation {
+        self.at_mjd(epoch.to_mjd(TimeScale::UTC))
+    }
+
+    fn ut1_minus_utc(&self, mjd: f64) -> f64 {
+        self.at_mjd(mjd).ut1_minus_utc
+    }
+
+    /// Parameters at a UTC modified Julian date. `UT1 - UTC` is interpolated as `UT1 - TAI`, so
+    /// that leap seconds between two days don't smear into it.
+    fn at_mjd(&self, mjd: f64) -> EarthOrientation {
+        let index = self.entries.partition_point(|(start, _)| *start <= mjd);
+        let (before, after) = match index {
+            0 => return self.entries[0].1,
+            index if index == self.entries.len() => return self.entries[index - 1].1,
+            index => (self.entries[index - 1], self.entries[index]),
+        };
+        let leap_seconds = LeapSeconds::installed();
+        let tai_minus_utc = |mjd: f64| leap_seconds.tai_minus_utc(mjd.floor() as i64) as f64;
+        let fraction = (mjd - before.0) / (after.0 - before.0);
+        let interpolate = |a: f64, b: f64| a + (b - a) * fraction;
+        let ut1_minus_tai = interpolate(
+            before.1.ut1_minus_utc - tai_minus_utc(before.0),
+            after.1.ut1_minus_utc - tai_minus_utc(after.0),
+        );
+        EarthOrientation {
+            x_pole: interpolate(before.1.x_pole, after.1.x_pole),
+            y_pole: interpolate(before.1.y_pole, after.1.y_pole),
+            ut1_minus_utc: ut1_minus_tai + tai_minus_utc(mjd),
+            length_of_day: interpolate(before.1.length_of_day, after.1.length_of_day),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum EopError {
+    Io(io::Error),
+    /// A malformed line, numbered from 1
+    Parse {
+        line: usize,
+    },
+    /// No entries were found
+    Empty,
+}
+
+impl fmt::Display for EopError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "reading Earth orientation parameters: {err}"),
+            Self::Parse { line } => write!(f, "Earth orientation line {line} is malformed"),
+            Self::Empty => write!(f, "no Earth orientation parameters found"),
+        }
+    }
+}
+
+impl Error for EopError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for EopError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FINALS: &str = "\
+161230 57752.00 I  0.047706 0.000028  0.276722 0.000031  I-0.5868130 0.0000081  0.7938 0.0062  I   -95.945    0.397    -6.730    0.300   .047640   .276790   .3827690   -95.940    -6.730
+161231 57753.00 I  0.047009 0.000028  0.278070 0.000031  I-0.5880820 0.0000089  1.3183 0.0059  I   -95.989    0.397    -6.744    0.300   .046930   .278130   .3816160   -96.000    -6.740
+17 1 1 57754.00 I  0.046279 0.000028  0.279428 0.000032  I 0.4106220 0.0000085  1.2880 0.0058  I   -96.086    0.397    -6.741    0.300   .046190   .279520  -.6197400   -96.140    -6.790
+17 1 2 57755.00
+";
+
+    #[test]
+    fn finals_test() {
+        let data = EopData::read_finals(FINALS.as_bytes()).unwrap();
+        assert_eq!(data.entries.len(), 3);
+        let (mjd, first) = data.entries[0];
+        assert_eq!(mjd, 57_752.);
+        assert_relative_eq!(first.x_pole, 0.047_706 * ARCSECOND);
+        assert_relative_eq!(first.y_pole, 0.276_722 * ARCSECOND);
+        assert_eq!(first.ut1_minus_utc, -0.586_813);
+        assert_relative_eq!(first.length_of_day, 0.793_8e-3);
+
+        // Halfway through a day, and across the leap second at the end of 2016
+        let noon = data.at_mjd(57_752.5);
+        assert_relative_eq!(noon.x_pole, 0.047_357_5 * ARCSECOND, max_relative = 1e-12);
+        assert_relative_eq!(noon.ut1_minus_utc, -0.587_447_5, epsilon = 1e-12);
+        let new_year = data.at_mjd(57_753.75);
+        assert_relative_eq!(
+            new_year.ut1_minus_utc,
+            -0.588_082 + (0.410_622 - 1. + 0.588_082) * 0.75,
+            epsilon = 1e-12
+        );
+        assert_eq!(data.at_mjd(50_000.), first);
+        assert_eq!(data.at_mjd(60_000.), data.entries[2].1);
+    }
+
+    #[test]
+    fn c04_test() {
+        let c04 = "\
+# EOP 14 C04
+      YR  MM  DD       MJD        x(\")        y(\")  UT1-UTC(s)      LOD(s)       dX(\")      dY(\")
+2017   1   1  57754   0.046314   0.279487   0.4106176   0.0012884   0.000098  -0.000029
+";
+        let data = EopData::read_c04(c04.as_bytes()).unwrap();
+        let (mjd, orientation) = data.entries[0];
+        assert_eq!(mjd, 57_754.);
+        assert_relative_eq!(orientation.y_pole, 0.279_487 * ARCSECOND);
+        assert_eq!(orientation.ut1_minus_utc, 0.410_617_6);
+        assert_eq!(orientation.length_of_day, 0.001_288_4);
+
+        let c04_20 = "2017  01  01  00  57754.00   0.046314   0.279487   0.4106176   0.000098  \
+            -0.000029   0.000512  -0.000227   0.0012884\n";
+        assert_eq!(EopData::read_c04(c04_20.as_bytes()).unwrap(), data);
+        assert!(matches!(
+            EopData::read_c04("2017 1 1 57754 0.1\n".as_bytes()),
+            Err(EopError::Parse { line: 1 })
+        ));
+        assert!(matches!(
+            EopData::read_finals("".as_bytes()),
+            Err(EopError::Empty)
+        ));
+        assert!(matches!(EopData::new(vec![]), Err(EopError::Empty)));
+    }
+}
@@ -0,0 +1,90 @@
+//! The IAU-76/FK5 reduction, built from the IAU 1976 precession, the IAU 1980 nutation and the
+//! IAU 1982 sidereal time, as laid out in Vallado, Fundamentals of Astrodynamics and
+//! Applications, section 3.7.
+
+use super::*;
+
+/// Rotation from GCRF to the mean equator and equinox of J2000 (IERS Conventions 2010, equation
+/// 5.21)
+pub(super) fn frame_bias() -> Quaternion {
+    let right_ascension = -0.014_6 * ARCSECOND;
+    let xi = -0.016_617 * ARCSECOND;
+    let eta = -0.006_819_2 * ARCSECOND;
+    rotation(Z_AXIS, right_ascension) * rotation(Y_AXIS, xi) * rotation(X_AXIS, -eta)
+}
+
+/// Rotation from the mean equator and equinox of J2000 to those of date, at `T` Julian centuries
+/// of TT since J2000
+pub(super) fn precession(centuries: f64) -> Quaternion {
+    let polynomial = |a: f64, b: f64, c: f64| ((c * centuries + b) * centuries + a) * centuries;
+    let zeta = polynomial(2_306.218_1, 0.301_88, 0.017_998) * ARCSECOND;
+    let theta = polynomial(2_004.310_9, -0.426_65, -0.041_833) * ARCSECOND;
+    let z = polynomial(2_306.218_1, 1.094_68, 0.018_203) * ARCSECOND;
+    rotation(Z_AXIS, -zeta) * rotation(Y_AXIS, theta) * rotation(Z_AXIS, -z)
+}
+
+/// Mean obliquity of the ecliptic in radians
+fn mean_obliquity(centuries: f64) -> f64 {
+    let arcseconds =
+        84_381.448 + centuries * (-46.815 + centuries * (-0.000_59 + centuries * 0.001_813));
+    arcseconds * ARCSECOND
+}
+
+/// Rotation from the mean equator and equinox of date to the true ones, and the equation of the
+/// equinoxes, which takes mean sidereal time to apparent
+pub(super) fn nutation(centuries: f64) -> (Quaternion, f64) {
+    let (longitude, obliquity) = nutation::iau_1980(centuries);
+    let mean_obliquity = mean_obliquity(centuries);
+    let rotation = rotation(X_AXIS, mean_obliquity)
+        * rotation(Z_AXIS, -longitude)
+        * rotation(X_AXIS, -(mean_obliquity + obliquity));
+    let node = nutation::delaunay_arguments(centuries)[4];
+    let equation_of_equinoxes = longitude * mean_obliquity.cos()
+        + (0.002_64 * node.sin() + 0.000_063 * (2. * node).sin()) * ARCSECOND;
+    (rotation, equation_of_equinoxes)
+}
+
+/// Greenwich mean sidereal time in radians, from the IAU 1982 model, at UT1 seconds since
+/// 2000-01-01 12:00:00 UT1
+pub(super) fn mean_sidereal_time(ut1_seconds: f64) -> f64 {
+    let days = ut1_seconds / SECONDS_PER_DAY;
+    let centuries = days / 36_525.;
+    // Whole days don't change the angle, so they're left out to keep precision
+    let seconds = 67_310.548_41
+        + SECONDS_PER_DAY * days.rem_euclid(1.)
+        + centuries * (8_640_184.812_866 + centuries * (0.093_104 - centuries * 6.2e-6));
+    (seconds / SECONDS_PER_DAY).rem_euclid(1.) * 2. * PI
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing;
+
+    #[test]
+    /// Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., example 3-5: GMST is
+    /// 152.578787810° on 1992 August 20, 12:14 UT1
+    fn sidereal_time_test() {
+        let epoch = Epoch::from_gregorian(1992, 8, 20, 12, 14, 0., TimeScale::UT1).unwrap();
+        let gmst = mean_sidereal_time(epoch.seconds_since_j2000(TimeScale::UT1));
+        assert_relative_eq!(gmst.to_degrees(), 152.578_787_810, epsilon = 1e-7);
+    }
+
+    #[test]
+    /// The pole of date moves from the J2000 pole by `theta` towards right ascension `-zeta`
+    fn precession_test() {
+        let centuries = 0.25;
+        let pole = precession(centuries).rotated_vec_alibi(&Vector3::from(Z_AXIS));
+        let zeta =
+            ((0.017_998 * centuries + 0.301_88) * centuries + 2_306.218_1) * centuries * ARCSECOND;
+        let theta =
+            ((-0.041_833 * centuries - 0.426_65) * centuries + 2_004.310_9) * centuries * ARCSECOND;
+        let expected = [
+            zeta.cos() * theta.sin(),
+            -zeta.sin() * theta.sin(),
+            theta.cos(),
+        ];
+        testing::assert_array_eq_atol(&pole.elem, &expected, 1e-10);
+    }
+}
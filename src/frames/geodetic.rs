@@ -0,0 +1,216 @@
+//! Latitude, longitude and height above a reference ellipsoid, for Earth-fixed positions.
+
+use crate::bodies::CentralBody;
+
+use super::*;
+
+/// Ellipsoid of revolution about the Z axis, approximating a body's surface
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ellipsoid {
+    /// Semi-major axis in m
+    pub equatorial_radius: f64,
+    pub flattening: f64,
+}
+
+/// Latitude and longitude in radians, and height in m, above an [`Ellipsoid`]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Geodetic {
+    /// Angle between the equator and the ellipsoid's normal, positive north
+    pub latitude: f64,
+    /// Angle from the X axis, positive east, on [-pi, pi]
+    pub longitude: f64,
+    /// Distance along the ellipsoid's normal, positive outside
+    pub height: f64,
+}
+
+impl Geodetic {
+    pub fn new(latitude: f64, longitude: f64, height: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+            height,
+        }
+    }
+}
+
+impl Ellipsoid {
+    /// World Geodetic System 1984, the reference of GPS
+    pub const WGS84: Self = Self {
+        equatorial_radius: 6_378_137.,
+        flattening: 1. / 298.257_223_563,
+    };
+
+    /// Geodetic Reference System 1980, the reference of the ITRS
+    pub const GRS80: Self = Self {
+        equatorial_radius: 6_378_137.,
+        flattening: 1. / 298.257_222_101,
+    };
+
+    /// Sphere, over which geodetic latitude is geocentric latitude and height is altitude above
+    /// `radius`
+    pub const fn sphere(radius: f64) -> Self {
+        Self {
+            equatorial_radius: radius,
+            flattening: 0.,
+        }
+    }
+
+    pub fn polar_radius(&self) -> f64 {
+        self.equatorial_radius * (1. - self.flattening)
+    }
+
+    /// Square of the first eccentricity
+    pub fn eccentricity_squared(&self) -> f64 {
+        self.flattening * (2. - self.flattening)
+    }
+
+    /// Geocentric latitude of a point on the surface at `geodetic_latitude`
+    pub fn geocentric_latitude(&self, geodetic_latitude: f64) -> f64 {
+        let ratio = (1. - self.flattening).powi(2);
+        (ratio * geodetic_latitude.sin()).atan2(geodetic_latitude.cos())
+    }
+
+    /// Geodetic latitude of a point on the surface at `geocentric_latitude`
+    pub fn geodetic_latitude(&self, geocentric_latitude: f64) -> f64 {
+        let ratio = (1. - self.flattening).powi(2);
+        geocentric_latitude
+            .sin()
+            .atan2(ratio * geocentric_latitude.cos())
+    }
+
+    /// Coordinates of a position in the body-fixed frame.
+    ///
+    /// This is Vermeille's closed form (Journal of Geodesy 76, 2002), exact to rounding everywhere
+    /// but within `e^2 a`, about 43 km for the Earth, of the centre.
+    pub fn geodetic(&self, position: &Vector3) -> Geodetic {
+        let [x, y, z] = position.elem;
+        let radius_squared = self.equatorial_radius.powi(2);
+        let eccentricity_squared = self.eccentricity_squared();
+        let distance = x.hypot(y);
+
+        let p = distance.powi(2) / radius_squared;
+        let q = (1. - eccentricity_squared) * z.powi(2) / radius_squared;
+        let r = (p + q - eccentricity_squared.powi(2)) / 6.;
+        let s = eccentricity_squared.powi(2) * p * q / (4. * r.powi(3));
+        let t = (1. + s + (s * (2. + s)).sqrt()).cbrt();
+        let u = r * (1. + t + 1. / t);
+        let v = (u.powi(2) + eccentricity_squared.powi(2) * q).sqrt();
+        let w = eccentricity_squared * (u + v - q) / (2. * v);
+        let k = (u + v + w.powi(2)).sqrt() - w;
+        let d = k * distance / (k + eccentricity_squared);
+        let hypotenuse = d.hypot(z);
+
+        Geodetic {
+            latitude: 2. * z.atan2(d + hypotenuse),
+            longitude: y.atan2(x),
+            height: (k + eccentricity_squared - 1.) / k * hypotenuse,
+        }
+    }
+
+    /// Position in the body-fixed frame of the given coordinates
+    pub fn position(&self, geodetic: &Geodetic) -> Vector3 {
+        let (sin_latitude, cos_latitude) = geodetic.latitude.sin_cos();
+        let (sin_longitude, cos_longitude) = geodetic.longitude.sin_cos();
+        let eccentricity_squared = self.eccentricity_squared();
+        // Radius of curvature in the prime vertical
+        let normal =
+            self.equatorial_radius / (1. - eccentricity_squared * sin_latitude.powi(2)).sqrt();
+        let distance = (normal + geodetic.height) * cos_latitude;
+        Vector3::new([
+            distance * cos_longitude,
+            distance * sin_longitude,
+            (normal * (1. - eccentricity_squared) + geodetic.height) * sin_latitude,
+        ])
+    }
+}
+
+impl From<CentralBody> for Ellipsoid {
+    fn from(body: CentralBody) -> Self {
+        Self {
+            equatorial_radius: body.equatorial_radius,
+            flattening: body.flattening,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing;
+
+    #[test]
+    /// Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., example 3-3
+    fn vallado_test() {
+        let position = Vector3::new([6_524.834, 6_862.875, 6_448.296]) * 1e3;
+        let geodetic = Ellipsoid::WGS84.geodetic(&position);
+        assert_relative_eq!(geodetic.latitude.to_degrees(), 34.352_496, epsilon = 1e-6);
+        assert_relative_eq!(geodetic.longitude.to_degrees(), 46.446_4, epsilon = 1e-4);
+        assert_relative_eq!(geodetic.height, 5_085.22e3, epsilon = 10.);
+    }
+
+    #[test]
+    /// Positions survive the round trip to well under a millimetre, from the poles to the equator
+    /// and from below the surface to beyond geostationary orbit
+    fn round_trip_test() {
+        for ellipsoid in [
+            Ellipsoid::WGS84,
+            Ellipsoid::GRS80,
+            Ellipsoid::sphere(6_371e3),
+        ] {
+            for latitude in [-90., -89.999_9, -45., -1e-9, 0., 10., 60., 89.9, 90.] {
+                for height in [-5e6, -1e4, 0., 400e3, 42_164e3, 1e9] {
+                    let expected = Geodetic::new(f64::to_radians(latitude), 2., height);
+                    let position = ellipsoid.position(&expected);
+                    let geodetic = ellipsoid.geodetic(&position);
+                    testing::assert_array_eq_atol(
+                        &ellipsoid.position(&geodetic).elem,
+                        &position.elem,
+                        1e-6,
+                    );
+                    assert_relative_eq!(geodetic.height, height, epsilon = 1e-6);
+                    assert_relative_eq!(geodetic.latitude, expected.latitude, epsilon = 1e-14);
+                }
+            }
+        }
+    }
+
+    #[test]
+    /// On a sphere, latitude is geocentric and height is altitude
+    fn sphere_test() {
+        let position = Vector3::new([-3_000e3, 4_000e3, 5_000e3]);
+        let geodetic = Ellipsoid::sphere(6_000e3).geodetic(&position);
+        assert_relative_eq!(
+            geodetic.latitude,
+            f64::asin(0.5_f64.sqrt()),
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(geodetic.longitude, f64::atan2(4., -3.), epsilon = 1e-15);
+        assert_relative_eq!(geodetic.height, position.norm() - 6_000e3, epsilon = 1e-8);
+    }
+
+    #[test]
+    /// Geocentric latitude falls short of geodetic by up to about 11.5' on the Earth, at 45°
+    fn geocentric_latitude_test() {
+        let ellipsoid = Ellipsoid::from(bodies::EARTH);
+        let geodetic = 45_f64.to_radians();
+        let geocentric = ellipsoid.geocentric_latitude(geodetic);
+        assert_relative_eq!(
+            (geodetic - geocentric).to_degrees() * 60.,
+            11.5,
+            epsilon = 0.05
+        );
+        assert_relative_eq!(
+            ellipsoid.geodetic_latitude(geocentric),
+            geodetic,
+            epsilon = 1e-15
+        );
+
+        let surface = ellipsoid.position(&Geodetic::new(geodetic, 0., 0.));
+        assert_relative_eq!(
+            surface.elem[2].atan2(surface.elem[0]),
+            geocentric,
+            epsilon = 1e-15
+        );
+    }
+}
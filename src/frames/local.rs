@@ -0,0 +1,270 @@
+//! Frames which follow an orbiting body, with axes set by its position and velocity.
+
+use crate::orbit::structs::CartesianState;
+
+use super::*;
+
+/// Frame which moves with a body along its orbit
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LocalFrame {
+    /// The inertial frame the orbit is given in
+    #[default]
+    Inertial,
+    /// Radial, in-track, cross-track, also known as RSW or Hill's frame: x out along the
+    /// position, z along the angular momentum and y completing the triad, along the velocity for
+    /// a circular orbit
+    RIC,
+    /// Velocity, normal, co-normal: x along the velocity, y along the angular momentum and z
+    /// completing the triad, outwards from the central body for a circular orbit
+    VNC,
+    /// Normal, tangential, cross-track, also known as NTW: y along the velocity, z along the
+    /// angular momentum and x completing the triad, outwards for a circular orbit
+    NTW,
+    /// Local vertical, local horizontal as used for crewed vehicles: z down towards the central
+    /// body, y against the angular momentum and x completing the triad, along the velocity for a
+    /// circular orbit
+    LVLH,
+}
+
+impl LocalFrame {
+    /// Axes of the frame for a body at `state`, expressed in the inertial frame, or `None` for a
+    /// moving frame if the state has no angular momentum to fix them
+    pub fn axes(self, state: &CartesianState) -> Option<[Vector3; 3]> {
+        let CartesianState {
+            position, velocity, ..
+        } = state;
+        let unit = |vector: Vector3| vector.clone() * (1. / vector.norm());
+        let normal = || {
+            let momentum = position.cross(velocity);
+            (momentum.norm() != 0.).then(|| unit(momentum))
+        };
+        let axes = match self {
+            Self::Inertial => [X_AXIS, Y_AXIS, Z_AXIS].map(Vector3::from),
+            Self::RIC => {
+                let normal = normal()?;
+                let radial = unit(position.clone());
+                let in_track = normal.cross(&radial);
+                [radial, in_track, normal]
+            }
+            Self::VNC => {
+                let normal = normal()?;
+                let tangential = unit(velocity.clone());
+                let co_normal = tangential.cross(&normal);
+                [tangential, normal, co_normal]
+            }
+            Self::NTW => {
+                let normal = normal()?;
+                let tangential = unit(velocity.clone());
+                let in_plane = tangential.cross(&normal);
+                [in_plane, tangential, normal]
+            }
+            Self::LVLH => {
+                let normal = normal()?;
+                let down = unit(-position.clone());
+                let minus_normal = -normal;
+                let forward = minus_normal.cross(&down);
+                [forward, minus_normal, down]
+            }
+        };
+        Some(axes)
+    }
+
+    /// Rotation from the inertial frame to this frame for a body at `state`, or `None` for a
+    /// moving frame if the state has no angular momentum to fix its axes
+    pub fn rotation(self, state: &CartesianState) -> Option<Quaternion> {
+        let axes = self.axes(state)?;
+        Some(Quaternion::from_dcm(&axes.map(|axis| axis.elem)))
+    }
+
+    /// Rotation from the inertial frame to this frame for a body at `state`, along with the rate
+    /// at which the frame turns, or `None` for a moving frame if the state has no angular
+    /// momentum to fix its axes.
+    ///
+    /// The rate is that of a body on a Keplerian orbit, whose plane stays put, so it leaves out
+    /// the turning of the plane under perturbing forces.
+    pub fn orientation(self, state: &CartesianState) -> Option<LocalOrientation> {
+        let axes = self.axes(state)?;
+        let dcm = axes.map(|axis| axis.elem);
+        let CartesianState {
+            position,
+            velocity,
+            central_body,
+            ..
+        } = state;
+        let momentum = position.cross(velocity);
+        let radius_squared = position.dot(position);
+        let angular_velocity = match self {
+            Self::Inertial => Vector3::new([0.; 3]),
+            // The radius vector sweeps round at the rate of the true anomaly
+            Self::RIC | Self::LVLH => momentum * (1. / radius_squared),
+            // The velocity turns towards the gravitational acceleration
+            Self::VNC | Self::NTW => {
+                let radius_cubed = radius_squared * radius_squared.sqrt();
+                momentum * (central_body.mu / (radius_cubed * velocity.dot(velocity)))
+            }
+        };
+        Some(LocalOrientation {
+            rotation: Quaternion::from_dcm(&dcm),
+            dcm,
+            angular_velocity,
+        })
+    }
+}
+
+/// Attitude and rate of a local frame relative to the inertial frame at one instant
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalOrientation {
+    /// Rotation from the inertial frame to the local frame
+    pub rotation: Quaternion,
+    /// Direction cosine matrix from the inertial frame to the local frame, whose rows are the
+    /// local axes in inertial components
+    pub dcm: [[f64; 3]; 3],
+    /// Angular velocity in rad/s of the local frame relative to the inertial frame, in inertial
+    /// components
+    pub angular_velocity: Vector3,
+}
+
+impl LocalOrientation {
+    /// Local components of an inertial vector
+    pub fn vector(&self, vector: &Vector3) -> Vector3 {
+        self.rotation.rotated_vec_alias(vector)
+    }
+
+    /// Position and velocity in the local frame of an inertial position and velocity taken
+    /// relative to the frame's origin, the velocity being the rate of change seen from the
+    /// turning frame
+    pub fn state(&self, position: &Vector3, velocity: &Vector3) -> (Vector3, Vector3) {
+        let transport = self.angular_velocity.cross(position);
+        (
+            self.vector(position),
+            self.vector(&(velocity.clone() - transport)),
+        )
+    }
+
+    /// Inertial position and velocity, relative to the frame's origin, of a position and velocity
+    /// in the local frame, undoing [`Self::state`]
+    pub fn inertial_state(&self, position: &Vector3, velocity: &Vector3) -> (Vector3, Vector3) {
+        let position = self.rotation.rotated_vec_alibi(position);
+        let velocity = self.rotation.rotated_vec_alibi(velocity);
+        let transport = self.angular_velocity.cross(&position);
+        (position, velocity + transport)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::orbit::structs::COE;
+    use crate::orbit::Keplerian;
+    use crate::testing;
+
+    fn state() -> CartesianState {
+        (&COE::new(7_000e3, 0.1, 1., 2., 3., 4.)).into()
+    }
+
+    #[test]
+    /// Every frame is right-handed, and the rotation into it takes its axes onto the coordinate
+    /// axes
+    fn axes_test() {
+        let state = state();
+        for frame in [
+            LocalFrame::Inertial,
+            LocalFrame::RIC,
+            LocalFrame::VNC,
+            LocalFrame::NTW,
+            LocalFrame::LVLH,
+        ] {
+            let [x, y, z] = frame.axes(&state).unwrap();
+            testing::assert_array_eq_atol(&x.cross(&y).elem, &z.elem, 1e-15);
+            let rotation = frame.rotation(&state).unwrap();
+            for (axis, expected) in [x, y, z].iter().zip([X_AXIS, Y_AXIS, Z_AXIS]) {
+                let local = rotation.rotated_vec_alias(axis);
+                testing::assert_array_eq_atol(&local.elem, &expected, 1e-15);
+            }
+        }
+    }
+
+    #[test]
+    /// The position lies along +x in RIC and -z in LVLH, and the velocity along +x in VNC and +y
+    /// in NTW
+    fn components_test() {
+        let state = state();
+        let radius = state.position.norm();
+        let speed = state.velocity.norm();
+        let ric = LocalFrame::RIC.rotation(&state).unwrap();
+        let position = ric.rotated_vec_alias(&state.position);
+        testing::assert_array_eq_atol(&position.elem, &[radius, 0., 0.], 1e-8);
+        let velocity = ric.rotated_vec_alias(&state.velocity);
+        assert!(velocity.elem[1] > 0.);
+        assert_relative_eq!(velocity.elem[2], 0., epsilon = 1e-12);
+
+        let lvlh = LocalFrame::LVLH.rotation(&state).unwrap();
+        let position = lvlh.rotated_vec_alias(&state.position);
+        testing::assert_array_eq_atol(&position.elem, &[0., 0., -radius], 1e-8);
+
+        let vnc = LocalFrame::VNC.rotation(&state).unwrap();
+        let velocity = vnc.rotated_vec_alias(&state.velocity);
+        testing::assert_array_eq_atol(&velocity.elem, &[speed, 0., 0.], 1e-11);
+        let ntw = LocalFrame::NTW.rotation(&state).unwrap();
+        let velocity = ntw.rotated_vec_alias(&state.velocity);
+        testing::assert_array_eq_atol(&velocity.elem, &[0., speed, 0.], 1e-11);
+
+        let radial = CartesianState::new(state.position.clone(), state.position.clone());
+        assert!(LocalFrame::RIC.rotation(&radial).is_none());
+        assert_eq!(
+            LocalFrame::Inertial.rotation(&radial),
+            Some(Quaternion::identity())
+        );
+    }
+
+    #[test]
+    /// The DCM matches the quaternion, and the angular velocity is how fast the axes turn as the
+    /// body moves on its orbit
+    fn orientation_test() {
+        let state = state();
+        let step = 1e-2;
+        let before = Keplerian.propagate_cartesian(&state, -step);
+        let after = Keplerian.propagate_cartesian(&state, step);
+        for frame in [
+            LocalFrame::Inertial,
+            LocalFrame::RIC,
+            LocalFrame::VNC,
+            LocalFrame::NTW,
+            LocalFrame::LVLH,
+        ] {
+            let orientation = frame.orientation(&state).unwrap();
+            let from_quaternion = orientation.rotation.to_dcm();
+            for (row, expected) in orientation.dcm.iter().zip(&from_quaternion) {
+                testing::assert_array_eq_atol(row, expected, 1e-15);
+            }
+            // Each axis moves as the angular velocity crossed with it
+            let axes = frame.axes(&state).unwrap();
+            let [axes_before, axes_after] = [&before, &after].map(|s| frame.axes(s).unwrap());
+            for (axis, (early, late)) in axes.iter().zip(axes_before.iter().zip(&axes_after)) {
+                let rate = (late.clone() - early.clone()) * (0.5 / step);
+                let expected = orientation.angular_velocity.cross(axis);
+                testing::assert_array_eq_atol(&rate.elem, &expected.elem, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    /// A neighbour's relative state goes into the rotating frame and back unchanged, and one
+    /// riding along with the frame has no velocity in it
+    fn state_test() {
+        let state = state();
+        let orientation = LocalFrame::LVLH.orientation(&state).unwrap();
+        let position = Vector3::new([100., -50., 20.]);
+        let velocity = Vector3::new([0.1, 0.2, -0.3]);
+        let (local_position, local_velocity) = orientation.state(&position, &velocity);
+        let (back_position, back_velocity) =
+            orientation.inertial_state(&local_position, &local_velocity);
+        testing::assert_array_eq_atol(&back_position.elem, &position.elem, 1e-12);
+        testing::assert_array_eq_atol(&back_velocity.elem, &velocity.elem, 1e-15);
+
+        let riding = orientation.angular_velocity.cross(&position);
+        let (_, local_velocity) = orientation.state(&position, &riding);
+        testing::assert_array_eq_atol(&local_velocity.elem, &[0.; 3], 1e-18);
+    }
+}
@@ -0,0 +1,500 @@
+//! Earth-centred reference frames, and the rotations between them.
+//!
+//! Inertial frames are tied to GCRF through precession and nutation, and the Earth-fixed ITRF is
+//! tied to them through the Earth's rotation and polar motion. Two reductions are available:
+//! IAU-76/FK5, which passes through the equinox-based MOD, TOD and PEF frames, and IAU-2006, which
+//! passes through the CIO-based CIRS and TIRS frames. Both use truncated nutation series, good to
+//! a few milliarcseconds, or about 10 cm at the Earth's surface.
+//!
+//! The Earth's orientation at a given time, which can't be predicted far ahead, comes from an
+//! [`EopData`] series read from an IERS file and installed. Without one, polar motion is taken
+//! as zero and UT1 as UTC.
+//!
+//! Earth-fixed positions can be given as latitude, longitude and height over an [`Ellipsoid`],
+//! which places ground stations and sub-satellite points. A [`GroundStation`] sees orbiting
+//! bodies in its horizon frames, east-north-up and south-east-zenith.
+//!
+//! A [`FramedVector`] carries the frame and epoch its components belong to, so a transform can
+//! refuse one it wasn't built for rather than silently rotating it from the wrong frame.
+//!
+//! Orbiting bodies carry their own [`LocalFrame`]s, such as RIC and LVLH, whose axes follow the
+//! body's position and velocity in whichever inertial frame its orbit is given. Their
+//! [`LocalOrientation`] carries the rate at which they turn, for relative velocities.
+//!
+//! ```
+//! use orbitrs::frames::{Ellipsoid, Frame, FrameTransform};
+//! use orbitrs::prelude::*;
+//!
+//! let epoch: Epoch = "2024-03-20T03:06:00Z".parse().unwrap();
+//! let to_itrf = FrameTransform::new(Frame::GCRF, Frame::ITRF, epoch);
+//! let position = Vector3::new([7_000e3, 0., 0.]);
+//! let fixed = to_itrf.position(&position);
+//! assert!((fixed.norm() - position.norm()).abs() < 1e-6);
+//!
+//! // The sub-satellite point is on the equator
+//! let geodetic = Ellipsoid::WGS84.geodetic(&fixed);
+//! assert!(geodetic.latitude.abs() < 1e-2);
+//! ```
+
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::bodies;
+use crate::constants::{SECONDS_PER_DAY, X_AXIS, Y_AXIS, Z_AXIS};
+use crate::quaternions::Quaternion;
+use crate::time::{Epoch, TimeScale};
+use crate::vector::Vector3;
+
+mod cio;
+mod eop;
+mod fk5;
+mod geodetic;
+mod local;
+mod nutation;
+mod topocentric;
+
+pub use eop::{EarthOrientation, EopData, EopError};
+pub use geodetic::{Ellipsoid, Geodetic};
+pub use local::{LocalFrame, LocalOrientation};
+pub use topocentric::{GroundStation, Observation, Refraction};
+
+/// One arcsecond in radians
+const ARCSECOND: f64 = PI / 648_000.;
+
+/// Frames whose axes a `Vector3` can be expressed in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Frame {
+    /// Geocentric Celestial Reference Frame, the realisation of the ICRS at the Earth
+    GCRF,
+    /// Mean equator and equinox of J2000 (EME2000), offset from GCRF by the frame bias of about
+    /// 20 mas
+    J2000,
+    /// Mean equator and equinox of date, from IAU 1976 precession
+    MOD,
+    /// True equator and equinox of date, from IAU 1980 nutation
+    TOD,
+    /// True equator and mean equinox, the frame of SGP4 and two-line elements
+    TEME,
+    /// Pseudo Earth-fixed frame, which turns with the Earth about the true pole of date
+    PEF,
+    /// Celestial intermediate reference system, from IAU 2006 precession and IAU 2000B nutation
+    CIRS,
+    /// Terrestrial intermediate reference system, which turns with the Earth about the celestial
+    /// intermediate pole
+    TIRS,
+    /// International Terrestrial Reference Frame, fixed to the Earth's crust
+    ITRF,
+}
+
+impl Frame {
+    /// Whether the frame turns with the Earth
+    pub fn is_earth_fixed(&self) -> bool {
+        matches!(self, Self::PEF | Self::TIRS | Self::ITRF)
+    }
+}
+
+/// A vector tagged with the frame its components are in and the instant they hold at
+#[derive(Clone, Debug, PartialEq)]
+pub struct FramedVector {
+    pub frame: Frame,
+    pub epoch: Epoch,
+    pub vector: Vector3,
+}
+
+impl FramedVector {
+    pub fn new(frame: Frame, epoch: Epoch, vector: Vector3) -> Self {
+        Self {
+            frame,
+            epoch,
+            vector,
+        }
+    }
+
+    /// The same position expressed in another frame at the same epoch, with the default
+    /// reduction and the installed Earth orientation
+    pub fn to_frame(&self, frame: Frame) -> Self {
+        Self {
+            frame,
+            epoch: self.epoch,
+            vector: FrameTransform::new(self.frame, frame, self.epoch).position(&self.vector),
+        }
+    }
+}
+
+/// Models tying ITRF to GCRF
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Reduction {
+    /// Through TOD and PEF, with sidereal time
+    Iau76,
+    /// Through CIRS and TIRS, with the Earth rotation angle
+    #[default]
+    Iau2006,
+}
+
+/// Transformation of vectors from one frame to another at an epoch.
+///
+/// The frames on each side of ITRF follow from `reduction`, and the Earth's orientation from
+/// `orientation`, which comes from the installed [`EopData`] unless given.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrameTransform {
+    pub from: Frame,
+    pub to: Frame,
+    pub epoch: Epoch,
+    pub reduction: Reduction,
+    pub orientation: Option<EarthOrientation>,
+}
+
+impl FrameTransform {
+    pub fn new(from: Frame, to: Frame, epoch: Epoch) -> Self {
+        Self {
+            from,
+            to,
+            epoch,
+            reduction: Reduction::default(),
+            orientation: None,
+        }
+    }
+
+    pub fn with_reduction(self, reduction: Reduction) -> Self {
+        Self { reduction, ..self }
+    }
+
+    pub fn with_orientation(self, orientation: EarthOrientation) -> Self {
+        Self {
+            orientation: Some(orientation),
+            ..self
+        }
+    }
+
+    /// The transformation back
+    pub fn inverted(&self) -> Self {
+        Self {
+            from: self.to,
+            to: self.from,
+            ..self.clone()
+        }
+    }
+
+    /// Rotation taking the axes of `from` onto those of `to`, so that `rotated_vec_alias` turns
+    /// a vector in `from` into the same vector in `to`
+    pub fn rotation(&self) -> Quaternion {
+        let orientation = self.orientation();
+        let (from, _) = self.gcrf_to(self.from, &orientation);
+        let (to, _) = self.gcrf_to(self.to, &orientation);
+        from.inverted() * to
+    }
+
+    pub fn position(&self, position: &Vector3) -> Vector3 {
+        self.rotation().rotated_vec_alias(position)
+    }
+
+    /// Position and velocity in `to`, accounting for the rotation of Earth-fixed frames
+    pub fn state(&self, position: &Vector3, velocity: &Vector3) -> (Vector3, Vector3) {
+        let orientation = self.orientation();
+        let (from, from_rate) = self.gcrf_to(self.from, &orientation);
+        let (to, to_rate) = self.gcrf_to(self.to, &orientation);
+        let gcrf_position = from.rotated_vec_alibi(position);
+        let gcrf_velocity = from.rotated_vec_alibi(&(velocity.clone() + from_rate.cross(position)));
+        let position = to.rotated_vec_alias(&gcrf_position);
+        let velocity = to.rotated_vec_alias(&gcrf_velocity) - to_rate.cross(&position);
+        (position, velocity)
+    }
+
+    /// A position tagged with `from` and the transform's epoch, expressed in `to`
+    pub fn framed_position(&self, position: &FramedVector) -> Result<FramedVector, FrameError> {
+        self.check(position)?;
+        Ok(self.tagged(self.position(&position.vector)))
+    }
+
+    /// Position and velocity tagged with `from` and the transform's epoch, expressed in `to`,
+    /// accounting for the rotation of Earth-fixed frames
+    pub fn framed_state(
+        &self,
+        position: &FramedVector,
+        velocity: &FramedVector,
+    ) -> Result<(FramedVector, FramedVector), FrameError> {
+        self.check(position)?;
+        self.check(velocity)?;
+        let (position, velocity) = self.state(&position.vector, &velocity.vector);
+        Ok((self.tagged(position), self.tagged(velocity)))
+    }
+
+    fn check(&self, vector: &FramedVector) -> Result<(), FrameError> {
+        if vector.frame != self.from {
+            Err(FrameError::WrongFrame {
+                expected: self.from,
+                found: vector.frame,
+            })
+        } else if vector.epoch != self.epoch {
+            Err(FrameError::WrongEpoch {
+                expected: self.epoch,
+                found: vector.epoch,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn tagged(&self, vector: Vector3) -> FramedVector {
+        FramedVector::new(self.to, self.epoch, vector)
+    }
+
+    fn orientation(&self) -> EarthOrientation {
+        self.orientation
+            .unwrap_or_else(|| EarthOrientation::at(self.epoch))
+    }
+
+    /// Rotation from GCRF to a frame, and the frame's angular velocity in its own axes.
+    /// Precession and nutation are slow enough that their rates are left out.
+    fn gcrf_to(&self, frame: Frame, orientation: &EarthOrientation) -> (Quaternion, Vector3) {
+        let centuries = self.epoch.seconds_since_j2000(TimeScale::TT) / (SECONDS_PER_DAY * 36_525.);
+        let ut1_seconds =
+            self.epoch.seconds_since_j2000(TimeScale::UTC) + orientation.ut1_minus_utc;
+        let earth_rate = Vector3::new([0., 0., orientation.rotation_rate()]);
+        let still = Vector3::new([0.; 3]);
+        let true_of_date = || {
+            let (nutation, equation_of_equinoxes) = fk5::nutation(centuries);
+            (
+                fk5::frame_bias() * fk5::precession(centuries) * nutation,
+                equation_of_equinoxes,
+            )
+        };
+        let pseudo_earth_fixed = || {
+            let (true_of_date, equation_of_equinoxes) = true_of_date();
+            let sidereal_time = fk5::mean_sidereal_time(ut1_seconds) + equation_of_equinoxes;
+            true_of_date * rotation(Z_AXIS, sidereal_time)
+        };
+        let terrestrial_intermediate = || {
+            cio::celestial_to_intermediate(centuries)
+                * rotation(Z_AXIS, cio::earth_rotation_angle(ut1_seconds))
+        };
+        match frame {
+            Frame::GCRF => (Quaternion::identity(), still),
+            Frame::J2000 => (fk5::frame_bias(), still),
+            Frame::MOD => (fk5::frame_bias() * fk5::precession(centuries), still),
+            Frame::TOD => (true_of_date().0, still),
+            Frame::TEME => {
+                let (true_of_date, equation_of_equinoxes) = true_of_date();
+                (
+                    true_of_date * rotation(Z_AXIS, equation_of_equinoxes),
+                    still,
+                )
+            }
+            Frame::PEF => (pseudo_earth_fixed(), earth_rate),
+            Frame::CIRS => (cio::celestial_to_intermediate(centuries), still),
+            Frame::TIRS => (terrestrial_intermediate(), earth_rate),
+            Frame::ITRF => {
+                let (intermediate, tio_locator) = match self.reduction {
+                    Reduction::Iau76 => (pseudo_earth_fixed(), 0.),
+                    Reduction::Iau2006 => (terrestrial_intermediate(), cio::tio_locator(centuries)),
+                };
+                let polar_motion = rotation(Z_AXIS, tio_locator)
+                    * rotation(Y_AXIS, -orientation.x_pole)
+                    * rotation(X_AXIS, -orientation.y_pole);
+                let rate = polar_motion.rotated_vec_alias(&earth_rate);
+                (intermediate * polar_motion, rate)
+            }
+        }
+    }
+}
+
+/// A tagged vector handed to a transform built for another frame or epoch
+#[derive(Clone, Debug, PartialEq)]
+pub enum FrameError {
+    WrongFrame { expected: Frame, found: Frame },
+    WrongEpoch { expected: Epoch, found: Epoch },
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongFrame { expected, found } => {
+                write!(f, "vector is in {found:?}, not {expected:?}")
+            }
+            Self::WrongEpoch { expected, found } => {
+                write!(f, "vector holds at {found}, not {expected}")
+            }
+        }
+    }
+}
+
+impl Error for FrameError {}
+
+/// Earth rotation angle in radians at an epoch, between the celestial and terrestrial
+/// intermediate origins, with UT1 from the installed Earth orientation series
+pub fn earth_rotation_angle(epoch: Epoch) -> f64 {
+    cio::earth_rotation_angle(epoch.seconds_since_j2000(TimeScale::UT1))
+}
+
+/// Rotation of the axes by `angle` about one of them, whose alias rotation is the elementary
+/// rotation matrix `R_i(angle)`
+fn rotation(axis: [f64; 3], angle: f64) -> Quaternion {
+    Quaternion::from_angle_axis(angle, &axis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing;
+
+    /// Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., example 3-15: a satellite
+    /// on 2004 April 6, 07:51:28.386009 UTC
+    fn vallado() -> (FrameTransform, Vector3, Vector3) {
+        let epoch: Epoch = "2004-04-06T07:51:28.386009Z".parse().unwrap();
+        let orientation = EarthOrientation {
+            x_pole: -0.140_682 * ARCSECOND,
+            y_pole: 0.333_309 * ARCSECOND,
+            ut1_minus_utc: -0.439_961_9,
+            length_of_day: 0.001_556_3,
+        };
+        let transform =
+            FrameTransform::new(Frame::ITRF, Frame::GCRF, epoch).with_orientation(orientation);
+        let position = Vector3::new([-1_033.479_383_0, 7_901.295_275_4, 6_380.356_595_8]) * 1e3;
+        let velocity = Vector3::new([-3.225_636_520, -2.872_451_450, 5.531_924_446]) * 1e3;
+        (transform, position, velocity)
+    }
+
+    #[test]
+    fn vallado_iau_2006_test() {
+        let (transform, position, velocity) = vallado();
+        let (gcrf_position, gcrf_velocity) = transform.state(&position, &velocity);
+        testing::assert_array_eq_atol(
+            &gcrf_position.elem,
+            &[5_102_508.959, 6_123_011.403, 6_378_136.925],
+            0.5,
+        );
+        testing::assert_array_eq_atol(
+            &gcrf_velocity.elem,
+            &[-4_743.220_16, 790.536_50, 5_533.756_19],
+            5e-4,
+        );
+
+        let (back_position, back_velocity) =
+            transform.inverted().state(&gcrf_position, &gcrf_velocity);
+        testing::assert_array_eq_atol(&back_position.elem, &position.elem, 1e-6);
+        testing::assert_array_eq_atol(&back_velocity.elem, &velocity.elem, 1e-9);
+    }
+
+    #[test]
+    fn vallado_iau_76_test() {
+        let (transform, position, velocity) = vallado();
+        let transform = transform.with_reduction(Reduction::Iau76);
+        let to_pef = FrameTransform {
+            to: Frame::PEF,
+            ..transform.clone()
+        };
+        let (pef_position, pef_velocity) = to_pef.state(&position, &velocity);
+        testing::assert_array_eq_atol(
+            &pef_position.elem,
+            &[-1_033_475.031_3, 7_901_305.585_6, 6_380_344.532_8],
+            1e-3,
+        );
+        testing::assert_array_eq_atol(
+            &pef_velocity.elem,
+            &[-3_225.632_747, -2_872.442_511, 5_531.931_288],
+            1e-6,
+        );
+
+        // Without the celestial pole offsets that Vallado applies, the IAU 1980 nutation is off by
+        // about 50 mas
+        let (gcrf_position, gcrf_velocity) = transform.state(&position, &velocity);
+        testing::assert_array_eq_atol(
+            &gcrf_position.elem,
+            &[5_102_508.958, 6_123_011.401, 6_378_136.928],
+            5.,
+        );
+        testing::assert_array_eq_atol(
+            &gcrf_velocity.elem,
+            &[-4_743.220_16, 790.536_50, 5_533.756_19],
+            5e-3,
+        );
+    }
+
+    #[test]
+    /// Rotations compose, TEME differs from TOD by the equation of the equinoxes, and the
+    /// Earth-fixed frames turn once a sidereal day
+    fn rotation_test() {
+        let epoch: Epoch = "2017-06-15T08:30:00Z".parse().unwrap();
+        let direct = FrameTransform::new(Frame::J2000, Frame::TEME, epoch).rotation();
+        let through_tod = FrameTransform::new(Frame::J2000, Frame::TOD, epoch).rotation()
+            * FrameTransform::new(Frame::TOD, Frame::TEME, epoch).rotation();
+        let x = Vector3::from(X_AXIS);
+        testing::assert_array_eq_atol(
+            &direct.rotated_vec_alias(&x).elem,
+            &through_tod.rotated_vec_alias(&x).elem,
+            1e-15,
+        );
+        let (_, equation_of_equinoxes) =
+            fk5::nutation(epoch.seconds_since_j2000(TimeScale::TT) / (SECONDS_PER_DAY * 36_525.));
+        let [cos, sin, z] = FrameTransform::new(Frame::TOD, Frame::TEME, epoch)
+            .position(&x)
+            .elem;
+        assert_relative_eq!(z, 0., epsilon = 1e-15);
+        assert_relative_eq!(sin.atan2(cos), -equation_of_equinoxes, epsilon = 1e-12);
+
+        let sidereal_day = SECONDS_PER_DAY / 1.002_737_909_350_795;
+        let fixed = |epoch, reduction| {
+            let transform = FrameTransform::new(Frame::GCRF, Frame::ITRF, epoch)
+                .with_reduction(reduction)
+                .with_orientation(EarthOrientation::default());
+            transform.position(&x)
+        };
+        // Up to a day of precession, about 0.14"
+        for reduction in [Reduction::Iau76, Reduction::Iau2006] {
+            testing::assert_array_eq_atol(
+                &fixed(epoch, reduction).elem,
+                &fixed(epoch + sidereal_day, reduction).elem,
+                1e-6,
+            );
+        }
+        // The two reductions agree to within their nutation errors
+        let difference = fixed(epoch, Reduction::Iau76) - fixed(epoch, Reduction::Iau2006);
+        assert!(difference.norm() < 0.1 * ARCSECOND);
+    }
+
+    #[test]
+    /// Tagged vectors go through the transform they were built for, and come out tagged with
+    /// its destination, while any other transform refuses them
+    fn framed_vector_test() {
+        let (transform, position, velocity) = vallado();
+        let epoch = transform.epoch;
+        let framed_position = FramedVector::new(Frame::ITRF, epoch, position.clone());
+        let framed_velocity = FramedVector::new(Frame::ITRF, epoch, velocity.clone());
+        let (gcrf_position, gcrf_velocity) = transform
+            .framed_state(&framed_position, &framed_velocity)
+            .unwrap();
+        let (expected_position, expected_velocity) = transform.state(&position, &velocity);
+        assert_eq!(gcrf_position.frame, Frame::GCRF);
+        assert_eq!(gcrf_position.epoch, epoch);
+        assert_eq!(gcrf_position.vector, expected_position);
+        assert_eq!(gcrf_velocity.vector, expected_velocity);
+        assert_eq!(
+            transform.framed_position(&framed_position).unwrap(),
+            gcrf_position
+        );
+
+        assert_eq!(
+            transform.framed_position(&gcrf_position),
+            Err(FrameError::WrongFrame {
+                expected: Frame::ITRF,
+                found: Frame::GCRF
+            })
+        );
+        let later = FramedVector::new(Frame::ITRF, epoch + 1., position);
+        assert_eq!(
+            transform.framed_state(&framed_position, &later),
+            Err(FrameError::WrongEpoch {
+                expected: epoch,
+                found: epoch + 1.
+            })
+        );
+
+        let j2000 = FramedVector::new(Frame::GCRF, epoch, expected_position).to_frame(Frame::J2000);
+        let through_transform = FrameTransform::new(Frame::GCRF, Frame::J2000, epoch)
+            .framed_position(&FramedVector::new(Frame::GCRF, epoch, gcrf_position.vector))
+            .unwrap();
+        assert_eq!(j2000, through_transform);
+    }
+}
@@ -0,0 +1,244 @@
+//! Nutation series in longitude and obliquity, summed over multiples of the five Delaunay
+//! arguments.
+
+use super::*;
+
+/// Multiples of `l`, `l'`, `F`, `D` and `Omega`, then the longitude coefficients `psi + psi_t T`
+/// and obliquity coefficients `eps + eps_t T`, in units of 0.1 mas.
+///
+/// The IAU 1980 series truncated to the terms of at least 0.3 mas (Meeus, Astronomical Algorithms,
+/// table 22.A), which leaves out about 1 mas.
+#[rustfmt::skip]
+const IAU_1980: [([i8; 5], f64, f64, f64, f64); 63] = [
+    ([0, 0, 0, 0, 1], -171_996., -174.2, 92_025., 8.9),
+    ([0, 0, 2, -2, 2], -13_187., -1.6, 5_736., -3.1),
+    ([0, 0, 2, 0, 2], -2_274., -0.2, 977., -0.5),
+    ([0, 0, 0, 0, 2], 2_062., 0.2, -895., 0.5),
+    ([0, 1, 0, 0, 0], 1_426., -3.4, 54., -0.1),
+    ([1, 0, 0, 0, 0], 712., 0.1, -7., 0.),
+    ([0, 1, 2, -2, 2], -517., 1.2, 224., -0.6),
+    ([0, 0, 2, 0, 1], -386., -0.4, 200., 0.),
+    ([1, 0, 2, 0, 2], -301., 0., 129., -0.1),
+    ([0, -1, 2, -2, 2], 217., -0.5, -95., 0.3),
+    ([1, 0, 0, -2, 0], -158., 0., 0., 0.),
+    ([0, 0, 2, -2, 1], 129., 0.1, -70., 0.),
+    ([-1, 0, 2, 0, 2], 123., 0., -53., 0.),
+    ([0, 0, 0, 2, 0], 63., 0., 0., 0.),
+    ([1, 0, 0, 0, 1], 63., 0.1, -33., 0.),
+    ([-1, 0, 2, 2, 2], -59., 0., 26., 0.),
+    ([-1, 0, 0, 0, 1], -58., -0.1, 32., 0.),
+    ([1, 0, 2, 0, 1], -51., 0., 27., 0.),
+    ([2, 0, 0, -2, 0], 48., 0., 0., 0.),
+    ([-2, 0, 2, 0, 1], 46., 0., -24., 0.),
+    ([0, 0, 2, 2, 2], -38., 0., 16., 0.),
+    ([2, 0, 2, 0, 2], -31., 0., 13., 0.),
+    ([2, 0, 0, 0, 0], 29., 0., 0., 0.),
+    ([1, 0, 2, -2, 2], 29., 0., -12., 0.),
+    ([0, 0, 2, 0, 0], 26., 0., 0., 0.),
+    ([0, 0, 2, -2, 0], -22., 0., 0., 0.),
+    ([-1, 0, 2, 0, 1], 21., 0., -10., 0.),
+    ([0, 2, 0, 0, 0], 17., -0.1, 0., 0.),
+    ([-1, 0, 0, 2, 1], 16., 0., -8., 0.),
+    ([0, 2, 2, -2, 2], -16., 0.1, 7., 0.),
+    ([0, 1, 0, 0, 1], -15., 0., 9., 0.),
+    ([1, 0, 0, -2, 1], -13., 0., 7., 0.),
+    ([0, -1, 0, 0, 1], -12., 0., 6., 0.),
+    ([2, 0, -2, 0, 0], 11., 0., 0., 0.),
+    ([-1, 0, 2, 2, 1], -10., 0., 5., 0.),
+    ([1, 0, 2, 2, 2], -8., 0., 3., 0.),
+    ([0, 1, 2, 0, 2], 7., 0., -3., 0.),
+    ([1, 1, 0, -2, 0], -7., 0., 0., 0.),
+    ([0, -1, 2, 0, 2], -7., 0., 3., 0.),
+    ([0, 0, 2, 2, 1], -7., 0., 3., 0.),
+    ([1, 0, 0, 2, 0], 6., 0., 0., 0.),
+    ([2, 0, 2, -2, 2], 6., 0., -3., 0.),
+    ([1, 0, 2, -2, 1], 6., 0., -3., 0.),
+    ([-2, 0, 0, 2, 1], -6., 0., 3., 0.),
+    ([0, 0, 0, 2, 1], -6., 0., 3., 0.),
+    ([1, -1, 0, 0, 0], 5., 0., 0., 0.),
+    ([0, -1, 2, -2, 1], -5., 0., 3., 0.),
+    ([0, 0, 0, -2, 1], -5., 0., 3., 0.),
+    ([2, 0, 2, 0, 1], -5., 0., 3., 0.),
+    ([2, 0, 0, -2, 1], 4., 0., 0., 0.),
+    ([0, 1, 2, -2, 1], 4., 0., 0., 0.),
+    ([1, 0, -2, 0, 0], 4., 0., 0., 0.),
+    ([1, 0, 0, -1, 0], -4., 0., 0., 0.),
+    ([0, 1, 0, -2, 0], -4., 0., 0., 0.),
+    ([0, 0, 0, 1, 0], -4., 0., 0., 0.),
+    ([1, 0, 2, 0, 0], 3., 0., 0., 0.),
+    ([-2, 0, 2, 0, 2], -3., 0., 0., 0.),
+    ([1, -1, 0, -1, 0], -3., 0., 0., 0.),
+    ([1, 1, 0, 0, 0], -3., 0., 0., 0.),
+    ([1, -1, 2, 0, 2], -3., 0., 0., 0.),
+    ([-1, -1, 2, 2, 2], -3., 0., 0., 0.),
+    ([3, 0, 2, 0, 2], -3., 0., 0., 0.),
+    ([0, -1, 2, 2, 2], -3., 0., 0., 0.),
+];
+
+type Term2000 = ([i8; 5], f64, f64, f64, f64, f64, f64);
+
+/// Multiples of `l`, `l'`, `F`, `D` and `Omega`, then the longitude coefficients `psi + psi_t T`
+/// and `psi_c` on the cosine, and the obliquity coefficients `eps + eps_t T` and `eps_s` on the
+/// sine, in units of 0.1 µas.
+///
+/// The leading terms of the IAU 2000B luni-solar series (IERS Conventions 2010, section 5.5.4),
+/// which leave out a few mas.
+#[rustfmt::skip]
+const IAU_2000B: [Term2000; 40] = [
+    ([0, 0, 0, 0, 1], -172_064_161., -174_666., 33_386., 92_052_331., 9_086., 15_377.),
+    ([0, 0, 2, -2, 2], -13_170_906., -1_675., -13_696., 5_730_336., -3_015., -4_587.),
+    ([0, 0, 2, 0, 2], -2_276_413., -234., 2_796., 978_459., -485., 1_374.),
+    ([0, 0, 0, 0, 2], 2_074_554., 207., -698., -897_492., 470., -291.),
+    ([0, 1, 0, 0, 0], 1_475_877., -3_633., 11_817., 73_871., -184., -1_924.),
+    ([0, 1, 2, -2, 2], -516_821., 1_226., -524., 224_386., -677., -174.),
+    ([1, 0, 0, 0, 0], 711_159., 73., -872., -6_750., 0., 358.),
+    ([0, 0, 2, 0, 1], -387_298., -367., 380., 200_728., 18., 318.),
+    ([1, 0, 2, 0, 2], -301_461., -36., 816., 129_025., -63., 367.),
+    ([0, -1, 2, -2, 2], 215_829., -494., 111., -95_929., 299., 132.),
+    ([0, 0, 2, -2, 1], 128_227., 137., 181., -68_982., -9., 39.),
+    ([-1, 0, 2, 0, 2], 123_457., 11., 19., -53_311., 32., -4.),
+    ([-1, 0, 0, 2, 0], 156_994., 10., -168., -1_235., 0., 82.),
+    ([1, 0, 0, 0, 1], 63_110., 63., 27., -33_228., 0., -9.),
+    ([-1, 0, 0, 0, 1], -57_976., -63., -189., 31_429., 0., -75.),
+    ([-1, 0, 2, 2, 2], -59_641., -11., 149., 25_543., -11., 66.),
+    ([1, 0, 2, 0, 1], -51_613., -42., 129., 26_366., 0., 78.),
+    ([-2, 0, 2, 0, 1], 45_893., 50., 31., -24_236., -10., 20.),
+    ([0, 0, 0, 2, 0], 63_384., 11., -150., -1_220., 0., 29.),
+    ([0, 0, 2, 2, 2], -38_571., -1., 158., 16_452., -11., 68.),
+    ([0, -2, 2, -2, 2], 32_481., 0., 0., -13_870., 0., 0.),
+    ([-2, 0, 0, 2, 0], -47_722., 0., -18., 477., 0., -25.),
+    ([2, 0, 2, 0, 2], -31_046., -1., 131., 13_238., -11., 59.),
+    ([1, 0, 2, -2, 2], 28_593., 0., -1., -12_338., 10., -3.),
+    ([-1, 0, 2, 0, 1], 20_441., 21., 10., -10_758., 0., -3.),
+    ([2, 0, 0, 0, 0], 29_243., 0., -74., -609., 0., 13.),
+    ([0, 0, 2, 0, 0], 25_887., 0., -66., -550., 0., 11.),
+    ([0, 1, 0, 0, 1], -14_053., -25., 79., 8_551., -2., -45.),
+    ([-1, 0, 0, 2, 1], 15_164., 10., 11., -8_001., 0., -1.),
+    ([0, 2, 2, -2, 2], -15_794., 72., -16., 6_850., -42., -5.),
+    ([0, 0, -2, 2, 0], 21_783., 0., 13., -167., 0., 13.),
+    ([1, 0, 0, -2, 1], -12_873., -10., -37., 6_953., 0., -14.),
+    ([0, -1, 0, 0, 1], -12_654., 11., 63., 6_415., 0., 26.),
+    ([-1, 0, 2, 2, 1], -10_204., 0., 25., 5_222., 0., 15.),
+    ([0, 2, 0, 0, 0], 16_707., -85., -10., 168., -1., 10.),
+    ([1, 0, 2, 2, 2], -7_691., 0., 44., 3_268., 0., 19.),
+    ([-2, 0, 2, 0, 0], -11_024., 0., -14., 104., 0., 2.),
+    ([0, 1, 2, 0, 2], 7_566., -21., -11., -3_250., 0., -5.),
+    ([0, 0, 2, 2, 1], -6_637., -11., 25., 3_353., 0., 14.),
+    ([0, -1, 2, 0, 2], -7_141., 21., 8., 3_070., 0., 4.),
+];
+
+/// Fixed offsets of IAU 2000B standing in for the planetary terms, in mas
+const IAU_2000B_PLANETARY: (f64, f64) = (-0.135, 0.388);
+
+/// Delaunay arguments `l`, `l'`, `F`, `D` and `Omega` in radians, at `T` Julian centuries of TT
+/// since J2000 (IERS Conventions 2010, equation 5.43)
+pub(super) fn delaunay_arguments(centuries: f64) -> [f64; 5] {
+    let polynomials: [[f64; 5]; 5] = [
+        [
+            485_868.249_036,
+            1_717_915_923.217_8,
+            31.879_2,
+            0.051_635,
+            -0.000_244_70,
+        ],
+        [
+            1_287_104.793_05,
+            129_596_581.048_1,
+            -0.553_2,
+            0.000_136,
+            -0.000_011_49,
+        ],
+        [
+            335_779.526_232,
+            1_739_527_262.847_8,
+            -12.751_2,
+            -0.001_037,
+            0.000_004_17,
+        ],
+        [
+            1_072_260.703_69,
+            1_602_961_601.209_0,
+            -6.370_6,
+            0.006_593,
+            -0.000_031_69,
+        ],
+        [
+            450_160.398_036,
+            -6_962_890.543_1,
+            7.472_2,
+            0.007_702,
+            -0.000_059_39,
+        ],
+    ];
+    polynomials.map(|coefficients| {
+        let arcseconds = coefficients
+            .iter()
+            .rev()
+            .fold(0., |sum, coefficient| sum * centuries + coefficient);
+        (arcseconds % 1_296_000.) * ARCSECOND
+    })
+}
+
+fn argument(multiples: &[i8; 5], arguments: &[f64; 5]) -> f64 {
+    multiples
+        .iter()
+        .zip(arguments)
+        .map(|(multiple, argument)| f64::from(*multiple) * argument)
+        .sum()
+}
+
+/// Nutation in longitude and obliquity in radians from the IAU 1980 theory, at `T` Julian
+/// centuries of TT since J2000
+pub(super) fn iau_1980(centuries: f64) -> (f64, f64) {
+    let arguments = delaunay_arguments(centuries);
+    let (longitude, obliquity) = IAU_1980.iter().fold(
+        (0., 0.),
+        |(longitude, obliquity), (multiples, psi, psi_t, eps, eps_t)| {
+            let (sin, cos) = argument(multiples, &arguments).sin_cos();
+            (
+                longitude + (psi + psi_t * centuries) * sin,
+                obliquity + (eps + eps_t * centuries) * cos,
+            )
+        },
+    );
+    (longitude * 1e-4 * ARCSECOND, obliquity * 1e-4 * ARCSECOND)
+}
+
+/// Nutation in longitude and obliquity in radians from the IAU 2000B theory, at `T` Julian
+/// centuries of TT since J2000
+pub(super) fn iau_2000b(centuries: f64) -> (f64, f64) {
+    let arguments = delaunay_arguments(centuries);
+    let (longitude, obliquity) = IAU_2000B.iter().fold(
+        (0., 0.),
+        |(longitude, obliquity), (multiples, psi, psi_t, psi_c, eps, eps_t, eps_s)| {
+            let (sin, cos) = argument(multiples, &arguments).sin_cos();
+            (
+                longitude + (psi + psi_t * centuries) * sin + psi_c * cos,
+                obliquity + (eps + eps_t * centuries) * cos + eps_s * sin,
+            )
+        },
+    );
+    let (planetary_longitude, planetary_obliquity) = IAU_2000B_PLANETARY;
+    (
+        (longitude * 1e-7 + planetary_longitude * 1e-3) * ARCSECOND,
+        (obliquity * 1e-7 + planetary_obliquity * 1e-3) * ARCSECOND,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Meeus, Astronomical Algorithms, example 22.a: on 1987 April 10 at 0h TT the nutation is
+    /// -3.788" in longitude and +9.443" in obliquity. Both theories agree to within a few mas.
+    fn nutation_test() {
+        let centuries = -0.127_296_372_348;
+        let (longitude, obliquity) = iau_1980(centuries);
+        assert_relative_eq!(longitude / ARCSECOND, -3.788, epsilon = 5e-4);
+        assert_relative_eq!(obliquity / ARCSECOND, 9.443, epsilon = 5e-4);
+        let (longitude_2000, obliquity_2000) = iau_2000b(centuries);
+        assert_relative_eq!(longitude_2000, longitude, epsilon = 0.02 * ARCSECOND);
+        assert_relative_eq!(obliquity_2000, obliquity, epsilon = 0.01 * ARCSECOND);
+    }
+}
@@ -0,0 +1,263 @@
+//! Horizon frames at a ground station, and the look angles of an orbiting body from it.
+
+use std::f64::consts::FRAC_PI_2;
+
+use crate::orbit::structs::CartesianState;
+
+use super::*;
+
+/// Atmospheric conditions at a station, which bend the line of sight towards the zenith
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Refraction {
+    /// Pressure in Pa
+    pub pressure: f64,
+    /// Temperature in K
+    pub temperature: f64,
+}
+
+impl Default for Refraction {
+    /// The standard atmosphere at sea level, 101.0 kPa and 10 °C
+    fn default() -> Self {
+        Self {
+            pressure: 101_000.,
+            temperature: 283.,
+        }
+    }
+}
+
+impl Refraction {
+    /// Amount by which refraction raises a body at geometric `elevation`, in radians.
+    ///
+    /// This is Saemundsson's formula (Sky & Telescope 72, 1986), good to about 4" above 15° and
+    /// to a few arcminutes near the horizon. Below 1° under the horizon it is held at its value
+    /// there.
+    pub fn correction(&self, elevation: f64) -> f64 {
+        let degrees = elevation.to_degrees().max(-1.);
+        let arcminutes = 1.02 / (degrees + 10.3 / (degrees + 5.11)).to_radians().tan();
+        let scale = self.pressure / 101_000. * 283. / self.temperature;
+        (arcminutes * scale / 60.).to_radians()
+    }
+}
+
+/// Direction and distance of a body from a ground station
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Observation {
+    /// Angle from north towards east in radians, on [0, 2pi)
+    pub azimuth: f64,
+    /// Angle above the horizon in radians
+    pub elevation: f64,
+    /// Distance in m
+    pub range: f64,
+    /// Rate of change of the range in m/s, positive when receding
+    pub range_rate: f64,
+}
+
+/// Site on the surface of the Earth
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroundStation {
+    pub name: String,
+    pub location: Geodetic,
+    /// Reference surface that `location` is given over
+    pub ellipsoid: Ellipsoid,
+    /// Conditions to correct elevations for, or none to report geometric elevations
+    pub refraction: Option<Refraction>,
+    /// Elevation in radians above which the station can reach a body
+    pub minimum_elevation: f64,
+}
+
+impl GroundStation {
+    /// Station at the given coordinates over WGS84, without refraction, which reaches down to the
+    /// horizon
+    pub fn new(name: &str, location: Geodetic) -> Self {
+        Self {
+            name: name.to_string(),
+            location,
+            ellipsoid: Ellipsoid::WGS84,
+            refraction: None,
+            minimum_elevation: 0.,
+        }
+    }
+
+    pub fn with_ellipsoid(self, ellipsoid: Ellipsoid) -> Self {
+        Self { ellipsoid, ..self }
+    }
+
+    pub fn with_refraction(self, refraction: Refraction) -> Self {
+        Self {
+            refraction: Some(refraction),
+            ..self
+        }
+    }
+
+    pub fn with_minimum_elevation(self, minimum_elevation: f64) -> Self {
+        Self {
+            minimum_elevation,
+            ..self
+        }
+    }
+
+    /// Position in ITRF
+    pub fn position(&self) -> Vector3 {
+        self.ellipsoid.position(&self.location)
+    }
+
+    /// Rotation from ITRF to the east, north, up frame at the station
+    pub fn enu_rotation(&self) -> Quaternion {
+        rotation(Z_AXIS, FRAC_PI_2 + self.location.longitude)
+            * rotation(X_AXIS, FRAC_PI_2 - self.location.latitude)
+    }
+
+    /// Rotation from ITRF to the south, east, zenith frame at the station
+    pub fn sez_rotation(&self) -> Quaternion {
+        rotation(Z_AXIS, self.location.longitude)
+            * rotation(Y_AXIS, FRAC_PI_2 - self.location.latitude)
+    }
+
+    /// East, north and up components of an ITRF position relative to the station
+    pub fn enu(&self, position: &Vector3) -> Vector3 {
+        self.enu_rotation()
+            .rotated_vec_alias(&(position.clone() - self.position()))
+    }
+
+    /// South, east and zenith components of an ITRF position relative to the station
+    pub fn sez(&self, position: &Vector3) -> Vector3 {
+        self.sez_rotation()
+            .rotated_vec_alias(&(position.clone() - self.position()))
+    }
+
+    /// Look angles, range and range rate of a body at `state`, given in GCRF, at `epoch`
+    pub fn observe(&self, state: &CartesianState, epoch: Epoch) -> Observation {
+        let (position, velocity) = FrameTransform::new(Frame::GCRF, Frame::ITRF, epoch)
+            .state(&state.position, &state.velocity);
+        let to_enu = self.enu_rotation();
+        let relative = to_enu.rotated_vec_alias(&(position - self.position()));
+        let velocity = to_enu.rotated_vec_alias(&velocity);
+        let range = relative.norm();
+        let [east, north, up] = relative.elem;
+        let elevation = (up / range).asin();
+        Observation {
+            azimuth: east.atan2(north).rem_euclid(2. * PI),
+            elevation: elevation + self.refraction.map_or(0., |r| r.correction(elevation)),
+            range,
+            range_rate: relative.dot(&velocity) / range,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing;
+
+    fn station() -> GroundStation {
+        GroundStation::new(
+            "Kiruna",
+            Geodetic::new(67.857_f64.to_radians(), 20.964_f64.to_radians(), 402.),
+        )
+    }
+
+    #[test]
+    /// The up axis is the ellipsoid's normal, north points to the pole, and SEZ is ENU turned a
+    /// quarter turn about the vertical
+    fn axes_test() {
+        let station = station();
+        let up = station
+            .enu_rotation()
+            .rotated_vec_alibi(&Vector3::from(Z_AXIS));
+        let above = Geodetic {
+            height: station.location.height + 1.,
+            ..station.location
+        };
+        let expected = station.ellipsoid.position(&above) - station.position();
+        testing::assert_array_eq_atol(&up.elem, &expected.elem, 1e-9);
+
+        let north = station
+            .enu_rotation()
+            .rotated_vec_alibi(&Vector3::from(Y_AXIS));
+        assert!(north.elem[2] > 0.);
+        assert_relative_eq!(
+            north.elem[2],
+            station.location.latitude.cos(),
+            epsilon = 1e-15
+        );
+
+        let target = Vector3::new([2e6, 1e6, 7e6]);
+        let [east, north, up] = station.enu(&target).elem;
+        testing::assert_array_eq_atol(&station.sez(&target).elem, &[-north, east, up], 1e-8);
+    }
+
+    #[test]
+    /// A body straight above the station is at 90° elevation and its height in range, and moving
+    /// with the Earth it has no range rate
+    fn zenith_test() {
+        let station = station();
+        let epoch: Epoch = "2021-09-01T00:00:00Z".parse().unwrap();
+        let above = Geodetic {
+            height: 800e3,
+            ..station.location
+        };
+        let itrf_position = station.ellipsoid.position(&above);
+        let (position, velocity) = FrameTransform::new(Frame::ITRF, Frame::GCRF, epoch)
+            .state(&itrf_position, &Vector3::new([0.; 3]));
+        let state = CartesianState::new(position, velocity);
+        let observation = station.observe(&state, epoch);
+        assert_relative_eq!(observation.elevation, FRAC_PI_2, epsilon = 1e-9);
+        assert_relative_eq!(observation.range, 800e3 - 402., epsilon = 1e-6);
+        assert_relative_eq!(observation.range_rate, 0., epsilon = 1e-9);
+    }
+
+    #[test]
+    /// A body placed north-east of the station and climbing away from it is seen there
+    fn observe_test() {
+        let station = station();
+        let epoch: Epoch = "2021-09-01T00:00:00Z".parse().unwrap();
+        let azimuth = 45_f64.to_radians();
+        let elevation = 30_f64.to_radians();
+        let range = 1_500e3;
+        let direction = Vector3::new([
+            elevation.cos() * azimuth.sin(),
+            elevation.cos() * azimuth.cos(),
+            elevation.sin(),
+        ]);
+        let from_enu = station.enu_rotation().inverted();
+        let itrf_position =
+            station.position() + from_enu.rotated_vec_alias(&(direction.clone() * range));
+        let itrf_velocity = from_enu.rotated_vec_alias(&(direction * 2e3));
+        let (position, velocity) = FrameTransform::new(Frame::ITRF, Frame::GCRF, epoch)
+            .state(&itrf_position, &itrf_velocity);
+        let state = CartesianState::new(position, velocity);
+
+        let observation = station.observe(&state, epoch);
+        assert_relative_eq!(observation.azimuth, azimuth, epsilon = 1e-12);
+        assert_relative_eq!(observation.elevation, elevation, epsilon = 1e-12);
+        assert_relative_eq!(observation.range, range, epsilon = 1e-6);
+        assert_relative_eq!(observation.range_rate, 2e3, epsilon = 1e-9);
+
+        // Refraction lifts a body at 30° by about 1.7'
+        let refracted = station
+            .with_refraction(Refraction::default())
+            .observe(&state, epoch);
+        let lift = (refracted.elevation - elevation).to_degrees() * 60.;
+        assert_relative_eq!(lift, 1.7, epsilon = 0.05);
+    }
+
+    #[test]
+    /// Refraction lifts a body on the geometric horizon by about 29', and thins with the air
+    fn refraction_test() {
+        let standard = Refraction::default();
+        let horizon = standard.correction(0.).to_degrees() * 60.;
+        assert_relative_eq!(horizon, 29., epsilon = 0.1);
+        assert_relative_eq!(standard.correction(FRAC_PI_2), 0., epsilon = 1e-6);
+        let thin = Refraction {
+            pressure: 50_500.,
+            ..standard
+        };
+        assert_relative_eq!(
+            thin.correction(0.),
+            standard.correction(0.) / 2.,
+            epsilon = 1e-15
+        );
+        assert!(standard.correction(-0.5) == standard.correction(-0.1));
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::VecDeque;
+
+use super::runge_kutta::{check_finite, eighth_order_step, fixed_steps, EIGHTH_ORDER_EVALUATIONS};
+use super::*;
+
+/// Highest order supported, which the eighth-order starting steps can keep up with
+const MAX_ORDER: usize = 8;
+
+/// Adams–Bashforth–Moulton predictor-corrector with a fixed step, in PECE mode. Each step costs
+/// two derivative evaluations whatever the order, which makes it cheap for expensive force models
+/// with smooth dynamics. The first `order - 1` steps are taken with an eighth-order Runge–Kutta
+/// method to build up the history.
+#[derive(Clone, Debug)]
+pub struct AdamsBashforthMoulton {
+    step: f64,
+    order: usize,
+    dense_output: bool,
+}
+
+impl AdamsBashforthMoulton {
+    /// Method of the given order, from 1 to 8. The step is shrunk slightly if needed so a whole
+    /// number of steps spans the integration.
+    pub fn new(step: f64, order: usize) -> Self {
+        Self {
+            step,
+            order,
+            dense_output: false,
+        }
+    }
+
+    /// Records a cubic Hermite interpolant for every step, see `Solution::at`
+    pub fn with_dense_output(self) -> Self {
+        Self {
+            dense_output: true,
+            ..self
+        }
+    }
+}
+
+impl Integrator for AdamsBashforthMoulton {
+    fn integrate<S: State, D: Dynamics<S>>(
+        &self,
+        dynamics: &D,
+        start: f64,
+        state: &S,
+        end: f64,
+    ) -> Result<Solution<S>, IntegrationError> {
+        if !(1..=MAX_ORDER).contains(&self.order) {
+            return Err(IntegrationError::InvalidSettings(
+                "order must be between 1 and 8",
+            ));
+        }
+        let (count, step) = fixed_steps(self.step, start, end)?;
+        let dynamics = &Timed { dynamics, start };
+        // Nodes relative to the latest step, in units of the step
+        let predictor =
+            integration_weights(&(0..self.order).map(|i| -(i as f64)).collect::<Vec<_>>());
+        let corrector =
+            integration_weights(&(0..self.order).map(|i| 1. - i as f64).collect::<Vec<_>>());
+
+        let mut solution = Solution {
+            time: start,
+            state: state.clone(),
+            stats: Stats::default(),
+            segments: Vec::new(),
+        };
+        // Derivatives at the latest steps, most recent first
+        let mut history = VecDeque::with_capacity(self.order);
+        history.push_front(dynamics.derivative(start, state));
+        solution.stats.evaluations += 1;
+
+        for i in 0..count {
+            let time = solution.time;
+            let next_time = start + (i + 1) as f64 * step;
+            let next = if history.len() < self.order {
+                solution.stats.evaluations += EIGHTH_ORDER_EVALUATIONS;
+                eighth_order_step(dynamics, time, &solution.state, &history[0], step)
+            } else {
+                let derivatives = history.make_contiguous();
+                let predicted = step_from(&solution.state, step, &predictor, derivatives);
+                let predicted_derivative = dynamics.derivative(next_time, &predicted);
+                solution.stats.evaluations += 1;
+                let mut corrected = step_from(
+                    &solution.state,
+                    step,
+                    &corrector[1..],
+                    &derivatives[..self.order - 1],
+                );
+                corrected.add_scaled(step * corrector[0], &predicted_derivative);
+                corrected
+            };
+            check_finite(&next, next_time)?;
+            let next_derivative = dynamics.derivative(next_time, &next);
+            solution.stats.evaluations += 1;
+            solution.stats.accepted_steps += 1;
+
+            if self.dense_output {
+                solution.segments.push(Segment {
+                    start: time,
+                    step,
+                    interpolant: Interpolant::Hermite3 {
+                        states: [solution.state.clone(), next.clone()],
+                        derivatives: [history[0].clone(), next_derivative.clone()],
+                    },
+                });
+            }
+            if history.len() == self.order {
+                history.pop_back();
+            }
+            history.push_front(next_derivative);
+            solution.time = next_time;
+            solution.state = next;
+        }
+        Ok(solution.timed(start))
+    }
+}
+
+/// Weights which integrate the polynomial through values at `nodes` over `[0, 1]`, by integrating
+/// each Lagrange basis polynomial
+fn integration_weights(nodes: &[f64]) -> Vec<f64> {
+    nodes
+        .iter()
+        .enumerate()
+        .map(|(j, node)| {
+            // Coefficients of the basis polynomial, lowest power first
+            let mut basis = vec![1.];
+            for (_, other) in nodes.iter().enumerate().filter(|(i, _)| *i != j) {
+                let scale = 1. / (node - other);
+                let mut product = vec![0.; basis.len() + 1];
+                for (power, coefficient) in basis.iter().enumerate() {
+                    product[power + 1] += coefficient * scale;
+                    product[power] -= coefficient * other * scale;
+                }
+                basis = product;
+            }
+            basis
+                .iter()
+                .enumerate()
+                .map(|(power, coefficient)| coefficient / (power + 1) as f64)
+                .sum()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f64::consts::PI;
+
+    #[test]
+    /// The familiar fourth-order coefficients
+    fn weights_test() {
+        let bashforth = integration_weights(&[0., -1., -2., -3.]);
+        let moulton = integration_weights(&[1., 0., -1., -2.]);
+        let expected_bashforth = [55. / 24., -59. / 24., 37. / 24., -9. / 24.];
+        let expected_moulton = [9. / 24., 19. / 24., -5. / 24., 1. / 24.];
+        for (weight, expected) in bashforth.iter().zip(expected_bashforth) {
+            assert_relative_eq!(*weight, expected, epsilon = 1e-14);
+        }
+        for (weight, expected) in moulton.iter().zip(expected_moulton) {
+            assert_relative_eq!(*weight, expected, epsilon = 1e-14);
+        }
+        assert_eq!(integration_weights(&[0.]), [1.]);
+    }
+
+    fn two_body(_: f64, state: &[f64; 4]) -> [f64; 4] {
+        let [x, y, vx, vy] = *state;
+        let r3 = x.hypot(y).powi(3);
+        [vx, vy, -x / r3, -y / r3]
+    }
+
+    fn revolution_error(step: f64, order: usize) -> f64 {
+        let start = [0.8, 0., 0., 1.5_f64.sqrt()];
+        let solution = AdamsBashforthMoulton::new(step, order)
+            .integrate(&two_body, 0., &start, 2. * PI)
+            .unwrap();
+        solution
+            .state
+            .iter()
+            .zip(start)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0., f64::max)
+    }
+
+    #[test]
+    /// Halving the step cuts the error after one revolution by about 2^order
+    fn order_test() {
+        for order in [2, 4, 6] {
+            let ratio = revolution_error(0.01, order) / revolution_error(0.005, order);
+            let observed = ratio.log2();
+            assert!(
+                (observed - order as f64).abs() < 0.3,
+                "order {order}, observed {observed}"
+            );
+        }
+    }
+
+    #[test]
+    fn stats_test() {
+        let solution = AdamsBashforthMoulton::new(0.1, 4)
+            .with_dense_output()
+            .integrate(&two_body, 0., &[0.8, 0., 0., 1.5_f64.sqrt()], 1.)
+            .unwrap();
+        assert_eq!(solution.stats.accepted_steps, 10);
+        assert_eq!(solution.stats.evaluations, 1 + 3 * 13 + 7 * 2);
+        assert_eq!(solution.time, 1.);
+        assert!(solution.at(0.55).is_some());
+        assert!(AdamsBashforthMoulton::new(0.1, 9)
+            .integrate(&two_body, 0., &[1., 0., 0., 1.], 1.)
+            .is_err());
+    }
+}
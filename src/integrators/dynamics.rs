@@ -0,0 +1,120 @@
+use super::*;
+
+/// A perturbing acceleration on an orbiting body, on top of the point-mass gravity of the central
+/// body
+pub trait ForceModel {
+    /// Acceleration at integration time `time`, the independent variable exactly as the
+    /// integrator steps it from its `start` rather than counted from zero
+    fn acceleration(&self, time: f64, state: &CartesianState) -> Vector3;
+}
+
+impl<F> ForceModel for F
+where
+    F: Fn(f64, &CartesianState) -> Vector3,
+{
+    fn acceleration(&self, time: f64, state: &CartesianState) -> Vector3 {
+        self(time, state)
+    }
+}
+
+/// Motion of a `CartesianState` under the point-mass gravity of its central body and any number of
+/// perturbing forces
+#[derive(Default)]
+pub struct OrbitDynamics {
+    forces: Vec<Box<dyn ForceModel>>,
+}
+
+impl OrbitDynamics {
+    /// Unperturbed two-body motion
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_force(mut self, force: impl ForceModel + 'static) -> Self {
+        self.forces.push(Box::new(force));
+        self
+    }
+}
+
+impl Dynamics<CartesianState> for OrbitDynamics {
+    fn derivative(&self, time: f64, state: &CartesianState) -> CartesianState {
+        let radius = state.position.norm();
+        let mut acceleration = state.position.clone() * (-state.central_body.mu / radius.powi(3));
+        for force in &self.forces {
+            acceleration = acceleration + force.acceleration(time, state);
+        }
+        CartesianState {
+            position: state.velocity.clone(),
+            velocity: acceleration,
+            central_body: state.central_body,
+            epoch: state.epoch,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::bodies;
+    use crate::orbit::structs::COE;
+    use crate::orbit::Shaped;
+    use crate::time::{Epoch, TimeScale};
+
+    #[test]
+    fn two_body_test() {
+        let state = CartesianState::new([7_000_000., 0., 0.].into(), [0., 7_500., 0.].into())
+            .with_central_body(bodies::MARS);
+        let derivative = OrbitDynamics::new().derivative(0., &state);
+        assert_eq!(derivative.position, state.velocity);
+        assert_relative_eq!(
+            derivative.velocity.elem[0],
+            -bodies::MARS.mu / 7_000_000_f64.powi(2)
+        );
+        assert_eq!(derivative.central_body, bodies::MARS);
+    }
+
+    #[test]
+    /// A small tangential thrust raises the semi-major axis at the rate given by the Gauss
+    /// variational equations, `da/dt = 2 a^2 v f / mu`
+    fn tangential_thrust_test() {
+        let thrust = 1e-3;
+        let dynamics = OrbitDynamics::new().with_force(move |_: f64, state: &CartesianState| {
+            let direction = state.velocity.clone() * (1. / state.velocity.norm());
+            direction * thrust
+        });
+        let coe = COE::new(7_000_000., 0., 0.5, 0., 0., 0.);
+        let time_of_flight = 600.;
+        let solution = AdaptiveRungeKutta::dormand_prince_87(1e-6, 1e-12)
+            .integrate(&dynamics, 0., &CartesianState::from(&coe), time_of_flight)
+            .unwrap();
+        let raised = COE::from(&solution.state);
+        let speed = coe.vis_viva_speed(0.);
+        let rate = 2. * coe.semi_major_axis.powi(2) * speed * thrust / bodies::EARTH.mu;
+        assert_relative_eq!(
+            raised.semi_major_axis - coe.semi_major_axis,
+            rate * time_of_flight,
+            max_relative = 1e-3
+        );
+    }
+
+    #[test]
+    /// The state handed back, at the end or from dense output, and each state the forces see are
+    /// at their own instants rather than the one the integration started from
+    fn epoch_test() {
+        let epoch = Epoch::from_seconds_since_j2000(1e8, TimeScale::TT);
+        let state =
+            CartesianState::from(&COE::new(7_000_000., 0.01, 0.5, 0., 0., 0.)).with_epoch(epoch);
+        let start = 100.;
+        let dynamics = OrbitDynamics::new().with_force(move |time: f64, state: &CartesianState| {
+            assert_eq!(state.epoch, Some(epoch + (time - start)));
+            Vector3::new([0.; 3])
+        });
+        let solution = RungeKutta4::new(60.)
+            .with_dense_output()
+            .integrate(&dynamics, start, &state, start + 600.)
+            .unwrap();
+        assert_eq!(solution.state.epoch, Some(epoch + 600.));
+        assert_eq!(solution.at(start + 90.).unwrap().epoch, Some(epoch + 90.));
+    }
+}
@@ -0,0 +1,361 @@
+//! Numerical integration of ordinary differential equations, for propagating orbits under
+//! perturbing forces.
+//!
+//! Integrators advance any [`State`] under some [`Dynamics`], either a closure or a type such as
+//! [`OrbitDynamics`], which moves a `CartesianState` under the gravity of its central body plus
+//! any number of [`ForceModel`]s.
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+
+use crate::orbit::structs::CartesianState;
+use crate::vector::Vector3;
+
+mod adams;
+mod dynamics;
+mod runge_kutta;
+
+pub use adams::AdamsBashforthMoulton;
+pub use dynamics::{ForceModel, OrbitDynamics};
+pub use runge_kutta::{AdaptiveRungeKutta, RungeKutta4};
+
+/// Anything an integrator can advance, which must behave like a vector of numbers. Derivatives
+/// are of the same type as the state itself.
+pub trait State: Clone {
+    /// Adds `scale * other` to this state
+    fn add_scaled(&mut self, scale: f64, other: &Self);
+
+    /// Every number in the state, in a fixed order, for estimating errors
+    fn components(&self) -> impl Iterator<Item = f64> + '_;
+
+    /// The state moved on to `elapsed` seconds after the instant it records, for states which
+    /// keep track of when they are.
+    ///
+    /// Integrators step states without touching that instant, and move it on from the start of
+    /// the integration wherever a state is passed to the dynamics or handed back.
+    fn after(&self, elapsed: f64) -> Cow<'_, Self> {
+        let _ = elapsed;
+        Cow::Borrowed(self)
+    }
+}
+
+impl State for f64 {
+    fn add_scaled(&mut self, scale: f64, other: &Self) {
+        *self += scale * other;
+    }
+
+    fn components(&self) -> impl Iterator<Item = f64> + '_ {
+        std::iter::once(*self)
+    }
+}
+
+impl<const N: usize> State for [f64; N] {
+    fn add_scaled(&mut self, scale: f64, other: &Self) {
+        for (elem, other) in self.iter_mut().zip(other) {
+            *elem += scale * other;
+        }
+    }
+
+    fn components(&self) -> impl Iterator<Item = f64> + '_ {
+        self.iter().copied()
+    }
+}
+
+impl State for Vec<f64> {
+    fn add_scaled(&mut self, scale: f64, other: &Self) {
+        for (elem, other) in self.iter_mut().zip(other) {
+            *elem += scale * other;
+        }
+    }
+
+    fn components(&self) -> impl Iterator<Item = f64> + '_ {
+        self.iter().copied()
+    }
+}
+
+impl State for Vector3 {
+    fn add_scaled(&mut self, scale: f64, other: &Self) {
+        self.elem.add_scaled(scale, &other.elem);
+    }
+
+    fn components(&self) -> impl Iterator<Item = f64> + '_ {
+        self.elem.components()
+    }
+}
+
+/// The derivative of a `CartesianState` holds the velocity in `position` and the acceleration in
+/// `velocity`
+impl State for CartesianState {
+    fn add_scaled(&mut self, scale: f64, other: &Self) {
+        self.position.add_scaled(scale, &other.position);
+        self.velocity.add_scaled(scale, &other.velocity);
+    }
+
+    fn components(&self) -> impl Iterator<Item = f64> + '_ {
+        self.position.components().chain(self.velocity.components())
+    }
+
+    fn after(&self, elapsed: f64) -> Cow<'_, Self> {
+        match self.epoch {
+            Some(epoch) => Cow::Owned(Self {
+                epoch: Some(epoch + elapsed),
+                ..self.clone()
+            }),
+            None => Cow::Borrowed(self),
+        }
+    }
+}
+
+/// Right-hand side of the differential equation `dy/dt = f(t, y)`
+pub trait Dynamics<S> {
+    fn derivative(&self, time: f64, state: &S) -> S;
+}
+
+impl<S, F> Dynamics<S> for F
+where
+    F: Fn(f64, &S) -> S,
+{
+    fn derivative(&self, time: f64, state: &S) -> S {
+        self(time, state)
+    }
+}
+
+/// Dynamics which see each state at its own instant, `time - start` seconds after the one the
+/// integration started from
+struct Timed<'a, D> {
+    dynamics: &'a D,
+    start: f64,
+}
+
+impl<S: State, D: Dynamics<S>> Dynamics<S> for Timed<'_, D> {
+    fn derivative(&self, time: f64, state: &S) -> S {
+        self.dynamics
+            .derivative(time, &state.after(time - self.start))
+    }
+}
+
+pub trait Integrator {
+    /// Integrates `dynamics` from `state` at `start` to `end`, which may be earlier than `start`
+    fn integrate<S: State, D: Dynamics<S>>(
+        &self,
+        dynamics: &D,
+        start: f64,
+        state: &S,
+        end: f64,
+    ) -> Result<Solution<S>, IntegrationError>;
+}
+
+/// Work done by an integrator
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub accepted_steps: usize,
+    /// Steps thrown away because their error estimate was too large
+    pub rejected_steps: usize,
+    /// Calls to `Dynamics::derivative`
+    pub evaluations: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Solution<S> {
+    /// Time the integration ended at
+    pub time: f64,
+    /// State at `time`
+    pub state: S,
+    pub stats: Stats,
+    /// Interpolants for every step, in the order they were taken, if dense output was requested
+    segments: Vec<Segment<S>>,
+}
+
+impl<S: State> Solution<S> {
+    /// Moves the final state on from the instant the integration started at `start`
+    fn timed(mut self, start: f64) -> Self {
+        self.state = self.state.after(self.time - start).into_owned();
+        self
+    }
+
+    /// State at any time covered by the integration, or `None` outside it or if dense output
+    /// wasn't requested
+    pub fn at(&self, time: f64) -> Option<S> {
+        let first = self.segments.first()?;
+        let direction = first.step.signum();
+        // Steps are ordered along the direction of integration
+        let index = self
+            .segments
+            .partition_point(|segment| (time - segment.start) * direction > 0.)
+            .max(1)
+            - 1;
+        let segment = &self.segments[index];
+        let theta = (time - segment.start) / segment.step;
+        (-1e-12..=1. + 1e-12).contains(&theta).then(|| {
+            let state = segment.interpolant.evaluate(theta, segment.step);
+            state.after(time - first.start).into_owned()
+        })
+    }
+}
+
+/// Continuous approximation of the solution over one step
+#[derive(Clone, Debug)]
+struct Segment<S> {
+    start: f64,
+    step: f64,
+    interpolant: Interpolant<S>,
+}
+
+#[derive(Clone, Debug)]
+enum Interpolant<S> {
+    /// Cubic Hermite polynomial through the states and derivatives at the ends of the step
+    Hermite3 { states: [S; 2], derivatives: [S; 2] },
+    /// Quintic Hermite polynomial through the states and derivatives at the start, middle and
+    /// end of the step
+    Hermite5 { states: [S; 3], derivatives: [S; 3] },
+    /// Continuous extension of the Dormand–Prince 5(4) pair, in the `rcont` form of Hairer's
+    /// DOPRI5
+    DormandPrince(Box<[S; 5]>),
+}
+
+impl<S: State> Interpolant<S> {
+    /// State a fraction `theta` of the way through a step of size `step`
+    fn evaluate(&self, theta: f64, step: f64) -> S {
+        let (t, t2, t3) = (theta, theta.powi(2), theta.powi(3));
+        match self {
+            Self::Hermite3 {
+                states,
+                derivatives,
+            } => {
+                let weights = [1. - 3. * t2 + 2. * t3, 3. * t2 - 2. * t3];
+                let derivative_weights = [t - 2. * t2 + t3, t3 - t2];
+                hermite(states, derivatives, &weights, &derivative_weights, step)
+            }
+            Self::Hermite5 {
+                states,
+                derivatives,
+            } => {
+                let (t4, t5) = (theta.powi(4), theta.powi(5));
+                let weights = [
+                    1. - 23. * t2 + 66. * t3 - 68. * t4 + 24. * t5,
+                    16. * t2 - 32. * t3 + 16. * t4,
+                    7. * t2 - 34. * t3 + 52. * t4 - 24. * t5,
+                ];
+                let derivative_weights = [
+                    t - 6. * t2 + 13. * t3 - 12. * t4 + 4. * t5,
+                    -8. * t2 + 32. * t3 - 40. * t4 + 16. * t5,
+                    -t2 + 5. * t3 - 8. * t4 + 4. * t5,
+                ];
+                hermite(states, derivatives, &weights, &derivative_weights, step)
+            }
+            Self::DormandPrince(rcont) => {
+                let s = 1. - theta;
+                let mut state = rcont[0].clone();
+                state.add_scaled(theta, &rcont[1]);
+                state.add_scaled(theta * s, &rcont[2]);
+                state.add_scaled(theta * s * theta, &rcont[3]);
+                state.add_scaled(theta * s * theta * s, &rcont[4]);
+                state
+            }
+        }
+    }
+}
+
+fn hermite<S: State>(
+    states: &[S],
+    derivatives: &[S],
+    weights: &[f64],
+    derivative_weights: &[f64],
+    step: f64,
+) -> S {
+    // States can only be added to, so scale the first one by adding the rest of its weight
+    let mut state = states[0].clone();
+    state.add_scaled(weights[0] - 1., &states[0]);
+    for (other, weight) in states.iter().zip(weights).skip(1) {
+        state.add_scaled(*weight, other);
+    }
+    for (derivative, weight) in derivatives.iter().zip(derivative_weights) {
+        state.add_scaled(step * weight, derivative);
+    }
+    state
+}
+
+/// `state + step * sum(weight * derivative)`, skipping zero weights
+fn step_from<S: State>(state: &S, step: f64, weights: &[f64], derivatives: &[S]) -> S {
+    let mut result = state.clone();
+    for (weight, derivative) in weights.iter().zip(derivatives) {
+        if *weight != 0. {
+            result.add_scaled(step * weight, derivative);
+        }
+    }
+    result
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum IntegrationError {
+    /// The step size needed to meet the tolerances fell below what `f64` can resolve at `time`
+    StepSizeTooSmall {
+        time: f64,
+    },
+    /// The limit on the number of steps was reached at `time`
+    TooManySteps {
+        time: f64,
+    },
+    /// The dynamics produced a NaN or infinite value near `time`
+    NonFinite {
+        time: f64,
+    },
+    InvalidSettings(&'static str),
+}
+
+impl fmt::Display for IntegrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StepSizeTooSmall { time } => write!(f, "step size too small at t = {time}"),
+            Self::TooManySteps { time } => write!(f, "too many steps, stopped at t = {time}"),
+            Self::NonFinite { time } => write!(f, "non-finite state near t = {time}"),
+            Self::InvalidSettings(what) => write!(f, "invalid integrator settings: {what}"),
+        }
+    }
+}
+
+impl Error for IntegrationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_test() {
+        let mut state = CartesianState::new([1., 2., 3.].into(), [4., 5., 6.].into());
+        let derivative = CartesianState::new([4., 5., 6.].into(), [-1., -1., -1.].into());
+        state.add_scaled(2., &derivative);
+        let components: Vec<_> = state.components().collect();
+        assert_eq!(components, [9., 12., 15., 2., 3., 4.]);
+
+        let mut state = vec![1., 2.];
+        state.add_scaled(-1., &vec![1., 1.]);
+        assert_eq!(state, [0., 1.]);
+    }
+
+    #[test]
+    /// Each interpolant reproduces the polynomials it should be exact for
+    fn interpolant_test() {
+        let cubic = |t: f64| 1. + t - 2. * t.powi(2) + 0.5 * t.powi(3);
+        let cubic_rate = |t: f64| 1. - 4. * t + 1.5 * t.powi(2);
+        let step = 2.;
+        let interpolant = Interpolant::Hermite3 {
+            states: [cubic(0.), cubic(step)],
+            derivatives: [cubic_rate(0.), cubic_rate(step)],
+        };
+        assert_relative_eq!(interpolant.evaluate(0.3, step), cubic(0.6), epsilon = 1e-14);
+
+        let quintic = |t: f64| 1. - t + t.powi(3) - 0.2 * t.powi(5);
+        let quintic_rate = |t: f64| -1. + 3. * t.powi(2) - t.powi(4);
+        let interpolant = Interpolant::Hermite5 {
+            states: [quintic(0.), quintic(1.), quintic(2.)],
+            derivatives: [quintic_rate(0.), quintic_rate(1.), quintic_rate(2.)],
+        };
+        assert_relative_eq!(
+            interpolant.evaluate(0.8, step),
+            quintic(1.6),
+            epsilon = 1e-13
+        );
+    }
+}
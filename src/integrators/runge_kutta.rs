@@ -0,0 +1,830 @@
+use super::*;
+
+/// Butcher tableau of an explicit Runge–Kutta method with an embedded error estimate
+struct Tableau {
+    c: &'static [f64],
+    /// Lower triangle of the coefficient matrix, row by row
+    a: &'static [&'static [f64]],
+    /// Weights of the propagated solution
+    b: &'static [f64],
+    /// Weights of the embedded solution the error is estimated against
+    b_hat: &'static [f64],
+    /// Order of the propagated solution
+    order: i32,
+    /// Order of the embedded solution, which sets how step sizes scale with the error
+    error_order: i32,
+    /// Whether the last stage is the derivative at the end of the step
+    first_same_as_last: bool,
+}
+
+/// Dormand & Prince, "A family of embedded Runge-Kutta formulae", J. Comp. Appl. Math. 6, 1980
+const DORMAND_PRINCE_54: Tableau = Tableau {
+    c: &[0., 1. / 5., 3. / 10., 4. / 5., 8. / 9., 1., 1.],
+    a: &[
+        &[],
+        &[1. / 5.],
+        &[3. / 40., 9. / 40.],
+        &[44. / 45., -56. / 15., 32. / 9.],
+        &[
+            19372. / 6561.,
+            -25360. / 2187.,
+            64448. / 6561.,
+            -212. / 729.,
+        ],
+        &[
+            9017. / 3168.,
+            -355. / 33.,
+            46732. / 5247.,
+            49. / 176.,
+            -5103. / 18656.,
+        ],
+        &[
+            35. / 384.,
+            0.,
+            500. / 1113.,
+            125. / 192.,
+            -2187. / 6784.,
+            11. / 84.,
+        ],
+    ],
+    b: &[
+        35. / 384.,
+        0.,
+        500. / 1113.,
+        125. / 192.,
+        -2187. / 6784.,
+        11. / 84.,
+        0.,
+    ],
+    b_hat: &[
+        5179. / 57600.,
+        0.,
+        7571. / 16695.,
+        393. / 640.,
+        -92097. / 339200.,
+        187. / 2100.,
+        1. / 40.,
+    ],
+    order: 5,
+    error_order: 4,
+    first_same_as_last: true,
+};
+
+/// Shampine's dense output coefficients for `DORMAND_PRINCE_54`, as used in Hairer's DOPRI5
+const DORMAND_PRINCE_54_DENSE: [f64; 7] = [
+    -12715105075. / 11282082432.,
+    0.,
+    87487479700. / 32700410799.,
+    -10690763975. / 1880347072.,
+    701980252875. / 199316789632.,
+    -1453857185. / 822651844.,
+    69997945. / 29380423.,
+];
+
+/// Prince & Dormand, "High order embedded Runge-Kutta formulae", J. Comp. Appl. Math. 7, 1981,
+/// RK8(7)13M
+const PRINCE_DORMAND_87: Tableau = Tableau {
+    c: &[
+        0.,
+        1. / 18.,
+        1. / 12.,
+        1. / 8.,
+        5. / 16.,
+        3. / 8.,
+        59. / 400.,
+        93. / 200.,
+        5490023248. / 9719169821.,
+        13. / 20.,
+        1201146811. / 1299019798.,
+        1.,
+        1.,
+    ],
+    a: &[
+        &[],
+        &[1. / 18.],
+        &[1. / 48., 1. / 16.],
+        &[1. / 32., 0., 3. / 32.],
+        &[5. / 16., 0., -75. / 64., 75. / 64.],
+        &[3. / 80., 0., 0., 3. / 16., 3. / 20.],
+        &[
+            29443841. / 614563906.,
+            0.,
+            0.,
+            77736538. / 692538347.,
+            -28693883. / 1125000000.,
+            23124283. / 1800000000.,
+        ],
+        &[
+            16016141. / 946692911.,
+            0.,
+            0.,
+            61564180. / 158732637.,
+            22789713. / 633445777.,
+            545815736. / 2771057229.,
+            -180193667. / 1043307555.,
+        ],
+        &[
+            39632708. / 573591083.,
+            0.,
+            0.,
+            -433636366. / 683701615.,
+            -421739975. / 2616292301.,
+            100302831. / 723423059.,
+            790204164. / 839813087.,
+            800635310. / 3783071287.,
+        ],
+        &[
+            246121993. / 1340847787.,
+            0.,
+            0.,
+            -37695042795. / 15268766246.,
+            -309121744. / 1061227803.,
+            -12992083. / 490766935.,
+            6005943493. / 2108947869.,
+            393006217. / 1396673457.,
+            123872331. / 1001029789.,
+        ],
+        &[
+            -1028468189. / 846180014.,
+            0.,
+            0.,
+            8478235783. / 508512852.,
+            1311729495. / 1432422823.,
+            -10304129995. / 1701304382.,
+            -48777925059. / 3047939560.,
+            15336726248. / 1032824649.,
+            -45442868181. / 3398467696.,
+            3065993473. / 597172653.,
+        ],
+        &[
+            185892177. / 718116043.,
+            0.,
+            0.,
+            -3185094517. / 667107341.,
+            -477755414. / 1098053517.,
+            -703635378. / 230739211.,
+            5731566787. / 1027545527.,
+            5232866602. / 850066563.,
+            -4093664535. / 808688257.,
+            3962137247. / 1805957418.,
+            65686358. / 487910083.,
+        ],
+        &[
+            403863854. / 491063109.,
+            0.,
+            0.,
+            -5068492393. / 434740067.,
+            -411421997. / 543043805.,
+            652783627. / 914296604.,
+            11173962825. / 925320556.,
+            -13158990841. / 6184727034.,
+            3936647629. / 1978049680.,
+            -160528059. / 685178525.,
+            248638103. / 1413531060.,
+            0.,
+        ],
+    ],
+    b: &[
+        14005451. / 335480064.,
+        0.,
+        0.,
+        0.,
+        0.,
+        -59238493. / 1068277825.,
+        181606767. / 758867731.,
+        561292985. / 797845732.,
+        -1041891430. / 1371343529.,
+        760417239. / 1151165299.,
+        118820643. / 751138087.,
+        -528747749. / 2220607170.,
+        1. / 4.,
+    ],
+    b_hat: &[
+        13451932. / 455176623.,
+        0.,
+        0.,
+        0.,
+        0.,
+        -808719846. / 976000145.,
+        1757004468. / 5645159321.,
+        656045339. / 265891186.,
+        -3867574721. / 1518517206.,
+        465885868. / 322736535.,
+        53011238. / 667516719.,
+        2. / 45.,
+        0.,
+    ],
+    order: 8,
+    error_order: 7,
+    first_same_as_last: false,
+};
+
+/// Derivatives at every stage of one step, given the derivative at its start
+fn stages<S: State, D: Dynamics<S>>(
+    tableau: &Tableau,
+    dynamics: &D,
+    time: f64,
+    state: &S,
+    derivative: &S,
+    step: f64,
+) -> Vec<S> {
+    let mut stages = Vec::with_capacity(tableau.c.len());
+    stages.push(derivative.clone());
+    for (c, a) in tableau.c.iter().zip(tableau.a).skip(1) {
+        let stage_state = step_from(state, step, a, &stages);
+        stages.push(dynamics.derivative(time + c * step, &stage_state));
+    }
+    stages
+}
+
+/// Takes one step of the eighth-order Prince–Dormand solution without error control
+pub(super) fn eighth_order_step<S: State, D: Dynamics<S>>(
+    dynamics: &D,
+    time: f64,
+    state: &S,
+    derivative: &S,
+    step: f64,
+) -> S {
+    let tableau = &PRINCE_DORMAND_87;
+    let stages = stages(tableau, dynamics, time, state, derivative, step);
+    step_from(state, step, tableau.b, &stages)
+}
+
+/// Number of derivative evaluations `eighth_order_step` makes
+pub(super) const EIGHTH_ORDER_EVALUATIONS: usize = 12;
+
+/// Classic fourth-order Runge–Kutta with a fixed step
+#[derive(Clone, Debug)]
+pub struct RungeKutta4 {
+    step: f64,
+    dense_output: bool,
+}
+
+impl RungeKutta4 {
+    /// The step is shrunk slightly if needed so a whole number of steps spans the integration
+    pub fn new(step: f64) -> Self {
+        Self {
+            step,
+            dense_output: false,
+        }
+    }
+
+    /// Records a cubic Hermite interpolant for every step, see `Solution::at`
+    pub fn with_dense_output(self) -> Self {
+        Self {
+            dense_output: true,
+            ..self
+        }
+    }
+}
+
+impl Integrator for RungeKutta4 {
+    fn integrate<S: State, D: Dynamics<S>>(
+        &self,
+        dynamics: &D,
+        start: f64,
+        state: &S,
+        end: f64,
+    ) -> Result<Solution<S>, IntegrationError> {
+        let (count, step) = fixed_steps(self.step, start, end)?;
+        let dynamics = &Timed { dynamics, start };
+        let mut solution = Solution {
+            time: start,
+            state: state.clone(),
+            stats: Stats::default(),
+            segments: Vec::new(),
+        };
+        let mut derivative = dynamics.derivative(start, state);
+        solution.stats.evaluations += 1;
+        for i in 0..count {
+            let time = solution.time;
+            let state = &solution.state;
+            let along = |scale: f64, derivative: &S| {
+                let mut state = state.clone();
+                state.add_scaled(scale, derivative);
+                state
+            };
+            let k2 = dynamics.derivative(time + step / 2., &along(step / 2., &derivative));
+            let k3 = dynamics.derivative(time + step / 2., &along(step / 2., &k2));
+            let k4 = dynamics.derivative(time + step, &along(step, &k3));
+            let next = step_from(
+                state,
+                step,
+                &[1. / 6., 1. / 3., 1. / 3., 1. / 6.],
+                &[derivative.clone(), k2, k3, k4],
+            );
+            let next_time = start + (i + 1) as f64 * step;
+            let next_derivative = dynamics.derivative(next_time, &next);
+            solution.stats.evaluations += 4;
+            solution.stats.accepted_steps += 1;
+            check_finite(&next, next_time)?;
+
+            if self.dense_output {
+                solution.segments.push(Segment {
+                    start: time,
+                    step,
+                    interpolant: Interpolant::Hermite3 {
+                        states: [solution.state.clone(), next.clone()],
+                        derivatives: [derivative, next_derivative.clone()],
+                    },
+                });
+            }
+            solution.time = next_time;
+            solution.state = next;
+            derivative = next_derivative;
+        }
+        Ok(solution.timed(start))
+    }
+}
+
+/// Number and size of equal steps no larger than `step` which span `start` to `end`
+pub(super) fn fixed_steps(
+    step: f64,
+    start: f64,
+    end: f64,
+) -> Result<(usize, f64), IntegrationError> {
+    if !(step > 0. && step.is_finite()) {
+        return Err(IntegrationError::InvalidSettings("step must be positive"));
+    }
+    if !(start.is_finite() && end.is_finite()) {
+        return Err(IntegrationError::InvalidSettings("times must be finite"));
+    }
+    let span = end - start;
+    // Allow for rounding so a span which is a multiple of the step isn't given an extra step
+    let count = (span.abs() / step * (1. - 1e-12)).ceil() as usize;
+    Ok((count, if count == 0 { 0. } else { span / count as f64 }))
+}
+
+pub(super) fn check_finite<S: State>(state: &S, time: f64) -> Result<(), IntegrationError> {
+    if state.components().all(f64::is_finite) {
+        Ok(())
+    } else {
+        Err(IntegrationError::NonFinite { time })
+    }
+}
+
+/// Explicit Runge–Kutta integration with an embedded error estimate, which adapts its step size
+/// to keep the estimated error of each step within the tolerances
+#[derive(Clone, Debug)]
+pub struct AdaptiveRungeKutta {
+    tableau: &'static Tableau,
+    absolute_tolerance: f64,
+    relative_tolerance: f64,
+    initial_step: Option<f64>,
+    max_step: f64,
+    max_steps: usize,
+    dense_output: bool,
+}
+
+impl std::fmt::Debug for Tableau {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RK{}({})", self.order, self.error_order)
+    }
+}
+
+/// Factor the step size is multiplied by after a step, relative to the optimal step
+const SAFETY: f64 = 0.9;
+/// Bounds on how much the step size can change after a single step
+const MIN_FACTOR: f64 = 0.2;
+const MAX_FACTOR: f64 = 10.;
+
+impl AdaptiveRungeKutta {
+    /// Dormand–Prince 5(4), which is efficient for moderate tolerances. Dense output comes from its
+    /// fourth-order continuous extension at no extra cost.
+    pub fn dormand_prince_45(absolute_tolerance: f64, relative_tolerance: f64) -> Self {
+        Self::new(&DORMAND_PRINCE_54, absolute_tolerance, relative_tolerance)
+    }
+
+    /// Prince–Dormand 8(7), which is efficient for tight tolerances. Dense output comes from a
+    /// quintic Hermite interpolant through the ends and middle of each step, which costs an extra
+    /// half step and is sixth-order accurate.
+    pub fn dormand_prince_87(absolute_tolerance: f64, relative_tolerance: f64) -> Self {
+        Self::new(&PRINCE_DORMAND_87, absolute_tolerance, relative_tolerance)
+    }
+
+    fn new(tableau: &'static Tableau, absolute_tolerance: f64, relative_tolerance: f64) -> Self {
+        Self {
+            tableau,
+            absolute_tolerance,
+            relative_tolerance,
+            initial_step: None,
+            max_step: f64::INFINITY,
+            max_steps: 100_000,
+            dense_output: false,
+        }
+    }
+
+    /// Size of the first step attempted, which is estimated from the dynamics unless set
+    pub fn with_initial_step(self, initial_step: f64) -> Self {
+        Self {
+            initial_step: Some(initial_step),
+            ..self
+        }
+    }
+
+    pub fn with_max_step(self, max_step: f64) -> Self {
+        Self { max_step, ..self }
+    }
+
+    /// Number of steps, accepted or rejected, after which the integration gives up
+    pub fn with_max_steps(self, max_steps: usize) -> Self {
+        Self { max_steps, ..self }
+    }
+
+    /// Records an interpolant for every step, see `Solution::at`
+    pub fn with_dense_output(self) -> Self {
+        Self {
+            dense_output: true,
+            ..self
+        }
+    }
+
+    /// Root mean square of the components of `error`, each relative to the tolerance for the
+    /// larger of the two states
+    fn error_norm<S: State>(&self, error: &S, state: &S, next: &S) -> f64 {
+        let (sum, count) = error
+            .components()
+            .zip(state.components().zip(next.components()))
+            .map(|(error, (a, b))| {
+                let scale =
+                    self.absolute_tolerance + self.relative_tolerance * a.abs().max(b.abs());
+                (error / scale).powi(2)
+            })
+            .fold((0., 0), |(sum, count), x| (sum + x, count + 1));
+        (sum / count.max(1) as f64).sqrt()
+    }
+
+    /// Estimates a first step from the size of the state and its first two derivatives, following
+    /// Hairer, Nørsett & Wanner, Solving Ordinary Differential Equations I, section II.4
+    fn initial_step<S: State, D: Dynamics<S>>(
+        &self,
+        dynamics: &D,
+        time: f64,
+        state: &S,
+        derivative: &S,
+        span: f64,
+    ) -> f64 {
+        let norm = |x: &S| self.error_norm(x, state, state);
+        let (state_norm, derivative_norm) = (norm(state), norm(derivative));
+        let first_guess = if state_norm < 1e-5 || derivative_norm < 1e-5 {
+            1e-6
+        } else {
+            0.01 * state_norm / derivative_norm
+        }
+        .min(span.abs())
+        .min(self.max_step);
+        let direction = span.signum();
+
+        let euler = step_from(
+            state,
+            direction * first_guess,
+            &[1.],
+            std::slice::from_ref(derivative),
+        );
+        let mut change = dynamics.derivative(time + direction * first_guess, &euler);
+        change.add_scaled(-1., derivative);
+        let second_derivative_norm = norm(&change) / first_guess;
+
+        let largest = derivative_norm.max(second_derivative_norm);
+        let second_guess = if largest <= 1e-15 {
+            (first_guess * 1e-3).max(1e-6)
+        } else {
+            (0.01 / largest).powf(1. / f64::from(self.tableau.order))
+        };
+        (100. * first_guess)
+            .min(second_guess)
+            .min(span.abs())
+            .min(self.max_step)
+    }
+}
+
+impl Integrator for AdaptiveRungeKutta {
+    fn integrate<S: State, D: Dynamics<S>>(
+        &self,
+        dynamics: &D,
+        start: f64,
+        state: &S,
+        end: f64,
+    ) -> Result<Solution<S>, IntegrationError> {
+        if !(self.absolute_tolerance >= 0. && self.relative_tolerance >= 0.)
+            || self.absolute_tolerance + self.relative_tolerance == 0.
+        {
+            return Err(IntegrationError::InvalidSettings(
+                "tolerances must be non-negative and not both zero",
+            ));
+        }
+        if self.max_step.is_nan() || self.max_step <= 0. {
+            return Err(IntegrationError::InvalidSettings(
+                "max step must be positive",
+            ));
+        }
+        if !(start.is_finite() && end.is_finite()) {
+            return Err(IntegrationError::InvalidSettings("times must be finite"));
+        }
+
+        let tableau = self.tableau;
+        let dynamics = &Timed { dynamics, start };
+        let mut solution = Solution {
+            time: start,
+            state: state.clone(),
+            stats: Stats::default(),
+            segments: Vec::new(),
+        };
+        if start == end {
+            return Ok(solution);
+        }
+        let direction = (end - start).signum();
+        let mut derivative = dynamics.derivative(start, state);
+        solution.stats.evaluations += 1;
+        let mut step = match self.initial_step {
+            Some(initial_step) => initial_step.abs().min(self.max_step),
+            None => {
+                solution.stats.evaluations += 1;
+                self.initial_step(dynamics, start, state, &derivative, end - start)
+            }
+        } * direction;
+        let exponent = -1. / f64::from(tableau.error_order + 1);
+        let error_weights: Vec<_> = tableau
+            .b
+            .iter()
+            .zip(tableau.b_hat)
+            .map(|(b, b_hat)| b - b_hat)
+            .collect();
+        let mut last_rejected = false;
+
+        while solution.time != end {
+            let stats = &mut solution.stats;
+            if stats.accepted_steps + stats.rejected_steps >= self.max_steps {
+                return Err(IntegrationError::TooManySteps {
+                    time: solution.time,
+                });
+            }
+            let time = solution.time;
+            let remaining = end - time;
+            let last_step = step.abs() >= remaining.abs();
+            if last_step {
+                step = remaining;
+            }
+            if step.abs() <= 16. * f64::EPSILON * time.abs() || step == 0. {
+                return Err(IntegrationError::StepSizeTooSmall { time });
+            }
+
+            let stages = stages(tableau, dynamics, time, &solution.state, &derivative, step);
+            stats.evaluations += tableau.c.len() - 1;
+            let next = step_from(&solution.state, step, tableau.b, &stages);
+            let mut error = step_from(&solution.state, step, &error_weights, &stages);
+            error.add_scaled(-1., &solution.state);
+            let error_norm = self.error_norm(&error, &solution.state, &next);
+            if !error_norm.is_finite() {
+                return Err(IntegrationError::NonFinite { time });
+            }
+
+            let factor = SAFETY * error_norm.powf(exponent);
+            if error_norm > 1. {
+                stats.rejected_steps += 1;
+                step *= factor.max(MIN_FACTOR);
+                last_rejected = true;
+                continue;
+            }
+
+            stats.accepted_steps += 1;
+            let next_time = if last_step { end } else { time + step };
+            let next_derivative = if tableau.first_same_as_last {
+                stages.last().unwrap().clone()
+            } else {
+                stats.evaluations += 1;
+                dynamics.derivative(next_time, &next)
+            };
+            if self.dense_output {
+                let interpolant = if tableau.first_same_as_last {
+                    dormand_prince_interpolant(&solution.state, &next, step, &stages)
+                } else {
+                    let midpoint =
+                        eighth_order_step(dynamics, time, &solution.state, &derivative, step / 2.);
+                    let midpoint_derivative = dynamics.derivative(time + step / 2., &midpoint);
+                    stats.evaluations += EIGHTH_ORDER_EVALUATIONS + 1;
+                    Interpolant::Hermite5 {
+                        states: [solution.state.clone(), midpoint, next.clone()],
+                        derivatives: [derivative, midpoint_derivative, next_derivative.clone()],
+                    }
+                };
+                solution.segments.push(Segment {
+                    start: time,
+                    step,
+                    interpolant,
+                });
+            }
+
+            solution.time = next_time;
+            solution.state = next;
+            derivative = next_derivative;
+            let max_factor = if last_rejected { 1. } else { MAX_FACTOR };
+            step =
+                (step * factor.clamp(MIN_FACTOR, max_factor)).clamp(-self.max_step, self.max_step);
+            last_rejected = false;
+        }
+        Ok(solution.timed(start))
+    }
+}
+
+fn dormand_prince_interpolant<S: State>(
+    state: &S,
+    next: &S,
+    step: f64,
+    stages: &[S],
+) -> Interpolant<S> {
+    let mut difference = next.clone();
+    difference.add_scaled(-1., state);
+    // rcont3 = h k1 - (y1 - y0)
+    let mut third = step_from(&difference, step, &[1.], &stages[..1]);
+    third.add_scaled(-2., &difference);
+    // rcont4 = (y1 - y0) - h k7 - rcont3
+    let mut fourth = step_from(&difference, step, &[-1.], &stages[6..]);
+    fourth.add_scaled(-1., &third);
+    let mut fifth = step_from(state, step, &DORMAND_PRINCE_54_DENSE, stages);
+    fifth.add_scaled(-1., state);
+    Interpolant::DormandPrince(Box::new([state.clone(), difference, third, fourth, fifth]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f64::consts::PI;
+
+    use crate::orbit::structs::COE;
+    use crate::orbit::Keplerian;
+    use crate::testing;
+
+    /// Row sums of each tableau match its nodes, and both sets of weights sum to one
+    #[test]
+    fn tableau_test() {
+        for tableau in [&DORMAND_PRINCE_54, &PRINCE_DORMAND_87] {
+            for (c, a) in tableau.c.iter().zip(tableau.a) {
+                assert_relative_eq!(a.iter().sum::<f64>(), c, epsilon = 1e-14);
+            }
+            assert_relative_eq!(tableau.b.iter().sum::<f64>(), 1., epsilon = 1e-14);
+            assert_relative_eq!(tableau.b_hat.iter().sum::<f64>(), 1., epsilon = 1e-14);
+        }
+    }
+
+    #[test]
+    /// Halving the step of RK4 cuts the error by about 2^4
+    fn rk4_order_test() {
+        let dynamics = |_: f64, y: &f64| *y;
+        let error = |step| {
+            let solution = RungeKutta4::new(step)
+                .integrate(&dynamics, 0., &1., 1.)
+                .unwrap();
+            (solution.state - 1_f64.exp()).abs()
+        };
+        let ratio = error(0.1) / error(0.05);
+        assert!((15. ..17.).contains(&ratio), "ratio {ratio}");
+    }
+
+    #[test]
+    fn rk4_steps_test() {
+        let dynamics = |_: f64, _: &f64| 1.;
+        let solution = RungeKutta4::new(0.3)
+            .integrate(&dynamics, 0., &0., 1.)
+            .unwrap();
+        assert_eq!(solution.stats.accepted_steps, 4);
+        assert_eq!(solution.stats.evaluations, 17);
+        assert_eq!(solution.time, 1.);
+        assert_relative_eq!(solution.state, 1., epsilon = 1e-15);
+        assert!(RungeKutta4::new(0.)
+            .integrate(&dynamics, 0., &0., 1.)
+            .is_err());
+    }
+
+    fn two_body(_: f64, state: &[f64; 4]) -> [f64; 4] {
+        let [x, y, vx, vy] = *state;
+        let r3 = x.hypot(y).powi(3);
+        [vx, vy, -x / r3, -y / r3]
+    }
+
+    /// An orbit with eccentricity 0.5 in units where mu = 1 and a = 1, starting at periapsis
+    fn periapsis() -> [f64; 4] {
+        [0.5, 0., 0., 3_f64.sqrt()]
+    }
+
+    #[test]
+    /// Tightening the tolerances tightens the error after one revolution
+    fn adaptive_tolerance_test() {
+        for integrator in [
+            AdaptiveRungeKutta::dormand_prince_45,
+            AdaptiveRungeKutta::dormand_prince_87,
+        ] {
+            let mut last_error = f64::INFINITY;
+            for tolerance in [1e-6, 1e-9, 1e-12] {
+                let solution = integrator(tolerance, tolerance)
+                    .integrate(&two_body, 0., &periapsis(), 2. * PI)
+                    .unwrap();
+                assert_eq!(solution.time, 2. * PI);
+                let error = solution
+                    .state
+                    .iter()
+                    .zip(periapsis())
+                    .map(|(a, b)| (a - b).abs())
+                    .fold(0., f64::max);
+                assert!(error < 1e3 * tolerance, "error {error} at {tolerance}");
+                assert!(error < last_error);
+                last_error = error;
+            }
+        }
+    }
+
+    #[test]
+    /// The higher-order pair needs far fewer steps at tight tolerances
+    fn adaptive_efficiency_test() {
+        let stats = |integrator: AdaptiveRungeKutta| {
+            integrator
+                .integrate(&two_body, 0., &periapsis(), 2. * PI)
+                .unwrap()
+                .stats
+        };
+        let low = stats(AdaptiveRungeKutta::dormand_prince_45(1e-12, 1e-12));
+        let high = stats(AdaptiveRungeKutta::dormand_prince_87(1e-12, 1e-12));
+        assert!(high.evaluations * 2 < low.evaluations);
+        assert_eq!(
+            low.evaluations,
+            2 + 6 * (low.accepted_steps + low.rejected_steps)
+        );
+        assert_eq!(
+            high.evaluations,
+            2 + 12 * (high.accepted_steps + high.rejected_steps) + high.accepted_steps
+        );
+    }
+
+    #[test]
+    /// Dense output agrees with integrating straight to the requested time
+    fn dense_output_test() {
+        for (integrator, tolerance) in [
+            (AdaptiveRungeKutta::dormand_prince_45(1e-10, 1e-10), 1e-7),
+            (AdaptiveRungeKutta::dormand_prince_87(1e-12, 1e-12), 1e-9),
+        ] {
+            let solution = integrator
+                .clone()
+                .with_dense_output()
+                .integrate(&two_body, 0., &periapsis(), 2. * PI)
+                .unwrap();
+            for time in [0., 0.1, 1., 3.3, 2. * PI] {
+                let expected = integrator
+                    .integrate(&two_body, 0., &periapsis(), time)
+                    .unwrap()
+                    .state;
+                testing::assert_array_eq_atol(&solution.at(time).unwrap(), &expected, tolerance);
+            }
+            assert!(solution.at(-0.1).is_none());
+            assert!(solution.at(7.).is_none());
+        }
+        let solution = AdaptiveRungeKutta::dormand_prince_45(1e-9, 1e-9)
+            .integrate(&two_body, 0., &periapsis(), 1.)
+            .unwrap();
+        assert!(solution.at(0.5).is_none());
+    }
+
+    #[test]
+    /// Propagating a Cartesian state built from `COE` backwards and forwards matches the
+    /// analytic solution
+    fn cartesian_test() {
+        let coe = COE::new(7_000_000., 0.1, 1., 2., 3., 4.);
+        let state = CartesianState::from(&coe);
+        let dynamics = OrbitDynamics::new();
+        for time_of_flight in [5_000., -5_000.] {
+            let solution = AdaptiveRungeKutta::dormand_prince_87(1e-6, 1e-13)
+                .integrate(&dynamics, 0., &state, time_of_flight)
+                .unwrap();
+            let expected = Keplerian.propagate_cartesian(&state, time_of_flight);
+            testing::assert_array_eq_atol(
+                &solution.state.position.elem,
+                &expected.position.elem,
+                1e-4,
+            );
+            testing::assert_array_eq_atol(
+                &solution.state.velocity.elem,
+                &expected.velocity.elem,
+                1e-7,
+            );
+        }
+    }
+
+    #[test]
+    fn failure_test() {
+        let blow_up = |_: f64, y: &f64| y * y;
+        assert!(matches!(
+            AdaptiveRungeKutta::dormand_prince_45(1e-9, 1e-9).integrate(&blow_up, 0., &1., 2.),
+            Err(IntegrationError::StepSizeTooSmall { .. } | IntegrationError::NonFinite { .. })
+        ));
+        assert!(matches!(
+            AdaptiveRungeKutta::dormand_prince_45(1e-12, 1e-12)
+                .with_max_steps(10)
+                .integrate(&two_body, 0., &periapsis(), 2. * PI),
+            Err(IntegrationError::TooManySteps { .. })
+        ));
+        assert!(matches!(
+            AdaptiveRungeKutta::dormand_prince_45(0., 0.).integrate(&blow_up, 0., &1., 2.),
+            Err(IntegrationError::InvalidSettings(_))
+        ));
+    }
+}
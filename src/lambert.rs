@@ -0,0 +1,766 @@
+//! Lambert's problem: the orbits that join two positions in a given time.
+//!
+//! Solutions follow Izzo's algorithm (Celestial Mechanics and Dynamical Astronomy 121, 2015),
+//! which reduces every transfer to one time-of-flight equation in the Lancaster-Blanchard variable
+//! `x` and solves it with Householder's method from a close initial guess. Where that iteration
+//! fails to converge or strays onto the wrong branch, which can happen near parabolic transfers and
+//! minimum-time multi-revolution transfers, the transfer is solved instead with Gooding's method
+//! (Celestial Mechanics 48, 1990), which has its own form of the time-of-flight equation in the
+//! same variable and starters fitted to it, refined with Halley's method.
+//!
+//! ```
+//! use orbitrs::lambert::Lambert;
+//! use orbitrs::prelude::*;
+//!
+//! let departure = Vector3::new([7_000e3, 0., 0.]);
+//! let arrival = Vector3::new([0., 8_000e3, 1_000e3]);
+//! let solutions = Lambert::new()
+//!     .solve(bodies::EARTH.mu, &departure, &arrival, 3_000.)
+//!     .unwrap();
+//! let transfer = CartesianState::new(departure, solutions[0].departure_velocity.clone());
+//! let reached = Keplerian.propagate_cartesian(&transfer, 3_000.).position;
+//! assert!((reached - arrival).norm() < 1e-3);
+//! ```
+
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::vector::Vector3;
+
+/// Tolerance on `x` for both solvers
+const X_TOL: f64 = 1e-13;
+
+/// Iteration cap for Householder's and Halley's methods, which usually converge in a handful
+const MAX_ITER: usize = 35;
+
+/// Relative tolerance on `x` of the minimum time of flight in Gooding's method
+const GOODING_MIN_TOL: f64 = 3e-7;
+
+/// Largest `|1 - x^2|` for which Gooding's time equation is summed as a series, and smallest `f`
+/// for which its hyperbolic term is taken as a logarithm rather than a series
+const GOODING_SWITCH: f64 = 0.4;
+
+/// Coefficients of Gooding's starters
+const GOODING_C0: f64 = 1.7;
+const GOODING_C1: f64 = 0.5;
+const GOODING_C2: f64 = 0.03;
+const GOODING_C3: f64 = 0.15;
+const GOODING_C41: f64 = 1.;
+const GOODING_C42: f64 = 0.24;
+
+/// Sine of the transfer angle below which the two positions don't define a transfer plane
+const COLLINEAR_TOL: f64 = 1e-12;
+
+/// Sense of motion of a transfer about the Z axis
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Angular momentum along +Z
+    #[default]
+    Prograde,
+    /// Angular momentum along -Z
+    Retrograde,
+}
+
+/// One orbit joining the two positions
+#[derive(Clone, Debug, PartialEq)]
+pub struct LambertSolution {
+    /// Complete revolutions made on the way
+    pub revolutions: u32,
+    pub departure_velocity: Vector3,
+    pub arrival_velocity: Vector3,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LambertError {
+    NonPositiveTimeOfFlight(f64),
+    /// A position at the centre of the body
+    ZeroPosition,
+    /// Positions in the same or opposite directions, which leave the transfer plane undefined
+    CollinearPositions,
+}
+
+impl fmt::Display for LambertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonPositiveTimeOfFlight(time) => {
+                write!(f, "time of flight {time} s is not positive")
+            }
+            Self::ZeroPosition => write!(f, "position is at the centre of the body"),
+            Self::CollinearPositions => {
+                write!(
+                    f,
+                    "positions are collinear, so the transfer plane is undefined"
+                )
+            }
+        }
+    }
+}
+
+impl Error for LambertError {}
+
+/// Solver for Lambert's problem
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Lambert {
+    pub direction: Direction,
+    /// Most complete revolutions to look for solutions with
+    pub revolutions: u32,
+}
+
+impl Lambert {
+    /// Prograde transfers of under one revolution
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_direction(self, direction: Direction) -> Self {
+        Self { direction, ..self }
+    }
+
+    pub fn with_revolutions(self, revolutions: u32) -> Self {
+        Self {
+            revolutions,
+            ..self
+        }
+    }
+
+    /// Every transfer from `departure` to `arrival` in `time_of_flight` seconds, about a body of
+    /// gravitational parameter `mu`, with up to `revolutions` complete revolutions.
+    ///
+    /// There is one direct transfer, then two for each number of revolutions until the time of
+    /// flight is too short to fit them in, from the left then the right branch of Izzo's
+    /// time-of-flight curve.
+    pub fn solve(
+        &self,
+        mu: f64,
+        departure: &Vector3,
+        arrival: &Vector3,
+        time_of_flight: f64,
+    ) -> Result<Vec<LambertSolution>, LambertError> {
+        if time_of_flight <= 0. || time_of_flight.is_nan() {
+            return Err(LambertError::NonPositiveTimeOfFlight(time_of_flight));
+        }
+        let (r1, r2) = (departure.norm(), arrival.norm());
+        if r1 == 0. || r2 == 0. {
+            return Err(LambertError::ZeroPosition);
+        }
+        let chord = (arrival.clone() - departure.clone()).norm();
+        let semi_perimeter = (r1 + r2 + chord) / 2.;
+
+        let radial_1 = departure.clone() * (1. / r1);
+        let radial_2 = arrival.clone() * (1. / r2);
+        let normal = radial_1.cross(&radial_2);
+        let sin_angle = normal.norm();
+        if sin_angle < COLLINEAR_TOL {
+            return Err(LambertError::CollinearPositions);
+        }
+        let normal = normal * (1. / sin_angle);
+        let mut lambda = (1. - (chord / semi_perimeter).min(1.)).sqrt();
+        let mut tangential_1 = normal.cross(&radial_1);
+        let mut tangential_2 = normal.cross(&radial_2);
+        // The transfer goes the long way round when the short way runs against `direction`
+        if (normal.elem[2] < 0.) == (self.direction == Direction::Prograde) {
+            lambda = -lambda;
+            tangential_1 = -tangential_1;
+            tangential_2 = -tangential_2;
+        }
+
+        let time = (2. * mu / semi_perimeter.powi(3)).sqrt() * time_of_flight;
+        let gamma = (mu * semi_perimeter / 2.).sqrt();
+        let rho = (r1 - r2) / chord;
+        let sigma = (1. - rho.powi(2)).sqrt();
+        let velocities = |x: f64| {
+            let y = y_of(x, lambda);
+            let (sum, difference) = (lambda * y + x, lambda * y - x);
+            let tangential = gamma * sigma * (y + lambda * x);
+            let departure_velocity = radial_1.clone() * (gamma * (difference - rho * sum) / r1)
+                + tangential_1.clone() * (tangential / r1);
+            let arrival_velocity = radial_2.clone() * (-gamma * (difference + rho * sum) / r2)
+                + tangential_2.clone() * (tangential / r2);
+            (departure_velocity, arrival_velocity)
+        };
+
+        let mut solutions = vec![];
+        for revolutions in 0..=self.revolutions {
+            for x in solve_x(time, lambda, revolutions) {
+                let (departure_velocity, arrival_velocity) = velocities(x);
+                solutions.push(LambertSolution {
+                    revolutions,
+                    departure_velocity,
+                    arrival_velocity,
+                });
+            }
+        }
+        Ok(solutions)
+    }
+}
+
+/// Values of `x` that give the non-dimensional `time`, on the left and then the right branch
+/// for multi-revolution transfers
+fn solve_x(time: f64, lambda: f64, revolutions: u32) -> Vec<f64> {
+    if revolutions == 0 {
+        let guess = initial_guess(time, lambda);
+        return match householder(guess, time, lambda, revolutions).filter(|&x| x > -1.) {
+            Some(x) => vec![x],
+            None => gooding(time, lambda, revolutions),
+        };
+    }
+
+    let x_min = minimum_time_x(lambda, revolutions);
+    if time < time_of_flight(x_min, lambda, revolutions) {
+        return vec![];
+    }
+    let [left, right] = multi_revolution_guesses(time, revolutions);
+    let roots = [(left, -1., x_min), (right, x_min, 1.)].map(|(guess, lower, upper)| {
+        householder(guess, time, lambda, revolutions).filter(|x| (lower..=upper).contains(x))
+    });
+    if let [Some(left), Some(right)] = roots {
+        return vec![left, right];
+    }
+    // Gooding's roots on either side of the minimum, or the minimum itself where they meet
+    let fallback = gooding(time, lambda, revolutions);
+    let side = |left: bool| {
+        fallback
+            .iter()
+            .copied()
+            .find(|&x| (x < x_min) == left)
+            .unwrap_or(x_min)
+    };
+    vec![
+        roots[0].unwrap_or_else(|| side(true)),
+        roots[1].unwrap_or_else(|| side(false)),
+    ]
+}
+
+/// Izzo's initial guesses for the left and right branches of multi-revolution transfers,
+/// equations 31
+fn multi_revolution_guesses(time: f64, revolutions: u32) -> [f64; 2] {
+    let m = revolutions as f64;
+    let left_ratio = ((m * PI + PI) / (8. * time)).powf(2. / 3.);
+    let right_ratio = (8. * time / (m * PI)).powf(2. / 3.);
+    [
+        (left_ratio - 1.) / (left_ratio + 1.),
+        (right_ratio - 1.) / (right_ratio + 1.),
+    ]
+}
+
+/// Izzo's initial guess for direct transfers, equations 30
+fn initial_guess(time: f64, lambda: f64) -> f64 {
+    let time_zero = lambda.acos() + lambda * (1. - lambda.powi(2)).sqrt();
+    let time_one = 2. * (1. - lambda.powi(3)) / 3.;
+    if time >= time_zero {
+        (time_zero / time).powf(2. / 3.) - 1.
+    } else if time < time_one {
+        2.5 * time_one / time * (time_one - time) / (1. - lambda.powi(5)) + 1.
+    } else {
+        ((time / time_zero).ln() / (time_one / time_zero).ln() * 2_f64.ln()).exp() - 1.
+    }
+}
+
+/// `y` as a function of `x`, equation 14
+fn y_of(x: f64, lambda: f64) -> f64 {
+    (1. - lambda.powi(2) * (1. - x.powi(2))).sqrt()
+}
+
+/// Non-dimensional time of flight at `x`, equation 18, or Battin's series near the parabola for
+/// direct transfers
+fn time_of_flight(x: f64, lambda: f64, revolutions: u32) -> f64 {
+    let y = y_of(x, lambda);
+    if revolutions == 0 && (0.6_f64.sqrt()..1.4_f64.sqrt()).contains(&x) {
+        let eta = y - lambda * x;
+        let s = (1. - lambda - x * eta) / 2.;
+        let q = 4. / 3. * hypergeometric(s);
+        return (eta.powi(3) * q + 4. * lambda * eta) / 2.;
+    }
+    let one_minus_x_squared = 1. - x.powi(2);
+    let psi = if x < 1. {
+        (x * y + lambda * one_minus_x_squared).acos()
+    } else {
+        ((y - x * lambda) * (x.powi(2) - 1.).sqrt()).asinh()
+    };
+    ((psi + revolutions as f64 * PI) / one_minus_x_squared.abs().sqrt() - x + lambda * y)
+        / one_minus_x_squared
+}
+
+/// First three derivatives of the time of flight `time` with respect to `x`, equations 22
+fn derivatives(x: f64, time: f64, lambda: f64) -> (f64, f64, f64) {
+    let y = y_of(x, lambda);
+    let one_minus_x_squared = 1. - x.powi(2);
+    let lambda_squared = lambda.powi(2);
+    let first = (3. * time * x - 2. + 2. * lambda.powi(3) * x / y) / one_minus_x_squared;
+    let second =
+        (3. * time + 5. * x * first + 2. * (1. - lambda_squared) * lambda.powi(3) / y.powi(3))
+            / one_minus_x_squared;
+    let third = (7. * x * second + 8. * first
+        - 6. * (1. - lambda_squared) * lambda.powi(5) * x / y.powi(5))
+        / one_minus_x_squared;
+    (first, second, third)
+}
+
+/// Gauss's hypergeometric function `2F1(3, 1, 5/2, z)`, by its series
+fn hypergeometric(z: f64) -> f64 {
+    let mut sum = 1.;
+    let mut term = 1.;
+    for index in 0.. {
+        let index = index as f64;
+        term *= (3. + index) * (1. + index) / (2.5 + index) * z / (index + 1.);
+        let previous = sum;
+        sum += term;
+        if sum == previous {
+            break;
+        }
+    }
+    sum
+}
+
+/// Householder's third-order iteration on the time-of-flight equation, or `None` if it fails to
+/// converge
+fn householder(guess: f64, target: f64, lambda: f64, revolutions: u32) -> Option<f64> {
+    let mut x = guess;
+    for _ in 0..MAX_ITER {
+        let time = time_of_flight(x, lambda, revolutions);
+        let residual = time - target;
+        let (first, second, third) = derivatives(x, time, lambda);
+        let step = residual * (first.powi(2) - residual * second / 2.)
+            / (first * (first.powi(2) - residual * second) + third * residual.powi(2) / 6.);
+        x -= step;
+        if !x.is_finite() {
+            return None;
+        }
+        if step.abs() < X_TOL {
+            return Some(x);
+        }
+    }
+    None
+}
+
+/// `x` of the shortest transfer with `revolutions` revolutions, where the time of flight is
+/// stationary, found with Halley's method on its derivative
+fn minimum_time_x(lambda: f64, revolutions: u32) -> f64 {
+    let mut x = 0.1;
+    for _ in 0..MAX_ITER {
+        let time = time_of_flight(x, lambda, revolutions);
+        let (first, second, third) = derivatives(x, time, lambda);
+        let step = 2. * first * second / (2. * second.powi(2) - first * third);
+        x -= step;
+        if !(-1. ..1.).contains(&x) {
+            break;
+        }
+        if step.abs() < X_TOL {
+            return x;
+        }
+    }
+    // The time of flight is convex over (-1, 1), so its derivative has a single root there
+    let slope = |x| derivatives(x, time_of_flight(x, lambda, revolutions), lambda).0;
+    let (mut lower, mut upper) = (-1., 1.);
+    while upper - lower > X_TOL {
+        let middle = (lower + upper) / 2.;
+        if slope(middle) > 0. {
+            upper = middle;
+        } else {
+            lower = middle;
+        }
+    }
+    (lower + upper) / 2.
+}
+
+/// Every `x` that gives the non-dimensional `time`, in increasing order, by Gooding's method:
+/// a starter fitted to the time-of-flight curve, then Halley's method on Gooding's own form of
+/// the time equation, [`tlamb`]. That form, and so Gooding's time, is twice Izzo's.
+fn gooding(time: f64, lambda: f64, revolutions: u32) -> Vec<f64> {
+    let target = 2. * time;
+    let q = lambda;
+    let m = revolutions as f64;
+    let thr2 = (1. - q * q).atan2(2. * q) / PI;
+    let halley = |mut x: f64| {
+        for _ in 0..MAX_ITER {
+            let (time, first, second, _) = tlamb(revolutions, q, x);
+            let residual = target - time;
+            if first == 0. {
+                break;
+            }
+            let step = residual * first / (first.powi(2) + residual * second / 2.);
+            x += step;
+            if !x.is_finite() || step.abs() < X_TOL {
+                break;
+            }
+        }
+        x
+    };
+    // Starter from the time of flight at `x = 0`, where its slope is -4, to the left of which
+    // the curve is fitted bilinearly, scaled by `factor`
+    let starter = |zero_time: f64, factor: f64| {
+        let difference = target - zero_time;
+        if difference <= 0. {
+            return zero_time * difference / (-4. * target);
+        }
+        let mut x = -difference / (difference + 4.);
+        let w = x + GOODING_C0 * (2. * (1. - thr2)).sqrt();
+        if w < 0. {
+            x -= (-w).powf(0.125) * (x + (difference / (difference + 1.5 * zero_time)).sqrt());
+        }
+        let w = 4. / (4. + difference);
+        x * (1. + factor * x * (GOODING_C1 * w - GOODING_C2 * x * w.sqrt()))
+    };
+    let (zero_time, ..) = tlamb(revolutions, q, 0.);
+    if revolutions == 0 {
+        return vec![halley(starter(zero_time, 1.))];
+    }
+
+    // Halley's method on the slope for the minimum time of flight
+    let mut x_min = 1. / (1.5 * (m + 0.5) * PI);
+    if thr2 < 0.5 {
+        x_min *= (2. * thr2).powf(0.125);
+    } else if thr2 > 0.5 {
+        x_min *= 2. - (2. - 2. * thr2).powf(0.125);
+    }
+    let mut minimum = None;
+    for _ in 0..12 {
+        let (time, first, second, third) = tlamb(revolutions, q, x_min);
+        if second == 0. {
+            minimum = Some((time, second));
+            break;
+        }
+        let previous = x_min;
+        x_min -= first * second / (second.powi(2) - first * third / 2.);
+        if (previous / x_min - 1.).abs() <= GOODING_MIN_TOL {
+            minimum = Some((time, second));
+            break;
+        }
+    }
+    let Some((min_time, second)) = minimum else {
+        return vec![];
+    };
+    let above_min = target - min_time;
+    if above_min < 0. {
+        return vec![];
+    } else if above_min == 0. {
+        return vec![x_min];
+    }
+    let half_second = if second == 0. {
+        3. * m * PI
+    } else {
+        second / 2.
+    };
+
+    let mut x = (above_min / (half_second + above_min / (1. - x_min).powi(2))).sqrt();
+    let w = x_min + x;
+    let w = w * 4. / (4. + above_min) + (1. - w).powi(2);
+    let factor = (1. + m + GOODING_C41 * (thr2 - 0.5)) / (1. + GOODING_C3 * m);
+    x = x * (1. - factor * x * (GOODING_C1 * w + GOODING_C2 * x * w.sqrt())) + x_min;
+    let right = (x < 1.).then(|| halley(x));
+
+    let x = if target <= zero_time {
+        let above_zero = zero_time - min_time;
+        x_min
+            - (above_min / (half_second - above_min * (half_second / above_zero - x_min.powi(-2))))
+                .sqrt()
+    } else {
+        let factor = (1. + m + GOODING_C42 * (thr2 - 0.5)) / (1. + GOODING_C3 * m);
+        starter(zero_time, factor)
+    };
+    let left = (x > -1.).then(|| halley(x));
+    left.into_iter().chain(right).collect()
+}
+
+/// Gooding's time of flight at `x` with `revolutions` revolutions for `q`, which is Izzo's
+/// `lambda`, and its first three derivatives.
+///
+/// Near the parabola, for direct transfers, it is summed as a series in `1 - x^2`, and far out on
+/// hyperbolic transfers its logarithmic term is too, so it keeps its precision everywhere.
+fn tlamb(revolutions: u32, q: f64, x: f64) -> (f64, f64, f64, f64) {
+    let m = revolutions as f64;
+    let q_squared = q * q;
+    let one_minus_q_squared = 1. - q_squared;
+    let x_squared = x * x;
+    let u = (1. - x) * (1. + x);
+    if revolutions > 0 || x < 0. || u.abs() > GOODING_SWITCH {
+        let y = u.abs().sqrt();
+        let z = (one_minus_q_squared + q_squared * x_squared).sqrt();
+        let qx = q * x;
+        let (a, b) = if qx <= 0. {
+            (z - qx, q * z - x)
+        } else {
+            (
+                one_minus_q_squared / (z + qx),
+                one_minus_q_squared * (q_squared * u - x_squared) / (q * z + x),
+            )
+        };
+        let g = if qx * u >= 0. {
+            x * z + q * u
+        } else {
+            (x_squared - q_squared * u) / (x * z - q * u)
+        };
+        let f = a * y;
+        let angle = if x <= 1. {
+            m * PI + f.atan2(g)
+        } else if f > GOODING_SWITCH {
+            (f + g).ln()
+        } else {
+            // 2 artanh(f / (g + 1)), by its series
+            let ratio = f / (g + 1.);
+            let ratio_squared = ratio * ratio;
+            let mut term = 2. * ratio;
+            let mut sum = term;
+            for odd in (3..).step_by(2) {
+                term *= ratio_squared;
+                let previous = sum;
+                sum += term / odd as f64;
+                if sum == previous {
+                    break;
+                }
+            }
+            sum
+        };
+        let time = 2. * (angle / y + b) / u;
+        if z == 0. {
+            return (time, 0., 0., 0.);
+        }
+        let qz = q / z;
+        let qz_squared = qz * qz;
+        let qz_cubed = qz * qz_squared;
+        let first = (3. * x * time - 4. * (a + qx * one_minus_q_squared) / z) / u;
+        let second = (3. * time + 5. * x * first + 4. * qz_cubed * one_minus_q_squared) / u;
+        let third = (8. * first + 7. * x * second
+            - 12. * qz_cubed * qz_squared * x * one_minus_q_squared)
+            / u;
+        return (time, first, second, third);
+    }
+
+    let (mut u_power, mut u_power_1, mut u_power_2, mut u_power_3) = (1., 1., 1., 1.);
+    let mut term = 4.;
+    let mut q_term = q * one_minus_q_squared;
+    let mut q_sum = if q < 0.5 {
+        1. - q * q_squared
+    } else {
+        (1. / (1. + q) + q) * one_minus_q_squared
+    };
+    let mut previous_term = term / 3.;
+    let mut time = previous_term * q_sum;
+    let (mut first, mut second, mut third) = (0., 0., 0.);
+    for index in 1.. {
+        let p = index as f64;
+        u_power *= u;
+        if index > 1 {
+            u_power_1 *= u;
+        }
+        if index > 2 {
+            u_power_2 *= u;
+        }
+        if index > 3 {
+            u_power_3 *= u;
+        }
+        term *= (p - 0.5) / p;
+        q_term *= q_squared;
+        q_sum += q_term;
+        let previous = time;
+        let time_term = term / (2. * p + 3.);
+        let tq_term = time_term * q_sum;
+        time -= u_power * ((1.5 * p + 0.25) * tq_term / (p * p - 0.25) - previous_term * q_term);
+        previous_term = time_term;
+        let tq_term = tq_term * p;
+        first += tq_term * u_power_1;
+        second += tq_term * u_power_2 * (p - 1.);
+        third += tq_term * u_power_3 * (p - 1.) * (p - 2.);
+        if index >= 3 && time == previous {
+            break;
+        }
+    }
+    let third = 8. * x * (1.5 * second - x_squared * third);
+    let second = 2. * (2. * x_squared * second - first);
+    let first = -2. * x * first;
+    (time / x_squared, first, second, third)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::bodies;
+    use crate::orbit::structs::CartesianState;
+    use crate::orbit::Keplerian;
+    use crate::testing;
+
+    /// Flies each solution from `departure` and checks that it reaches `arrival` on time
+    fn assert_reaches(
+        mu: f64,
+        departure: &Vector3,
+        arrival: &Vector3,
+        time_of_flight: f64,
+        solutions: &[LambertSolution],
+    ) {
+        let body = bodies::CentralBody {
+            mu,
+            ..bodies::EARTH
+        };
+        for solution in solutions {
+            let state = CartesianState::new(departure.clone(), solution.departure_velocity.clone())
+                .with_central_body(body);
+            let reached = Keplerian.propagate_cartesian(&state, time_of_flight);
+            testing::assert_array_eq_atol(&reached.position.elem, &arrival.elem, 1e-4);
+            testing::assert_array_eq_atol(
+                &reached.velocity.elem,
+                &solution.arrival_velocity.elem,
+                1e-7,
+            );
+        }
+    }
+
+    #[test]
+    /// Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., example 7-5
+    fn vallado_test() {
+        let mu = 3.986_004_418e14;
+        let departure = Vector3::new([15_945.34e3, 0., 0.]);
+        let arrival = Vector3::new([12_214.838_99e3, 10_249.467_31e3, 0.]);
+        let solutions = Lambert::new()
+            .solve(mu, &departure, &arrival, 76. * 60.)
+            .unwrap();
+        assert_eq!(solutions.len(), 1);
+        testing::assert_array_eq_atol(
+            &solutions[0].departure_velocity.elem,
+            &[2_058.913, 2_915.965, 0.],
+            0.5,
+        );
+        testing::assert_array_eq_atol(
+            &solutions[0].arrival_velocity.elem,
+            &[-3_451.565, 910.315, 0.],
+            0.5,
+        );
+    }
+
+    #[test]
+    /// Curtis, Orbital Mechanics for Engineering Students, 3rd ed., example 5.2
+    fn curtis_test() {
+        let mu = 398_600e9;
+        let departure = Vector3::new([5_000e3, 10_000e3, 2_100e3]);
+        let arrival = Vector3::new([-14_600e3, 2_500e3, 7_000e3]);
+        let solutions = Lambert::new()
+            .solve(mu, &departure, &arrival, 3_600.)
+            .unwrap();
+        testing::assert_array_eq_atol(
+            &solutions[0].departure_velocity.elem,
+            &[-5_992.5, 1_925.4, 3_245.6],
+            0.1,
+        );
+        testing::assert_array_eq_atol(
+            &solutions[0].arrival_velocity.elem,
+            &[-3_312.5, -4_196.6, -385.29],
+            0.1,
+        );
+        assert_reaches(mu, &departure, &arrival, 3_600., &solutions);
+    }
+
+    #[test]
+    /// Over five hours a low orbit fits up to two revolutions each way, and every solution flies
+    /// from one position to the other
+    fn multi_revolution_test() {
+        let mu = bodies::EARTH.mu;
+        let departure = Vector3::new([7_000e3, 0., 0.]);
+        let arrival = Vector3::new([-3_000e3, 6_500e3, 1_500e3]);
+        let time_of_flight = 5. * 3_600.;
+        let solutions = Lambert::new()
+            .with_revolutions(5)
+            .solve(mu, &departure, &arrival, time_of_flight)
+            .unwrap();
+        let revolutions: Vec<_> = solutions.iter().map(|s| s.revolutions).collect();
+        assert_eq!(revolutions, [0, 1, 1, 2, 2]);
+        assert_reaches(mu, &departure, &arrival, time_of_flight, &solutions);
+    }
+
+    #[test]
+    /// Retrograde transfers go clockwise about Z, the long way round for these positions
+    fn retrograde_test() {
+        let mu = bodies::EARTH.mu;
+        let departure = Vector3::new([7_000e3, 0., 0.]);
+        let arrival = Vector3::new([0., 7_500e3, 500e3]);
+        let lambert = Lambert::new().with_revolutions(1);
+        for direction in [Direction::Prograde, Direction::Retrograde] {
+            let solutions = lambert
+                .clone()
+                .with_direction(direction)
+                .solve(mu, &departure, &arrival, 8_000.)
+                .unwrap();
+            assert_reaches(mu, &departure, &arrival, 8_000., &solutions);
+            for solution in &solutions {
+                let momentum = departure.cross(&solution.departure_velocity).elem[2];
+                assert_eq!(momentum > 0., direction == Direction::Prograde);
+            }
+        }
+    }
+
+    #[test]
+    /// Gooding's time of flight and its derivatives are twice Izzo's, on elliptic, near-parabolic
+    /// and hyperbolic transfers, whichever way Gooding sums them
+    fn tlamb_test() {
+        for lambda in [-0.9, -0.3, 0., 0.4, 0.95] {
+            for (x, revolutions) in [
+                (-0.5, 0),
+                (0.3, 0),
+                (0.8, 0),
+                (0.95, 0),
+                (1.05, 0),
+                (1.5, 0),
+                (5., 0),
+                (-0.2, 2),
+                (0.6, 1),
+            ] {
+                let time = time_of_flight(x, lambda, revolutions);
+                let (first, second, third) = derivatives(x, time, lambda);
+                let (gooding_time, gooding_first, gooding_second, gooding_third) =
+                    tlamb(revolutions, lambda, x);
+                assert_relative_eq!(gooding_time, 2. * time, max_relative = 1e-12);
+                assert_relative_eq!(gooding_first, 2. * first, max_relative = 1e-9);
+                assert_relative_eq!(gooding_second, 2. * second, max_relative = 1e-8);
+                assert_relative_eq!(gooding_third, 2. * third, max_relative = 1e-7);
+            }
+        }
+    }
+
+    #[test]
+    /// Gooding's method finds the same roots as Izzo's, on both branches and for long and short
+    /// ways round, and across the parabolic series
+    fn fallback_test() {
+        for lambda in [-0.9, -0.3, 0., 0.4, 0.95] {
+            for time in [0.3, 1., 2., 10.] {
+                let x = householder(initial_guess(time, lambda), time, lambda, 0).unwrap();
+                let roots = gooding(time, lambda, 0);
+                assert_eq!(roots.len(), 1);
+                assert_relative_eq!(x, roots[0], epsilon = 1e-11);
+            }
+            for revolutions in [1, 2] {
+                let x_min = minimum_time_x(lambda, revolutions);
+                let time = time_of_flight(x_min, lambda, revolutions) * 1.3;
+                let guesses = multi_revolution_guesses(time, revolutions);
+                let roots = gooding(time, lambda, revolutions);
+                assert_eq!(roots.len(), 2);
+                for (guess, root) in guesses.into_iter().zip(roots) {
+                    let izzo = householder(guess, time, lambda, revolutions).unwrap();
+                    assert_relative_eq!(izzo, root, epsilon = 1e-11);
+                }
+                assert!(gooding(time * 0.7, lambda, revolutions).is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn error_test() {
+        let lambert = Lambert::new();
+        let position = Vector3::new([7_000e3, 0., 0.]);
+        assert_eq!(
+            lambert.solve(1., &position, &Vector3::new([8_000e3, 0., 0.]), 1.),
+            Err(LambertError::CollinearPositions)
+        );
+        assert_eq!(
+            lambert.solve(1., &position, &Vector3::new([-8_000e3, 0., 0.]), 1.),
+            Err(LambertError::CollinearPositions)
+        );
+        assert_eq!(
+            lambert.solve(1., &position, &Vector3::new([0., 1., 0.]), -1.),
+            Err(LambertError::NonPositiveTimeOfFlight(-1.))
+        );
+        assert_eq!(
+            lambert.solve(1., &Vector3::new([0.; 3]), &position, 1.),
+            Err(LambertError::ZeroPosition)
+        );
+    }
+}
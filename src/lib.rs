@@ -0,0 +1,59 @@
+//! Orbital mechanics: orbit representations and the conversions between them, anomalies,
+//! propagation and the physical constants of the bodies orbits are centered on.
+//!
+//! Most users will want everything in [`prelude`]:
+//!
+//! ```
+//! use orbitrs::prelude::*;
+//!
+//! let orbit = Builder::new()
+//!     .periapsis_altitude(400e3)
+//!     .apoapsis_altitude(4_000e3)
+//!     .inclination(0.9)
+//!     .build()
+//!     .unwrap();
+//! let later = Keplerian.propagate(&orbit, 3_600.);
+//! assert_eq!(later.central_body(), &bodies::EARTH);
+//! ```
+
+#![allow(clippy::wildcard_imports)]
+
+#[cfg(test)]
+#[macro_use]
+extern crate approx;
+
+pub mod angle_ops;
+pub mod anomaly;
+pub mod bodies;
+pub mod constants;
+pub mod ephemerides;
+pub mod forces;
+pub mod frames;
+pub mod integrators;
+pub mod lambert;
+pub mod maneuvers;
+pub mod orbit;
+pub mod passes;
+pub mod quaternions;
+pub mod relative;
+pub mod time;
+pub mod vector;
+pub mod vector_ops;
+
+mod roots;
+
+#[cfg(test)]
+mod testing;
+
+/// The types and traits needed to build, inspect and propagate orbits
+pub mod prelude {
+    pub use crate::bodies::{self, CentralBody};
+    pub use crate::integrators::{Integrator, OrbitDynamics};
+    pub use crate::orbit::structs::{COESlr, CartesianState, Orbit, COE, EOE, MEE};
+    pub use crate::orbit::{
+        BuildError, Builder, Keplerian, Oriented, Propagator, Representation, Scaled, Shaped,
+    };
+    pub use crate::quaternions::Quaternion;
+    pub use crate::time::{Epoch, TimeScale};
+    pub use crate::vector::Vector3;
+}
@@ -1,16 +1,22 @@
-#![allow(clippy::wildcard_imports)]
-
+#[cfg(test)]
 #[macro_use]
 extern crate approx;
 
-pub mod angle_ops;
-pub mod constants;
-pub mod orbit;
-pub mod quaternions;
-pub mod testing;
-pub mod vector;
-pub mod vector_ops;
+use std::process::ExitCode;
+
+use clap::Parser;
+
+mod cli;
 
-fn main() {
-    println!("Hello, world!");
+fn main() -> ExitCode {
+    match cli::Cli::parse().run() {
+        Ok(output) => {
+            print!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,695 @@
+//! Impulsive maneuvers between orbits about the same body, with their delta-v budgets and
+//! transfer times.
+//!
+//! Each maneuver starts from the initial orbit where it stands, coasts to where its first burn
+//! is due and returns the burns along with the orbit flown after each one, so that the whole
+//! sequence can be propagated and checked. Transfers to a final orbit match its size and plane,
+//! not the position of anything on it.
+//!
+//! A single burn can also be tried out on any orbit with [`apply_delta_v`], given in the inertial
+//! frame or one of the [`LocalFrame`]s that follow the orbiting body.
+//!
+//! ```
+//! use orbitrs::maneuvers;
+//! use orbitrs::prelude::*;
+//!
+//! let leo = COE::new(6_778e3, 0., 0.5, 0., 0., 0.);
+//! let meo = COE::new(26_560e3, 0., 0.5, 0., 0., 0.);
+//! let transfer = maneuvers::hohmann(&leo, &meo).unwrap();
+//! assert_eq!(transfer.burns.len(), 2);
+//! let arrival = Keplerian.propagate(&transfer.orbits[0], transfer.duration());
+//! let radius = arrival.to_cartesian().position.norm();
+//! assert!((radius - 26_560e3).abs() < 1e-3);
+//! ```
+
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::angle_ops;
+use crate::anomaly;
+use crate::frames::LocalFrame;
+use crate::orbit::structs::{CartesianState, Orbit, COE, EOE, MEE};
+use crate::orbit::{Keplerian, Propagator, Scaled};
+use crate::roots;
+use crate::vector::Vector3;
+
+/// Eccentricity below which an orbit counts as circular for transfers between circular orbits
+const ECCENTRICITY_TOL: f64 = 1e-6;
+
+/// Angle in radians between orbit planes below which they count as the same plane
+const PLANE_TOL: f64 = 1e-9;
+
+/// Tolerance on the share of the plane change made at the first burn of a combined transfer
+const SPLIT_TOL: f64 = 1e-10;
+
+/// Instantaneous change of velocity
+#[derive(Clone, Debug, PartialEq)]
+pub struct Burn {
+    /// Seconds after the start of the maneuver
+    pub time: f64,
+    /// Change of velocity in m/s, in the inertial frame of the orbit
+    pub delta_v: Vector3,
+}
+
+impl Burn {
+    pub fn magnitude(&self) -> f64 {
+        self.delta_v.norm()
+    }
+}
+
+/// Burns taking one orbit to another
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transfer {
+    pub burns: Vec<Burn>,
+    /// Orbit flown after each burn, at the instant of the burn, the last being the final orbit
+    pub orbits: Vec<Orbit>,
+}
+
+impl Transfer {
+    /// Total delta-v in m/s
+    pub fn delta_v(&self) -> f64 {
+        self.burns.iter().map(Burn::magnitude).sum()
+    }
+
+    /// Seconds from the start of the maneuver to the last burn
+    pub fn duration(&self) -> f64 {
+        self.burns.last().map_or(0., |burn| burn.time)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ManeuverError {
+    /// An orbit which should be circular, with the eccentricity it has
+    NotCircular(f64),
+    /// An orbit which never comes back round, with its eccentricity
+    OpenOrbit(f64),
+    DifferentBodies,
+    /// Orbits in different planes, with the angle between them in radians
+    NotCoplanar(f64),
+    /// A bi-elliptic apoapsis radius in m below one of the orbits
+    ApoapsisTooLow(f64),
+    /// A phase change in radians which the phasing orbit can't make up
+    UnreachablePhase(f64),
+    /// A true anomaly in radians which an open orbit never reaches again
+    UnreachableAnomaly(f64),
+    /// A state with no angular momentum, which leaves a local frame undefined
+    RectilinearOrbit,
+}
+
+impl fmt::Display for ManeuverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotCircular(e) => write!(f, "orbit with eccentricity {e} is not circular"),
+            Self::OpenOrbit(e) => write!(f, "orbit with eccentricity {e} is open"),
+            Self::DifferentBodies => write!(f, "orbits are about different central bodies"),
+            Self::NotCoplanar(angle) => {
+                write!(f, "orbit planes are {angle} rad apart, use a plane change")
+            }
+            Self::ApoapsisTooLow(radius) => {
+                write!(
+                    f,
+                    "bi-elliptic apoapsis radius {radius} m is inside an orbit"
+                )
+            }
+            Self::UnreachablePhase(phase) => {
+                write!(f, "phase change of {phase} rad needs more revolutions")
+            }
+            Self::UnreachableAnomaly(anomaly) => {
+                write!(f, "true anomaly {anomaly} rad is not ahead on the orbit")
+            }
+            Self::RectilinearOrbit => write!(f, "rectilinear orbit has no local frame"),
+        }
+    }
+}
+
+impl Error for ManeuverError {}
+
+/// Two tangential burns between coplanar circular orbits, through half an ellipse touching both
+pub fn hohmann(initial: &COE, final_: &COE) -> Result<Transfer, ManeuverError> {
+    check_circular(&[initial, final_])?;
+    check_coplanar(initial, final_)?;
+    let mut sequence = Sequence::new(initial);
+    let normal = sequence.normal();
+    sequence.half_ellipse(final_.semi_major_axis, &normal);
+    sequence.circularize(&normal);
+    Ok(sequence.finish())
+}
+
+/// Three tangential burns between coplanar circular orbits, out to `apoapsis_radius` on one half
+/// ellipse and back in to the final orbit on another. This beats a Hohmann transfer when the
+/// orbits' radii differ by a factor of more than about 11.94, given a high enough apoapsis.
+pub fn bi_elliptic(
+    initial: &COE,
+    apoapsis_radius: f64,
+    final_: &COE,
+) -> Result<Transfer, ManeuverError> {
+    check_circular(&[initial, final_])?;
+    check_coplanar(initial, final_)?;
+    if apoapsis_radius < initial.semi_major_axis.max(final_.semi_major_axis) {
+        return Err(ManeuverError::ApoapsisTooLow(apoapsis_radius));
+    }
+    let mut sequence = Sequence::new(initial);
+    let normal = sequence.normal();
+    sequence.half_ellipse(apoapsis_radius, &normal);
+    sequence.half_ellipse(final_.semi_major_axis, &normal);
+    sequence.circularize(&normal);
+    Ok(sequence.finish())
+}
+
+/// One burn at the first node reached, which turns the orbit into the plane of `inclination`
+/// and `raan` without changing its size or shape
+pub fn plane_change(initial: &COE, inclination: f64, raan: f64) -> Result<Transfer, ManeuverError> {
+    if initial.eccentricity >= 1. {
+        return Err(ManeuverError::OpenOrbit(initial.eccentricity));
+    }
+    let target = plane_normal(inclination, raan);
+    let mut sequence = Sequence::new(initial);
+    sequence.coast_to_node(&target);
+    let CartesianState {
+        position, velocity, ..
+    } = &sequence.state;
+    let radius = position.norm();
+    let radial = position.clone() * (1. / radius);
+    let horizontal_speed = position.cross(velocity).norm() / radius;
+    let velocity = radial.clone() * radial.dot(velocity) + target.cross(&radial) * horizontal_speed;
+    sequence.burn(velocity);
+    Ok(sequence.finish())
+}
+
+/// Hohmann transfer between circular orbits in different planes, which starts at the first node
+/// reached and shares the plane change between both burns so as to spend the least delta-v
+pub fn hohmann_with_plane_change(initial: &COE, final_: &COE) -> Result<Transfer, ManeuverError> {
+    check_circular(&[initial, final_])?;
+    let mu = initial.central_body.mu;
+    let end_normal = plane_normal(final_.inclination, final_.raan);
+    let mut sequence = Sequence::new(initial);
+    let start_normal = sequence.normal();
+    sequence.coast_to_node(&end_normal);
+
+    let (start_radius, end_radius) = (initial.semi_major_axis, final_.semi_major_axis);
+    let transfer_sma = (start_radius + end_radius) / 2.;
+    let speeds = [
+        (mu / start_radius).sqrt(),
+        vis_viva(mu, start_radius, transfer_sma),
+        vis_viva(mu, end_radius, transfer_sma),
+        (mu / end_radius).sqrt(),
+    ];
+    let line_of_nodes = start_normal.cross(&end_normal);
+    let angle = line_of_nodes.norm().atan2(start_normal.dot(&end_normal));
+    // Law of cosines for both burns, with `split` of the plane change made at the first
+    let cost = |split: f64| {
+        let burn = |before: f64, after: f64, angle: f64| {
+            (before.powi(2) + after.powi(2) - 2. * before * after * angle.cos()).sqrt()
+        };
+        burn(speeds[0], speeds[1], split * angle) + burn(speeds[2], speeds[3], (1. - split) * angle)
+    };
+    let split = roots::golden_section_maximum(|split| -cost(split), 0., 1., SPLIT_TOL);
+
+    let transfer_normal = if angle < PLANE_TOL {
+        start_normal
+    } else {
+        // Turning the initial plane about the line of nodes by `angle` gives the final one
+        let axis = line_of_nodes.clone() * (1. / line_of_nodes.norm());
+        let turn = split * angle;
+        start_normal.clone() * turn.cos() + axis.cross(&start_normal) * turn.sin()
+    };
+    sequence.half_ellipse(end_radius, &transfer_normal);
+    sequence.circularize(&end_normal);
+    Ok(sequence.finish())
+}
+
+/// Two tangential burns that bring a circular orbit back to itself `phase` radians further
+/// along, positive ahead, after `revolutions` revolutions of a phasing orbit. Phasing orbits
+/// which dip into the central body are not caught.
+pub fn phasing(initial: &COE, phase: f64, revolutions: u32) -> Result<Transfer, ManeuverError> {
+    check_circular(&[initial])?;
+    let mu = initial.central_body.mu;
+    let period = initial.period() * (1. - phase / (2. * PI * revolutions as f64));
+    if revolutions == 0 || period <= 0. {
+        return Err(ManeuverError::UnreachablePhase(phase));
+    }
+    let phasing_sma = (mu * (period / (2. * PI)).powi(2)).cbrt();
+    let mut sequence = Sequence::new(initial);
+    let normal = sequence.normal();
+    let radius = sequence.state.position.norm();
+    sequence.burn_horizontal(vis_viva(mu, radius, phasing_sma), &normal);
+    sequence.coast(revolutions as f64 * period);
+    sequence.circularize(&normal);
+    Ok(sequence.finish())
+}
+
+/// Where along an orbit a burn is made
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BurnPoint {
+    /// The next time the orbit passes this true anomaly in radians, which may be now
+    TrueAnomaly(f64),
+    /// This many seconds after the orbit's current state, or before if negative
+    TimeOfFlight(f64),
+}
+
+/// Orbit flown after a burn of `delta_v`, in m/s along the axes of `frame`, made at `at`.
+///
+/// The orbit coasts on a Keplerian arc to the burn, and the result is given at the instant of
+/// the burn, in the same representation as `orbit` and with its epoch moved on if it had one.
+pub fn apply_delta_v(
+    orbit: &Orbit,
+    at: BurnPoint,
+    delta_v: &Vector3,
+    frame: LocalFrame,
+) -> Result<Orbit, ManeuverError> {
+    let time_of_flight = match at {
+        BurnPoint::TimeOfFlight(time_of_flight) => time_of_flight,
+        BurnPoint::TrueAnomaly(true_anomaly) => time_to_anomaly(&orbit.to_coe(), true_anomaly)?,
+    };
+    let burn_point = Keplerian.propagate(orbit, time_of_flight);
+    let mut state = burn_point.to_cartesian();
+    let to_frame = frame
+        .rotation(&state)
+        .ok_or(ManeuverError::RectilinearOrbit)?;
+    state.velocity = state.velocity.clone() + to_frame.rotated_vec_alibi(delta_v);
+    Ok(match burn_point {
+        Orbit::COE(_) => Orbit::COE((&state).into()),
+        Orbit::Cartesian(_) => Orbit::Cartesian(state),
+        Orbit::EOE(_) => Orbit::EOE(EOE::from(&MEE::from(&state))),
+        Orbit::MEE(_) => Orbit::MEE(MEE::from(&state)),
+    })
+}
+
+/// Seconds from where `coe` stands until it next passes `true_anomaly`
+fn time_to_anomaly(coe: &COE, true_anomaly: f64) -> Result<f64, ManeuverError> {
+    let mean_anomaly = |true_anomaly| anomaly::true_to_mean(true_anomaly, coe.eccentricity);
+    if coe.eccentricity < 1. {
+        let sweep = mean_anomaly(true_anomaly) - mean_anomaly(coe.true_anomaly);
+        return Ok(angle_ops::wrap_0_2pi(sweep) / coe.mean_motion());
+    }
+    let true_anomaly = angle_ops::wrap_negpi_pi(true_anomaly);
+    let start = angle_ops::wrap_negpi_pi(coe.true_anomaly);
+    if 1. + coe.eccentricity * true_anomaly.cos() <= 0. || true_anomaly < start {
+        return Err(ManeuverError::UnreachableAnomaly(true_anomaly));
+    }
+    Ok((mean_anomaly(true_anomaly) - mean_anomaly(start)) / coe.mean_motion())
+}
+
+/// Speed at `radius` on an orbit with the given semi-major axis
+fn vis_viva(mu: f64, radius: f64, semi_major_axis: f64) -> f64 {
+    (mu * (2. / radius - 1. / semi_major_axis)).sqrt()
+}
+
+/// Unit normal of the orbit plane with the given inclination and right ascension
+fn plane_normal(inclination: f64, raan: f64) -> Vector3 {
+    let (sin_inclination, cos_inclination) = inclination.sin_cos();
+    let (sin_raan, cos_raan) = raan.sin_cos();
+    Vector3::new([
+        sin_raan * sin_inclination,
+        -cos_raan * sin_inclination,
+        cos_inclination,
+    ])
+}
+
+fn check_circular(orbits: &[&COE]) -> Result<(), ManeuverError> {
+    if orbits
+        .iter()
+        .any(|coe| coe.central_body != orbits[0].central_body)
+    {
+        return Err(ManeuverError::DifferentBodies);
+    }
+    match orbits
+        .iter()
+        .find(|coe| coe.eccentricity > ECCENTRICITY_TOL)
+    {
+        Some(coe) => Err(ManeuverError::NotCircular(coe.eccentricity)),
+        None => Ok(()),
+    }
+}
+
+fn check_coplanar(initial: &COE, final_: &COE) -> Result<(), ManeuverError> {
+    let start = plane_normal(initial.inclination, initial.raan);
+    let end = plane_normal(final_.inclination, final_.raan);
+    let angle = start.cross(&end).norm().atan2(start.dot(&end));
+    if angle > PLANE_TOL {
+        return Err(ManeuverError::NotCoplanar(angle));
+    }
+    Ok(())
+}
+
+/// Transfer built up by coasting and burning
+struct Sequence {
+    state: CartesianState,
+    time: f64,
+    burns: Vec<Burn>,
+    orbits: Vec<Orbit>,
+}
+
+impl Sequence {
+    fn new(initial: &COE) -> Self {
+        Self {
+            state: initial.into(),
+            time: 0.,
+            burns: vec![],
+            orbits: vec![],
+        }
+    }
+
+    fn finish(self) -> Transfer {
+        Transfer {
+            burns: self.burns,
+            orbits: self.orbits,
+        }
+    }
+
+    /// Unit normal of the current orbit
+    fn normal(&self) -> Vector3 {
+        let momentum = self.state.position.cross(&self.state.velocity);
+        momentum.clone() * (1. / momentum.norm())
+    }
+
+    fn coast(&mut self, duration: f64) {
+        self.state = Keplerian.propagate_cartesian(&self.state, duration);
+        self.time += duration;
+    }
+
+    /// Coasts to whichever node with the plane of `normal` comes first, or nowhere if the
+    /// planes are the same
+    fn coast_to_node(&mut self, normal: &Vector3) {
+        let own_normal = self.normal();
+        let line_of_nodes = own_normal.cross(normal);
+        if line_of_nodes.norm() < PLANE_TOL {
+            return;
+        }
+        let position = &self.state.position;
+        let sin_angle = own_normal.dot(&position.cross(&line_of_nodes));
+        let angle = angle_ops::wrap_0_2pi(sin_angle.atan2(position.dot(&line_of_nodes))) % PI;
+        let coe = COE::from(&self.state);
+        let mean_anomaly = |true_anomaly| anomaly::true_to_mean(true_anomaly, coe.eccentricity);
+        let sweep = mean_anomaly(coe.true_anomaly + angle) - mean_anomaly(coe.true_anomaly);
+        self.coast(angle_ops::wrap_0_2pi(sweep) / coe.mean_motion());
+    }
+
+    fn burn(&mut self, velocity: Vector3) {
+        self.burns.push(Burn {
+            time: self.time,
+            delta_v: velocity.clone() - self.state.velocity.clone(),
+        });
+        self.state.velocity = velocity;
+        self.orbits.push(Orbit::COE((&self.state).into()));
+    }
+
+    /// Burns to move horizontally at `speed`, in the plane of `normal`
+    fn burn_horizontal(&mut self, speed: f64, normal: &Vector3) {
+        let position = &self.state.position;
+        let radial = position.clone() * (1. / position.norm());
+        self.burn(normal.cross(&radial) * speed);
+    }
+
+    /// Burns onto the ellipse, in the plane of `normal`, whose other apsis is at `radius`, and
+    /// coasts there
+    fn half_ellipse(&mut self, radius: f64, normal: &Vector3) {
+        let mu = self.state.central_body.mu;
+        let here = self.state.position.norm();
+        let semi_major_axis = (here + radius) / 2.;
+        self.burn_horizontal(vis_viva(mu, here, semi_major_axis), normal);
+        self.coast(PI * (semi_major_axis.powi(3) / mu).sqrt());
+    }
+
+    /// Burns onto the circular orbit through the current position, in the plane of `normal`
+    fn circularize(&mut self, normal: &Vector3) {
+        let mu = self.state.central_body.mu;
+        let speed = (mu / self.state.position.norm()).sqrt();
+        self.burn_horizontal(speed, normal);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::bodies;
+    use crate::orbit::{Oriented, Shaped};
+    use crate::testing;
+    use crate::time::Epoch;
+
+    fn circular(radius: f64, inclination: f64, raan: f64) -> COE {
+        COE::new(radius, 0., inclination, 0., raan, 1.)
+    }
+
+    /// Each orbit of the transfer, flown until the next burn, arrives where that burn happens
+    fn assert_continuous(initial: &COE, transfer: &Transfer) {
+        let mut orbit = Orbit::COE(initial.clone());
+        let mut time = 0.;
+        for (burn, next) in transfer.burns.iter().zip(&transfer.orbits) {
+            let before = Keplerian.propagate(&orbit, burn.time - time).to_cartesian();
+            let after = next.to_cartesian();
+            testing::assert_array_eq_atol(&before.position.elem, &after.position.elem, 1e-4);
+            let delta_v = after.velocity - before.velocity;
+            testing::assert_array_eq_atol(&delta_v.elem, &burn.delta_v.elem, 1e-7);
+            (orbit, time) = (next.clone(), burn.time);
+        }
+    }
+
+    #[test]
+    /// Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., example 6-1: LEO to GEO
+    fn hohmann_test() {
+        let initial = circular(6_569_481.11, 0.4, 1.);
+        let final_ = circular(42_159_485.57, 0.4, 1.);
+        let transfer = hohmann(&initial, &final_).unwrap();
+        assert_relative_eq!(
+            transfer.burns[0].magnitude(),
+            2_457.038,
+            max_relative = 1e-5
+        );
+        assert_relative_eq!(
+            transfer.burns[1].magnitude(),
+            1_478.187,
+            max_relative = 1e-5
+        );
+        assert_relative_eq!(transfer.duration(), 5.256_713 * 3_600., max_relative = 1e-5);
+        assert_continuous(&initial, &transfer);
+
+        let arrived = transfer.orbits[1].to_coe();
+        assert_relative_eq!(
+            arrived.semi_major_axis,
+            final_.semi_major_axis,
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(arrived.eccentricity, 0., epsilon = 1e-12);
+        assert_relative_eq!(arrived.inclination, 0.4, epsilon = 1e-12);
+
+        // Coming down costs the same
+        let down = hohmann(&final_, &initial).unwrap();
+        assert_relative_eq!(down.delta_v(), transfer.delta_v(), max_relative = 1e-12);
+        assert_continuous(&final_, &down);
+    }
+
+    #[test]
+    fn bi_elliptic_test() {
+        let mu = bodies::EARTH.mu;
+        let (initial, final_) = (circular(7e6, 0., 0.), circular(140e6, 0., 0.));
+        let hohmann = hohmann(&initial, &final_).unwrap();
+
+        // Turning around at the final orbit is a Hohmann transfer followed by half a revolution
+        // of the final orbit
+        let transfer = bi_elliptic(&initial, 140e6, &final_).unwrap();
+        assert_relative_eq!(
+            transfer.burns[0].magnitude(),
+            hohmann.burns[0].magnitude(),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            transfer.burns[1].magnitude(),
+            hohmann.burns[1].magnitude(),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(transfer.burns[2].magnitude(), 0., epsilon = 1e-8);
+        let half_revolution = PI * (140e6_f64.powi(3) / mu).sqrt();
+        assert_relative_eq!(
+            transfer.duration(),
+            hohmann.duration() + half_revolution,
+            max_relative = 1e-12
+        );
+
+        // Beyond a radius ratio of about 15.58, going out far enough beats a Hohmann transfer
+        let transfer = bi_elliptic(&initial, 700e6, &final_).unwrap();
+        assert!(transfer.delta_v() < hohmann.delta_v());
+        assert_continuous(&initial, &transfer);
+    }
+
+    #[test]
+    /// A plane change costs `2 v sin(angle / 2)` in the horizontal velocity, and leaves the
+    /// shape of the orbit alone
+    fn plane_change_test() {
+        let initial = COE::new(9_000e3, 0.2, 28.5_f64.to_radians(), 1., 0.3, 2.5);
+        let (inclination, raan) = (51.6_f64.to_radians(), 1.);
+        let transfer = plane_change(&initial, inclination, raan).unwrap();
+        assert_eq!(transfer.burns.len(), 1);
+        assert!(transfer.duration() < initial.period());
+        assert_continuous(&initial, &transfer);
+
+        let changed = transfer.orbits[0].to_coe();
+        assert_relative_eq!(changed.inclination(), inclination, epsilon = 1e-12);
+        assert_relative_eq!(changed.raan(), raan, epsilon = 1e-12);
+        assert_relative_eq!(changed.semi_major_axis, 9_000e3, max_relative = 1e-12);
+        assert_relative_eq!(changed.eccentricity(), 0.2, epsilon = 1e-12);
+
+        let start = plane_normal(initial.inclination, initial.raan);
+        let end = plane_normal(inclination, raan);
+        let angle = start.dot(&end).acos();
+        let state = transfer.orbits[0].to_cartesian();
+        let horizontal_speed = state.position.cross(&state.velocity).norm() / state.position.norm();
+        assert_relative_eq!(
+            transfer.delta_v(),
+            2. * horizontal_speed * (angle / 2.).sin(),
+            max_relative = 1e-10
+        );
+    }
+
+    #[test]
+    /// LEO at 28.5° to GEO does best turning a few degrees at perigee and the rest at apogee
+    fn combined_plane_change_test() {
+        let initial = circular(6_678e3, 28.5_f64.to_radians(), 0.2);
+        let final_ = circular(42_164e3, 0., 0.);
+        let transfer = hohmann_with_plane_change(&initial, &final_).unwrap();
+        assert_continuous(&initial, &transfer);
+        let arrived = transfer.orbits[1].to_coe();
+        assert_relative_eq!(arrived.inclination, 0., epsilon = 1e-10);
+        assert_relative_eq!(arrived.semi_major_axis, 42_164e3, max_relative = 1e-10);
+        assert_relative_eq!(arrived.eccentricity, 0., epsilon = 1e-10);
+
+        let transfer_plane = transfer.orbits[0].to_coe().inclination;
+        let first_turn = (initial.inclination - transfer_plane).to_degrees();
+        assert!((1.0..3.0).contains(&first_turn), "{first_turn}");
+
+        // Better than making the whole change at either burn
+        let mu = bodies::EARTH.mu;
+        let (fast, transfer_low, transfer_high, slow) = (
+            (mu / 6_678e3_f64).sqrt(),
+            vis_viva(mu, 6_678e3, (6_678e3 + 42_164e3) / 2.),
+            vis_viva(mu, 42_164e3, (6_678e3 + 42_164e3) / 2.),
+            (mu / 42_164e3_f64).sqrt(),
+        );
+        let turn = |before: f64, after: f64| {
+            (before.powi(2) + after.powi(2) - 2. * before * after * initial.inclination.cos())
+                .sqrt()
+        };
+        let at_apogee = (transfer_low - fast) + turn(transfer_high, slow);
+        let at_perigee = turn(fast, transfer_low) + (slow - transfer_high);
+        assert!(transfer.delta_v() < at_apogee - 10.);
+        assert!(at_apogee < at_perigee);
+    }
+
+    #[test]
+    /// After phasing, the orbit is the initial one run forward by the time taken, plus the phase
+    fn phasing_test() {
+        let initial = circular(7_000e3, 0.9, 0.4);
+        let mean_motion = initial.mean_motion();
+        for phase in [-0.5, 0.3] {
+            let transfer = phasing(&initial, phase, 3).unwrap();
+            assert_continuous(&initial, &transfer);
+            let ghost = Keplerian.propagate(
+                &Orbit::COE(initial.clone()),
+                transfer.duration() + phase / mean_motion,
+            );
+            let arrived = transfer.orbits[1].to_cartesian();
+            testing::assert_array_eq_atol(
+                &arrived.position.elem,
+                &ghost.to_cartesian().position.elem,
+                1e-4,
+            );
+            testing::assert_array_eq_atol(
+                &arrived.velocity.elem,
+                &ghost.to_cartesian().velocity.elem,
+                1e-7,
+            );
+        }
+        assert_eq!(
+            phasing(&initial, 7., 1),
+            Err(ManeuverError::UnreachablePhase(7.))
+        );
+    }
+
+    #[test]
+    /// A prograde burn on a circular orbit is the same in every moving frame, leaves the burn point
+    /// at periapsis and sets the semi-major axis through vis-viva
+    fn apply_delta_v_test() {
+        let initial = Orbit::COE(circular(7e6, 0.5, 1.));
+        let mu = bodies::EARTH.mu;
+        let at = BurnPoint::TrueAnomaly(2.);
+        let speed = (mu / 7e6).sqrt() + 100.;
+        let expected_sma = 1. / (2. / 7e6 - speed.powi(2) / mu);
+        let expected = apply_delta_v(&initial, at, &Vector3::new([0., 100., 0.]), LocalFrame::RIC)
+            .unwrap()
+            .to_cartesian();
+        for (frame, delta_v) in [
+            (LocalFrame::VNC, [100., 0., 0.]),
+            (LocalFrame::NTW, [0., 100., 0.]),
+            (LocalFrame::LVLH, [100., 0., 0.]),
+        ] {
+            let after = apply_delta_v(&initial, at, &Vector3::new(delta_v), frame).unwrap();
+            let after = after.to_cartesian();
+            testing::assert_array_eq_atol(&after.position.elem, &expected.position.elem, 1e-6);
+            testing::assert_array_eq_atol(&after.velocity.elem, &expected.velocity.elem, 1e-9);
+        }
+        let coe = COE::from(&expected);
+        assert_relative_eq!(coe.semi_major_axis, expected_sma, max_relative = 1e-12);
+        let latitude = angle_ops::wrap_0_2pi(coe.arg_peri + coe.true_anomaly);
+        assert_relative_eq!(latitude, 2., epsilon = 1e-9);
+        assert_relative_eq!(coe.true_anomaly.sin(), 0., epsilon = 1e-9);
+
+        // An inertial burn adds straight onto the velocity
+        let epoch: Epoch = "2024-01-01T00:00:00Z".parse().unwrap();
+        let mee = Orbit::MEE(MEE::from(&COE::new(8e6, 0.2, 0.3, 0.4, 0.5, 0.6))).with_epoch(epoch);
+        let delta_v = Vector3::new([1., -2., 3.]);
+        let at = BurnPoint::TimeOfFlight(600.);
+        let after = apply_delta_v(&mee, at, &delta_v, LocalFrame::Inertial).unwrap();
+        assert!(matches!(after, Orbit::MEE(_)));
+        assert_eq!(after.epoch(), Some(epoch + 600.));
+        let before = Keplerian.propagate(&mee, 600.).to_cartesian();
+        let change = after.to_cartesian().velocity - before.velocity;
+        testing::assert_array_eq_atol(&change.elem, &delta_v.elem, 1e-9);
+    }
+
+    #[test]
+    /// Open orbits only reach true anomalies ahead of them and inside their asymptotes
+    fn apply_delta_v_open_test() {
+        let open = Orbit::COE(COE::new(-7e6, 1.5, 0.5, 0., 0., 0.5));
+        let delta_v = Vector3::new([0., 10., 0.]);
+        let after = apply_delta_v(&open, BurnPoint::TrueAnomaly(1.), &delta_v, LocalFrame::RIC);
+        let Ok(Orbit::COE(coe)) = after else {
+            panic!("burn should be made");
+        };
+        assert_relative_eq!(coe.arg_peri + coe.true_anomaly, 1., epsilon = 1e-9);
+        for true_anomaly in [0., 2.5] {
+            let at = BurnPoint::TrueAnomaly(true_anomaly);
+            assert_eq!(
+                apply_delta_v(&open, at, &delta_v, LocalFrame::RIC),
+                Err(ManeuverError::UnreachableAnomaly(true_anomaly))
+            );
+        }
+    }
+
+    #[test]
+    fn error_test() {
+        let (low, high) = (circular(7e6, 0.5, 0.), circular(8e6, 0.5, 0.));
+        let tilted = circular(8e6, 0.6, 0.);
+        assert!(matches!(
+            hohmann(&low, &tilted),
+            Err(ManeuverError::NotCoplanar(_))
+        ));
+        let elliptic = COE::new(7e6, 0.1, 0.5, 0., 0., 0.);
+        assert_eq!(
+            hohmann(&elliptic, &high),
+            Err(ManeuverError::NotCircular(0.1))
+        );
+        assert_eq!(
+            bi_elliptic(&low, 7.5e6, &high),
+            Err(ManeuverError::ApoapsisTooLow(7.5e6))
+        );
+        let lunar = high.clone().with_central_body(bodies::MOON);
+        assert_eq!(hohmann(&low, &lunar), Err(ManeuverError::DifferentBodies));
+        let open = COE::new(-7e6, 1.5, 0.5, 0., 0., 0.);
+        assert_eq!(
+            plane_change(&open, 0.2, 0.),
+            Err(ManeuverError::OpenOrbit(1.5))
+        );
+    }
+}
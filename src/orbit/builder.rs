@@ -1,8 +1,121 @@
-use crate::orbit::structs;
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt;
 
+use crate::anomaly;
+use crate::bodies::{self, CentralBody};
+use crate::orbit::structs::{self, CartesianState, COE, EOE, MEE};
+use crate::time::Epoch;
+use crate::vector::Vector3;
+
+/// Relative tolerance when checking that redundant parameters describe the same orbit
+const CONSISTENCY_RTOL: f64 = 1e-9;
+
+/// Orbit representations the builder can produce
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Representation {
+    COE,
+    Cartesian,
+    EOE,
+    MEE,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BuildError {
+    /// The parameters don't pin down the size (or the full state) of the orbit
+    NotEnoughParameters(&'static str),
+    /// Redundant parameters were given which describe different orbits
+    Inconsistent(&'static str),
+    /// A state vector was given alongside orbital elements
+    Overdetermined(&'static str),
+    NonFinite(&'static str),
+    NegativeEccentricity(f64),
+    /// The sign of the semi-major axis doesn't match the conic given by the eccentricity
+    SemiMajorAxisSign {
+        semi_major_axis: f64,
+        eccentricity: f64,
+    },
+    InclinationOutOfRange(f64),
+    /// Apsides that are non-positive or out of order
+    InvalidApsides {
+        periapsis: f64,
+        apoapsis: f64,
+    },
+    NonPositive(&'static str),
+    /// A true anomaly beyond the asymptotes of an open orbit
+    UnreachableAnomaly(f64),
+    /// A state vector with no angular momentum, which has no orbital elements
+    DegenerateState,
+    /// The requested representation can't hold the orbit, such as a parabolic `COE`
+    Unrepresentable(Representation),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotEnoughParameters(what) => write!(f, "not enough parameters: {what}"),
+            Self::Inconsistent(what) => write!(f, "inconsistent parameters: {what}"),
+            Self::Overdetermined(what) => write!(f, "overdetermined orbit: {what}"),
+            Self::NonFinite(what) => write!(f, "{what} must be finite"),
+            Self::NegativeEccentricity(e) => write!(f, "eccentricity {e} is negative"),
+            Self::SemiMajorAxisSign {
+                semi_major_axis,
+                eccentricity,
+            } => write!(
+                f,
+                "semi-major axis {semi_major_axis} has the wrong sign for eccentricity {eccentricity}"
+            ),
+            Self::InclinationOutOfRange(inc) => {
+                write!(f, "inclination {inc} is outside [0, pi]")
+            }
+            Self::InvalidApsides {
+                periapsis,
+                apoapsis,
+            } => write!(
+                f,
+                "periapsis radius {periapsis} and apoapsis radius {apoapsis} are invalid"
+            ),
+            Self::NonPositive(what) => write!(f, "{what} must be positive"),
+            Self::UnreachableAnomaly(nu) => {
+                write!(f, "true anomaly {nu} is beyond the asymptotes of the orbit")
+            }
+            Self::DegenerateState => write!(f, "state vector has no angular momentum"),
+            Self::Unrepresentable(representation) => {
+                write!(f, "orbit can't be represented as {representation:?}")
+            }
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+/// Assembles an `Orbit` from any consistent subset of parameters.
+///
+/// The size and shape of the orbit can come from the semi-major axis, the period, the semi-latus
+/// rectum, or the periapsis and apoapsis (as radii or altitudes), combined with the eccentricity
+/// where needed. Without an eccentricity, two different size parameters fix it and a single one
+/// gives a circular orbit. Redundant parameters are accepted as long as they agree. Orientation angles and
+/// the anomaly default to zero. Alternatively, a position and velocity give the full state.
 #[derive(Default)]
 pub struct Builder {
     semi_major_axis: Option<f64>,
+    eccentricity: Option<f64>,
+    inclination: Option<f64>,
+    arg_peri: Option<f64>,
+    raan: Option<f64>,
+    true_anomaly: Option<f64>,
+    mean_anomaly: Option<f64>,
+    semi_latus_rectum: Option<f64>,
+    period: Option<f64>,
+    periapsis_radius: Option<f64>,
+    apoapsis_radius: Option<f64>,
+    periapsis_altitude: Option<f64>,
+    apoapsis_altitude: Option<f64>,
+    position: Option<Vector3>,
+    velocity: Option<Vector3>,
+    central_body: Option<CentralBody>,
+    representation: Option<Representation>,
+    epoch: Option<Epoch>,
 }
 
 pub trait FromBuilder {
@@ -21,25 +134,453 @@ impl Builder {
         self
     }
 
-    pub fn build(&mut self) -> structs::Orbit {
-        match self.semi_major_axis {
-            Some(semi_major_axis) => structs::Orbit::COE(structs::COE {
-                semi_major_axis,
-                eccentricity: 0.,
-                inclination: 0.,
-                arg_peri: 0.,
-                raan: 0.,
-                true_anomaly: 0.,
-            }),
-            None => panic!("Not enough fields defined to construct any known Orbit variant"),
+    pub fn eccentricity(&mut self, eccentricity: f64) -> &mut Self {
+        self.eccentricity = Some(eccentricity);
+        self
+    }
+
+    pub fn inclination(&mut self, inclination: f64) -> &mut Self {
+        self.inclination = Some(inclination);
+        self
+    }
+
+    pub fn arg_peri(&mut self, arg_peri: f64) -> &mut Self {
+        self.arg_peri = Some(arg_peri);
+        self
+    }
+
+    pub fn raan(&mut self, raan: f64) -> &mut Self {
+        self.raan = Some(raan);
+        self
+    }
+
+    pub fn true_anomaly(&mut self, true_anomaly: f64) -> &mut Self {
+        self.true_anomaly = Some(true_anomaly);
+        self
+    }
+
+    pub fn mean_anomaly(&mut self, mean_anomaly: f64) -> &mut Self {
+        self.mean_anomaly = Some(mean_anomaly);
+        self
+    }
+
+    pub fn semi_latus_rectum(&mut self, slr: f64) -> &mut Self {
+        self.semi_latus_rectum = Some(slr);
+        self
+    }
+
+    pub fn period(&mut self, period: f64) -> &mut Self {
+        self.period = Some(period);
+        self
+    }
+
+    pub fn periapsis_radius(&mut self, radius: f64) -> &mut Self {
+        self.periapsis_radius = Some(radius);
+        self
+    }
+
+    pub fn apoapsis_radius(&mut self, radius: f64) -> &mut Self {
+        self.apoapsis_radius = Some(radius);
+        self
+    }
+
+    /// Periapsis altitude above the equatorial radius of the central body
+    pub fn periapsis_altitude(&mut self, altitude: f64) -> &mut Self {
+        self.periapsis_altitude = Some(altitude);
+        self
+    }
+
+    /// Apoapsis altitude above the equatorial radius of the central body
+    pub fn apoapsis_altitude(&mut self, altitude: f64) -> &mut Self {
+        self.apoapsis_altitude = Some(altitude);
+        self
+    }
+
+    pub fn position(&mut self, position: Vector3) -> &mut Self {
+        self.position = Some(position);
+        self
+    }
+
+    pub fn velocity(&mut self, velocity: Vector3) -> &mut Self {
+        self.velocity = Some(velocity);
+        self
+    }
+
+    /// Body the orbit is centered on, the Earth unless set
+    pub fn central_body(&mut self, central_body: CentralBody) -> &mut Self {
+        self.central_body = Some(central_body);
+        self
+    }
+
+    /// Representation of the built orbit. Unless set, a state vector builds a `Cartesian` orbit,
+    /// a semi-latus rectum or a parabolic orbit builds `MEE`, and anything else builds `COE`.
+    pub fn representation(&mut self, representation: Representation) -> &mut Self {
+        self.representation = Some(representation);
+        self
+    }
+
+    /// Instant the orbit is at, left unknown unless set
+    pub fn epoch(&mut self, epoch: Epoch) -> &mut Self {
+        self.epoch = Some(epoch);
+        self
+    }
+
+    pub fn build(&self) -> Result<structs::Orbit, BuildError> {
+        let orbit = self.build_orbit()?;
+        Ok(match self.epoch {
+            Some(epoch) => orbit.with_epoch(epoch),
+            None => orbit,
+        })
+    }
+
+    fn build_orbit(&self) -> Result<structs::Orbit, BuildError> {
+        let central_body = self.central_body.unwrap_or(bodies::EARTH);
+        let state = match (&self.position, &self.velocity) {
+            (Some(position), Some(velocity)) => {
+                self.check_no_elements()?;
+                check_finite("position", &position.elem)?;
+                check_finite("velocity", &velocity.elem)?;
+                let state = CartesianState::new(position.clone(), velocity.clone())
+                    .with_central_body(central_body);
+                if position.cross(velocity).norm() == 0. {
+                    return Err(BuildError::DegenerateState);
+                }
+                return convert_state(
+                    state,
+                    self.representation.unwrap_or(Representation::Cartesian),
+                );
+            }
+            (Some(_), None) => {
+                return Err(BuildError::NotEnoughParameters("velocity"));
+            }
+            (None, Some(_)) => {
+                return Err(BuildError::NotEnoughParameters("position"));
+            }
+            (None, None) => self.elements(&central_body)?,
+        };
+        let default_representation = if self.semi_latus_rectum.is_some() || state.eccentricity == 1.
+        {
+            Representation::MEE
+        } else {
+            Representation::COE
+        };
+        state.into_orbit(self.representation.unwrap_or(default_representation))
+    }
+
+    fn check_no_elements(&self) -> Result<(), BuildError> {
+        let elements = [
+            self.semi_major_axis,
+            self.eccentricity,
+            self.inclination,
+            self.arg_peri,
+            self.raan,
+            self.true_anomaly,
+            self.mean_anomaly,
+            self.semi_latus_rectum,
+            self.period,
+            self.periapsis_radius,
+            self.apoapsis_radius,
+            self.periapsis_altitude,
+            self.apoapsis_altitude,
+        ];
+        if elements.iter().any(Option::is_some) {
+            Err(BuildError::Overdetermined(
+                "orbital elements given alongside position and velocity",
+            ))
+        } else {
+            Ok(())
         }
     }
+
+    /// Resolves the size, shape, orientation and anomaly from the element parameters
+    fn elements(&self, central_body: &CentralBody) -> Result<Elements, BuildError> {
+        let scalars = [
+            ("semi-major axis", self.semi_major_axis),
+            ("eccentricity", self.eccentricity),
+            ("inclination", self.inclination),
+            ("argument of periapsis", self.arg_peri),
+            ("RAAN", self.raan),
+            ("true anomaly", self.true_anomaly),
+            ("mean anomaly", self.mean_anomaly),
+            ("semi-latus rectum", self.semi_latus_rectum),
+            ("period", self.period),
+            ("periapsis radius", self.periapsis_radius),
+            ("apoapsis radius", self.apoapsis_radius),
+            ("periapsis altitude", self.periapsis_altitude),
+            ("apoapsis altitude", self.apoapsis_altitude),
+        ];
+        for (name, value) in scalars.iter() {
+            if let Some(value) = value {
+                check_finite(name, &[*value])?;
+            }
+        }
+
+        let radius = central_body.equatorial_radius;
+        let periapsis = merge(
+            self.periapsis_radius,
+            self.periapsis_altitude.map(|altitude| altitude + radius),
+            "periapsis radius and altitude",
+        )?;
+        let apoapsis = merge(
+            self.apoapsis_radius,
+            self.apoapsis_altitude.map(|altitude| altitude + radius),
+            "apoapsis radius and altitude",
+        )?;
+
+        // Eccentricity, either given or from the apsides
+        let eccentricity_from_apsides = match (periapsis, apoapsis) {
+            (Some(periapsis), Some(apoapsis)) => {
+                if periapsis <= 0. || apoapsis < periapsis {
+                    return Err(BuildError::InvalidApsides {
+                        periapsis,
+                        apoapsis,
+                    });
+                }
+                Some((apoapsis - periapsis) / (apoapsis + periapsis))
+            }
+            _ => None,
+        };
+        let eccentricity = match merge(
+            self.eccentricity,
+            eccentricity_from_apsides,
+            "eccentricity and apsides",
+        )? {
+            Some(eccentricity) => eccentricity,
+            None => self.eccentricity_from_sizes(central_body, periapsis, apoapsis)?,
+        };
+        if eccentricity < 0. {
+            return Err(BuildError::NegativeEccentricity(eccentricity));
+        }
+
+        // Semi-latus rectum from every size parameter given, which must all agree
+        let mut slr = self.semi_latus_rectum;
+        if let Some(slr) = slr {
+            if slr <= 0. {
+                return Err(BuildError::NonPositive("semi-latus rectum"));
+            }
+        }
+        if let Some(semi_major_axis) = self.semi_major_axis {
+            check_sma_sign(semi_major_axis, eccentricity)?;
+            let from_sma = semi_major_axis * (1. - eccentricity.powi(2));
+            slr = merge(slr, Some(from_sma), "semi-major axis")?;
+        }
+        if let Some(period) = self.period {
+            if period <= 0. {
+                return Err(BuildError::NonPositive("period"));
+            }
+            if eccentricity >= 1. {
+                return Err(BuildError::Inconsistent(
+                    "a period was given for an open orbit",
+                ));
+            }
+            let semi_major_axis = (central_body.mu * (period / (2. * PI)).powi(2)).cbrt();
+            let from_period = semi_major_axis * (1. - eccentricity.powi(2));
+            slr = merge(slr, Some(from_period), "period")?;
+        }
+        if let Some(periapsis) = periapsis {
+            if periapsis <= 0. {
+                return Err(BuildError::InvalidApsides {
+                    periapsis,
+                    apoapsis: apoapsis.unwrap_or(f64::NAN),
+                });
+            }
+            slr = merge(slr, Some(periapsis * (1. + eccentricity)), "periapsis")?;
+        }
+        if let Some(apoapsis) = apoapsis {
+            if eccentricity >= 1. || apoapsis <= 0. {
+                return Err(BuildError::InvalidApsides {
+                    periapsis: periapsis.unwrap_or(f64::NAN),
+                    apoapsis,
+                });
+            }
+            slr = merge(slr, Some(apoapsis * (1. - eccentricity)), "apoapsis")?;
+        }
+        let semi_latus_rectum = slr.ok_or(BuildError::NotEnoughParameters(
+            "one of semi-major axis, period, semi-latus rectum or periapsis",
+        ))?;
+
+        let inclination = self.inclination.unwrap_or(0.);
+        if !(0. ..=PI).contains(&inclination) {
+            return Err(BuildError::InclinationOutOfRange(inclination));
+        }
+
+        let true_anomaly = match (self.true_anomaly, self.mean_anomaly) {
+            (Some(_), Some(_)) => {
+                return Err(BuildError::Overdetermined(
+                    "both true and mean anomaly given",
+                ));
+            }
+            (Some(true_anomaly), None) => true_anomaly,
+            (None, Some(mean_anomaly)) => anomaly::mean_to_true(mean_anomaly, eccentricity),
+            (None, None) => 0.,
+        };
+        if eccentricity >= 1. && 1. + eccentricity * true_anomaly.cos() <= 0. {
+            return Err(BuildError::UnreachableAnomaly(true_anomaly));
+        }
+
+        Ok(Elements {
+            semi_latus_rectum,
+            eccentricity,
+            inclination,
+            arg_peri: self.arg_peri.unwrap_or(0.),
+            raan: self.raan.unwrap_or(0.),
+            true_anomaly,
+            central_body: *central_body,
+        })
+    }
+
+    /// Eccentricity from two different size parameters when it isn't given, or zero when only
+    /// one is
+    fn eccentricity_from_sizes(
+        &self,
+        central_body: &CentralBody,
+        periapsis: Option<f64>,
+        apoapsis: Option<f64>,
+    ) -> Result<f64, BuildError> {
+        let from_period = self
+            .period
+            .filter(|period| *period > 0.)
+            .map(|period| (central_body.mu * (period / (2. * PI)).powi(2)).cbrt());
+        let semi_major_axis = self.semi_major_axis.or(from_period);
+        let sizes = (semi_major_axis, self.semi_latus_rectum, periapsis, apoapsis);
+        let (eccentricity, what) = match sizes {
+            (Some(a), _, Some(rp), _) => (1. - rp / a, "semi-major axis and periapsis"),
+            (Some(a), _, None, Some(ra)) => (ra / a - 1., "semi-major axis and apoapsis"),
+            (Some(a), Some(p), None, None) => {
+                ((1. - p / a).sqrt(), "semi-major axis and semi-latus rectum")
+            }
+            (None, Some(p), Some(rp), _) => (p / rp - 1., "semi-latus rectum and periapsis"),
+            (None, Some(p), None, Some(ra)) => (1. - p / ra, "semi-latus rectum and apoapsis"),
+            _ => return Ok(0.),
+        };
+        // A negative or imaginary eccentricity means no conic has both sizes
+        if eccentricity >= 0. {
+            Ok(eccentricity)
+        } else {
+            Err(BuildError::Inconsistent(what))
+        }
+    }
+}
+
+/// Fully resolved elements, sized by the semi-latus rectum so parabolic orbits fit too
+struct Elements {
+    semi_latus_rectum: f64,
+    eccentricity: f64,
+    inclination: f64,
+    arg_peri: f64,
+    raan: f64,
+    true_anomaly: f64,
+    central_body: CentralBody,
+}
+
+impl Elements {
+    fn into_orbit(self, representation: Representation) -> Result<structs::Orbit, BuildError> {
+        let parabolic = self.eccentricity == 1.;
+        let coe = COE::new(
+            self.semi_latus_rectum / (1. - self.eccentricity.powi(2)),
+            self.eccentricity,
+            self.inclination,
+            self.arg_peri,
+            self.raan,
+            self.true_anomaly,
+        )
+        .with_central_body(self.central_body);
+        match representation {
+            Representation::COE | Representation::EOE if parabolic => {
+                Err(BuildError::Unrepresentable(representation))
+            }
+            Representation::COE => Ok(structs::Orbit::COE(coe)),
+            Representation::EOE => Ok(structs::Orbit::EOE(EOE::from(&coe))),
+            Representation::MEE | Representation::Cartesian => {
+                // Build from the semi-latus rectum directly, which also covers parabolic orbits
+                let mee = MEE {
+                    semi_latus_rectum: self.semi_latus_rectum,
+                    ..MEE::from(&COE {
+                        semi_major_axis: 1.,
+                        ..coe
+                    })
+                };
+                if representation == Representation::MEE {
+                    Ok(structs::Orbit::MEE(mee))
+                } else {
+                    Ok(structs::Orbit::Cartesian(CartesianState::from(&mee)))
+                }
+            }
+        }
+    }
+}
+
+fn convert_state(
+    state: CartesianState,
+    representation: Representation,
+) -> Result<structs::Orbit, BuildError> {
+    // Parabolic states have no semi-major axis for `COE` or `EOE`
+    let coe = || {
+        state
+            .try_to_coe()
+            .map_err(|_| BuildError::Unrepresentable(representation))
+    };
+    Ok(match representation {
+        Representation::COE => structs::Orbit::COE(coe()?),
+        Representation::EOE => structs::Orbit::EOE(EOE::from(&coe()?)),
+        Representation::MEE => structs::Orbit::MEE(MEE::from(&state)),
+        Representation::Cartesian => structs::Orbit::Cartesian(state),
+    })
+}
+
+fn check_finite(name: &'static str, values: &[f64]) -> Result<(), BuildError> {
+    if values.iter().all(|value| value.is_finite()) {
+        Ok(())
+    } else {
+        Err(BuildError::NonFinite(name))
+    }
+}
+
+fn check_sma_sign(semi_major_axis: f64, eccentricity: f64) -> Result<(), BuildError> {
+    let valid = if eccentricity < 1. {
+        semi_major_axis > 0.
+    } else {
+        // Parabolic orbits have no finite semi-major axis
+        eccentricity > 1. && semi_major_axis < 0.
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(BuildError::SemiMajorAxisSign {
+            semi_major_axis,
+            eccentricity,
+        })
+    }
+}
+
+/// Combines two optional values for the same quantity, requiring them to agree if both are given
+fn merge(a: Option<f64>, b: Option<f64>, what: &'static str) -> Result<Option<f64>, BuildError> {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            if (a - b).abs() <= CONSISTENCY_RTOL * a.abs().max(b.abs()) {
+                Ok(Some(a))
+            } else {
+                Err(BuildError::Inconsistent(what))
+            }
+        }
+        (a, b) => Ok(a.or(b)),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use crate::orbit::structs::Orbit;
+    use crate::orbit::traits::*;
+
+    fn build_coe(builder: &Builder) -> COE {
+        match builder.build() {
+            Ok(Orbit::COE(coe)) => coe,
+            other => panic!("Expected COE, got {other:?}"),
+        }
+    }
+
     #[test]
     fn from_builder_test() {
         // Make an arbitrary orbit struct that can be built with the builder
@@ -50,4 +591,329 @@ mod tests {
         let builder = ImaginaryOrbit::build();
         assert_eq!(builder.semi_major_axis, None);
     }
+
+    #[test]
+    fn empty_builder_test() {
+        assert!(matches!(
+            Builder::new().build(),
+            Err(BuildError::NotEnoughParameters(_))
+        ));
+        assert!(matches!(
+            Builder::new().eccentricity(0.1).inclination(1.).build(),
+            Err(BuildError::NotEnoughParameters(_))
+        ));
+    }
+
+    #[test]
+    fn full_elements_test() {
+        let coe = build_coe(
+            Builder::new()
+                .semi_major_axis(7_000_000.)
+                .eccentricity(0.1)
+                .inclination(1.)
+                .raan(2.)
+                .arg_peri(3.)
+                .true_anomaly(4.),
+        );
+        assert_eq!(coe, COE::new(7_000_000., 0.1, 1., 3., 2., 4.));
+    }
+
+    #[test]
+    fn apsides_test() {
+        let coe = build_coe(
+            Builder::new()
+                .periapsis_altitude(400_000.)
+                .apoapsis_altitude(4_000_000.),
+        );
+        assert_relative_eq!(coe.periapsis_altitude(), 400_000., max_relative = 1e-12);
+        assert_relative_eq!(coe.apoapsis_altitude(), 4_000_000., max_relative = 1e-12);
+
+        // A radius and an altitude for the same apsis have to agree
+        let radius = bodies::EARTH.equatorial_radius + 400_000.;
+        let mut builder = Builder::new();
+        builder
+            .periapsis_radius(radius)
+            .periapsis_altitude(400_000.)
+            .eccentricity(0.);
+        assert_eq!(build_coe(&builder).semi_major_axis, radius);
+        builder.periapsis_altitude(500_000.);
+        assert!(matches!(builder.build(), Err(BuildError::Inconsistent(_))));
+
+        assert!(matches!(
+            Builder::new()
+                .periapsis_radius(8_000_000.)
+                .apoapsis_radius(7_000_000.)
+                .build(),
+            Err(BuildError::InvalidApsides { .. })
+        ));
+    }
+
+    #[test]
+    /// Two different size parameters without an eccentricity fix it, and a single one gives a
+    /// circular orbit
+    fn size_pairs_test() {
+        let expected = COE::new(7_000e3, 0.1, 0., 0., 0., 0.);
+        let (a, p) = (expected.semi_major_axis, expected.semi_latus_rectum());
+        let (rp, ra) = (a * 0.9, a * 1.1);
+        let period = expected.period();
+        let check = |builder: &mut Builder| {
+            let coe = build_coe(builder.representation(Representation::COE));
+            assert_relative_eq!(coe.eccentricity, 0.1, max_relative = 1e-9);
+            assert_relative_eq!(coe.semi_major_axis, a, max_relative = 1e-9);
+        };
+        check(Builder::new().semi_major_axis(a).periapsis_radius(rp));
+        check(Builder::new().semi_major_axis(a).apoapsis_radius(ra));
+        check(Builder::new().semi_major_axis(a).semi_latus_rectum(p));
+        check(Builder::new().period(period).periapsis_radius(rp));
+        check(Builder::new().period(period).apoapsis_radius(ra));
+        check(Builder::new().semi_latus_rectum(p).periapsis_radius(rp));
+        check(Builder::new().semi_latus_rectum(p).apoapsis_radius(ra));
+        let coe = build_coe(
+            Builder::new()
+                .semi_major_axis(7_000e3)
+                .periapsis_radius(6_800e3),
+        );
+        assert_relative_eq!(coe.periapsis_radius(), 6_800e3, max_relative = 1e-12);
+        // A third size still has to agree
+        let mut builder = Builder::new();
+        builder
+            .semi_major_axis(a)
+            .periapsis_radius(rp)
+            .semi_latus_rectum(p);
+        assert!(builder.build().is_ok());
+        builder.semi_latus_rectum(p * 1.01);
+        assert!(matches!(builder.build(), Err(BuildError::Inconsistent(_))));
+
+        assert_eq!(
+            Builder::new()
+                .semi_major_axis(7_000e3)
+                .periapsis_radius(8_000e3)
+                .build(),
+            Err(BuildError::Inconsistent("semi-major axis and periapsis"))
+        );
+        assert_eq!(
+            Builder::new()
+                .semi_major_axis(7_000e3)
+                .semi_latus_rectum(8_000e3)
+                .build(),
+            Err(BuildError::Inconsistent(
+                "semi-major axis and semi-latus rectum"
+            ))
+        );
+        // A hyperbola from its semi-major axis and periapsis
+        let hyperbola = build_coe(
+            Builder::new()
+                .semi_major_axis(-20_000e3)
+                .periapsis_radius(7_000e3),
+        );
+        assert_relative_eq!(hyperbola.eccentricity, 1.35, max_relative = 1e-12);
+        assert_eq!(build_coe(Builder::new().period(6_000.)).eccentricity, 0.);
+    }
+
+    #[test]
+    fn period_test() {
+        let coe = build_coe(Builder::new().period(86_164.090_5).eccentricity(0.));
+        assert_relative_eq!(coe.semi_major_axis, 42_164_172., max_relative = 1e-6);
+        assert_relative_eq!(coe.period(), 86_164.090_5, max_relative = 1e-12);
+
+        // Consistent with the semi-major axis
+        let mut builder = Builder::new();
+        builder
+            .period(coe.period())
+            .semi_major_axis(coe.semi_major_axis);
+        assert!(builder.build().is_ok());
+        builder.semi_major_axis(40_000_000.);
+        assert!(matches!(builder.build(), Err(BuildError::Inconsistent(_))));
+    }
+
+    #[test]
+    fn semi_latus_rectum_test() {
+        let orbit = Builder::new()
+            .semi_latus_rectum(10_000_000.)
+            .eccentricity(1.)
+            .build()
+            .unwrap();
+        if let Orbit::MEE(mee) = orbit {
+            assert_eq!(mee.semi_latus_rectum, 10_000_000.);
+            assert_relative_eq!(mee.eccentricity(), 1., epsilon = 1e-15);
+        } else {
+            panic!("Got the wrong orbit type");
+        }
+
+        let coe = build_coe(
+            Builder::new()
+                .semi_latus_rectum(7_000_000. * 0.99)
+                .eccentricity(0.1)
+                .representation(Representation::COE),
+        );
+        assert_relative_eq!(coe.semi_major_axis, 7_000_000., max_relative = 1e-15);
+
+        assert_eq!(
+            Builder::new()
+                .semi_latus_rectum(10_000_000.)
+                .eccentricity(1.)
+                .representation(Representation::COE)
+                .build(),
+            Err(BuildError::Unrepresentable(Representation::COE))
+        );
+    }
+
+    #[test]
+    fn mean_anomaly_test() {
+        let coe = build_coe(
+            Builder::new()
+                .semi_major_axis(7_000_000.)
+                .eccentricity(0.1)
+                .mean_anomaly(1.),
+        );
+        assert_relative_eq!(coe.mean_anomaly(), 1., epsilon = 1e-14);
+        assert!(matches!(
+            Builder::new()
+                .semi_major_axis(7_000_000.)
+                .mean_anomaly(1.)
+                .true_anomaly(1.)
+                .build(),
+            Err(BuildError::Overdetermined(_))
+        ));
+    }
+
+    #[test]
+    fn state_vector_test() {
+        let coe = COE::new(7_000_000., 0.1, 1., 2., 3., 4.);
+        let state = CartesianState::from(&coe);
+        let mut builder = Builder::new();
+        builder
+            .position(state.position.clone())
+            .velocity(state.velocity.clone());
+        assert_eq!(builder.build(), Ok(Orbit::Cartesian(state.clone())));
+
+        builder.representation(Representation::COE);
+        let built = build_coe(&builder);
+        assert_relative_eq!(built.semi_major_axis, 7_000_000., max_relative = 1e-12);
+
+        builder.eccentricity(0.1);
+        assert!(matches!(
+            builder.build(),
+            Err(BuildError::Overdetermined(_))
+        ));
+
+        assert_eq!(
+            Builder::new().position(state.position).build(),
+            Err(BuildError::NotEnoughParameters("velocity"))
+        );
+        assert_eq!(
+            Builder::new()
+                .position(Vector3::new([7_000_000., 0., 0.]))
+                .velocity(Vector3::new([1_000., 0., 0.]))
+                .build(),
+            Err(BuildError::DegenerateState)
+        );
+
+        // A state at the escape speed only fits the elements sized by the semi-latus rectum
+        let mu = crate::bodies::EARTH.mu;
+        let mut builder = Builder::new();
+        builder
+            .position(Vector3::new([mu, 0., 0.]))
+            .velocity(Vector3::new([1., 1., 0.]));
+        for representation in [Representation::COE, Representation::EOE] {
+            assert_eq!(
+                builder.representation(representation).build(),
+                Err(BuildError::Unrepresentable(representation))
+            );
+        }
+        let Ok(Orbit::MEE(mee)) = builder.representation(Representation::MEE).build() else {
+            panic!("Got the wrong orbit type");
+        };
+        assert_relative_eq!(mee.semi_latus_rectum, mu, max_relative = 1e-15);
+    }
+
+    #[test]
+    fn representations_test() {
+        let mut builder = Builder::new();
+        builder
+            .semi_major_axis(7_000_000.)
+            .eccentricity(0.1)
+            .inclination(1.)
+            .raan(2.)
+            .arg_peri(3.)
+            .true_anomaly(4.);
+        let expected = builder.build().unwrap().to_cartesian();
+        for representation in [
+            Representation::COE,
+            Representation::Cartesian,
+            Representation::EOE,
+            Representation::MEE,
+        ] {
+            let state = builder
+                .representation(representation)
+                .build()
+                .unwrap()
+                .to_cartesian();
+            crate::testing::assert_array_eq_atol(
+                &state.position.elem,
+                &expected.position.elem,
+                1e-6,
+            );
+        }
+    }
+
+    #[test]
+    fn unphysical_test() {
+        assert_eq!(
+            Builder::new()
+                .semi_major_axis(7_000_000.)
+                .eccentricity(-0.1)
+                .build(),
+            Err(BuildError::NegativeEccentricity(-0.1))
+        );
+        assert!(matches!(
+            Builder::new()
+                .semi_major_axis(-7_000_000.)
+                .eccentricity(0.1)
+                .build(),
+            Err(BuildError::SemiMajorAxisSign { .. })
+        ));
+        assert!(matches!(
+            Builder::new()
+                .semi_major_axis(7_000_000.)
+                .eccentricity(1.5)
+                .build(),
+            Err(BuildError::SemiMajorAxisSign { .. })
+        ));
+        assert!(Builder::new()
+            .semi_major_axis(-7_000_000.)
+            .eccentricity(1.5)
+            .build()
+            .is_ok());
+        assert_eq!(
+            Builder::new()
+                .semi_major_axis(7_000_000.)
+                .inclination(-0.1)
+                .build(),
+            Err(BuildError::InclinationOutOfRange(-0.1))
+        );
+        assert!(matches!(
+            Builder::new()
+                .semi_major_axis(7_000_000.)
+                .inclination(4.)
+                .build(),
+            Err(BuildError::InclinationOutOfRange(_))
+        ));
+        assert_eq!(
+            Builder::new().semi_major_axis(f64::NAN).build(),
+            Err(BuildError::NonFinite("semi-major axis"))
+        );
+        assert!(matches!(
+            Builder::new()
+                .semi_major_axis(-7_000_000.)
+                .eccentricity(1.5)
+                .true_anomaly(3.)
+                .build(),
+            Err(BuildError::UnreachableAnomaly(_))
+        ));
+        assert_eq!(
+            Builder::new().period(-1.).build(),
+            Err(BuildError::NonPositive("period"))
+        );
+    }
 }
@@ -4,6 +4,11 @@ pub use traits::*;
 pub mod structs;
 pub use structs::Orbit;
 
+mod propagator;
+pub use propagator::{Keplerian, Propagator};
+
 mod builder;
+pub use builder::BuildError;
 pub use builder::Builder;
 pub use builder::FromBuilder;
+pub use builder::Representation;
@@ -0,0 +1,375 @@
+use std::f64::consts::PI;
+
+use crate::anomaly;
+use crate::orbit::structs::{CartesianState, Orbit, COE, EOE, MEE};
+use crate::orbit::traits::*;
+use crate::time::Epoch;
+
+/// Relative tolerance on the universal anomaly
+const UNIVERSAL_TOL: f64 = 1e-15;
+
+/// Iteration cap for the universal Kepler solver
+const UNIVERSAL_MAX_ITER: usize = 100;
+
+/// Order of the Laguerre-Conway iteration used to solve the universal Kepler equation
+const LAGUERRE_ORDER: f64 = 5.;
+
+pub trait Propagator {
+    /// Advances an orbit by `time_of_flight` seconds, which may be negative, returning it in the
+    /// same representation it was given in
+    fn propagate(&self, orbit: &Orbit, time_of_flight: f64) -> Orbit;
+}
+
+/// Analytic two-body propagation.
+///
+/// Element sets are advanced through their mean anomaly, which leaves every other element
+/// untouched. Cartesian states are advanced with the universal variable formulation, which covers
+/// elliptic, parabolic and hyperbolic orbits alike.
+#[derive(Clone, Debug, Default)]
+pub struct Keplerian;
+
+impl Propagator for Keplerian {
+    fn propagate(&self, orbit: &Orbit, time_of_flight: f64) -> Orbit {
+        match orbit {
+            Orbit::COE(coe) => Orbit::COE(self.propagate_coe(coe, time_of_flight)),
+            Orbit::Cartesian(state) => {
+                Orbit::Cartesian(self.propagate_cartesian(state, time_of_flight))
+            }
+            Orbit::EOE(eoe) => {
+                let longitude_of_peri = eoe.g.atan2(eoe.f);
+                let true_anomaly = eoe.true_longitude - longitude_of_peri;
+                let true_anomaly = advance_true_anomaly(
+                    eoe.central_body.mu,
+                    eoe.semi_latus_rectum(),
+                    eoe.eccentricity(),
+                    true_anomaly,
+                    time_of_flight,
+                );
+                Orbit::EOE(EOE {
+                    true_longitude: longitude_of_peri + true_anomaly,
+                    epoch: advance_epoch(eoe.epoch, time_of_flight),
+                    ..eoe.clone()
+                })
+            }
+            Orbit::MEE(mee) => {
+                let longitude_of_peri = mee.g.atan2(mee.f);
+                let true_anomaly = mee.true_longitude - longitude_of_peri;
+                let true_anomaly = advance_true_anomaly(
+                    mee.central_body.mu,
+                    mee.semi_latus_rectum,
+                    mee.eccentricity(),
+                    true_anomaly,
+                    time_of_flight,
+                );
+                Orbit::MEE(MEE {
+                    true_longitude: longitude_of_peri + true_anomaly,
+                    epoch: advance_epoch(mee.epoch, time_of_flight),
+                    ..mee.clone()
+                })
+            }
+        }
+    }
+}
+
+impl Keplerian {
+    pub fn propagate_coe(&self, coe: &COE, time_of_flight: f64) -> COE {
+        COE {
+            true_anomaly: advance_true_anomaly(
+                coe.central_body.mu,
+                coe.semi_latus_rectum(),
+                coe.eccentricity,
+                coe.true_anomaly,
+                time_of_flight,
+            ),
+            epoch: advance_epoch(coe.epoch, time_of_flight),
+            ..coe.clone()
+        }
+    }
+
+    /// Universal variable propagation (Vallado, algorithm 8) with a Laguerre-Conway solver
+    pub fn propagate_cartesian(
+        &self,
+        state: &CartesianState,
+        time_of_flight: f64,
+    ) -> CartesianState {
+        let mu = state.central_body.mu;
+        let sqrt_mu = mu.sqrt();
+        let r0_vec = &state.position;
+        let v0_vec = &state.velocity;
+        let r0 = r0_vec.norm();
+        let sigma0 = r0_vec.dot(v0_vec) / sqrt_mu;
+        let alpha = 2. / r0 - v0_vec.dot(v0_vec) / mu;
+
+        // Whole revolutions of a closed orbit don't change the state
+        let mut dt = time_of_flight;
+        if alpha > 0. {
+            let period = 2. * PI / (sqrt_mu * alpha.powf(1.5));
+            dt %= period;
+        }
+
+        let chi = solve_universal_kepler(r0, sigma0, alpha, sqrt_mu * dt);
+        let chi_sq = chi.powi(2);
+        let z = alpha * chi_sq;
+        let (c2, c3) = (stumpff_c2(z), stumpff_c3(z));
+
+        let f = 1. - chi_sq / r0 * c2;
+        let g = dt - chi_sq * chi / sqrt_mu * c3;
+        let position = r0_vec.clone() * f + v0_vec.clone() * g;
+        let r = position.norm();
+        let f_dot = sqrt_mu / (r * r0) * chi * (z * c3 - 1.);
+        let g_dot = 1. - chi_sq / r * c2;
+        let velocity = r0_vec.clone() * f_dot + v0_vec.clone() * g_dot;
+
+        CartesianState {
+            position,
+            velocity,
+            central_body: state.central_body,
+            epoch: advance_epoch(state.epoch, time_of_flight),
+        }
+    }
+}
+
+fn advance_epoch(epoch: Option<Epoch>, time_of_flight: f64) -> Option<Epoch> {
+    epoch.map(|epoch| epoch + time_of_flight)
+}
+
+/// Mean motion in the sense of Barker's equation for parabolic orbits, so that the mean anomaly
+/// always advances as `n * t`
+fn mean_motion(mu: f64, semi_latus_rectum: f64, eccentricity: f64) -> f64 {
+    if eccentricity == 1. {
+        2. * (mu / semi_latus_rectum.powi(3)).sqrt()
+    } else {
+        let semi_major_axis = semi_latus_rectum / (1. - eccentricity.powi(2));
+        (mu / semi_major_axis.abs().powi(3)).sqrt()
+    }
+}
+
+fn advance_true_anomaly(
+    mu: f64,
+    semi_latus_rectum: f64,
+    eccentricity: f64,
+    true_anomaly: f64,
+    time_of_flight: f64,
+) -> f64 {
+    let mean_anomaly = anomaly::true_to_mean(true_anomaly, eccentricity)
+        + mean_motion(mu, semi_latus_rectum, eccentricity) * time_of_flight;
+    anomaly::mean_to_true(mean_anomaly, eccentricity)
+}
+
+/// Solves the universal Kepler equation for the universal anomaly `chi`, given the scaled time
+/// `sqrt(mu) * dt`
+fn solve_universal_kepler(r0: f64, sigma0: f64, alpha: f64, scaled_dt: f64) -> f64 {
+    let n = LAGUERRE_ORDER;
+    let mut chi = if alpha.abs() > f64::EPSILON / r0 {
+        // Closed orbits start from the mean motion, open ones from the asymptotic speed
+        if alpha > 0. {
+            scaled_dt * alpha
+        } else {
+            let a = 1. / alpha;
+            let sign = scaled_dt.signum();
+            let numerator = -2. * alpha * scaled_dt;
+            let denominator = sigma0 + sign * (-a).sqrt() * (1. - r0 * alpha);
+            sign * (-a).sqrt() * (numerator / denominator).ln()
+        }
+    } else {
+        scaled_dt / r0
+    };
+    if !chi.is_finite() {
+        chi = scaled_dt / r0;
+    }
+
+    for _ in 0..UNIVERSAL_MAX_ITER {
+        let chi_sq = chi.powi(2);
+        let z = alpha * chi_sq;
+        let (c2, c3) = (stumpff_c2(z), stumpff_c3(z));
+        let f = sigma0 * chi_sq * c2 + (1. - alpha * r0) * chi_sq * chi * c3 + r0 * chi - scaled_dt;
+        let df = sigma0 * chi * (1. - z * c3) + (1. - alpha * r0) * chi_sq * c2 + r0;
+        let d2f = sigma0 * (1. - z * c2) + (1. - alpha * r0) * chi * (1. - z * c3);
+
+        let discriminant = ((n - 1.).powi(2) * df.powi(2) - n * (n - 1.) * f * d2f).abs();
+        let step = n * f / (df + df.signum() * discriminant.sqrt());
+        chi -= step;
+        if step.abs() <= UNIVERSAL_TOL * chi.abs().max(1.) {
+            break;
+        }
+    }
+    chi
+}
+
+/// Stumpff function `c2(z) = (1 - cos(sqrt(z))) / z`, continued to negative `z`
+pub fn stumpff_c2(z: f64) -> f64 {
+    if z > 1e-3 {
+        (1. - z.sqrt().cos()) / z
+    } else if z < -1e-3 {
+        ((-z).sqrt().cosh() - 1.) / -z
+    } else {
+        1. / 2. - z / 24. + z.powi(2) / 720. - z.powi(3) / 40_320.
+    }
+}
+
+/// Stumpff function `c3(z) = (sqrt(z) - sin(sqrt(z))) / sqrt(z)^3`, continued to negative `z`
+pub fn stumpff_c3(z: f64) -> f64 {
+    if z > 1e-3 {
+        let sqrt_z = z.sqrt();
+        (sqrt_z - sqrt_z.sin()) / sqrt_z.powi(3)
+    } else if z < -1e-3 {
+        let sqrt_neg_z = (-z).sqrt();
+        (sqrt_neg_z.sinh() - sqrt_neg_z) / sqrt_neg_z.powi(3)
+    } else {
+        1. / 6. - z / 120. + z.powi(2) / 5_040. - z.powi(3) / 362_880.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::angle_ops;
+    use crate::bodies;
+    use crate::orbit::Builder;
+    use crate::testing;
+    use crate::vector::Vector3;
+
+    fn assert_state_eq(a: &CartesianState, b: &CartesianState, rtol: f64) {
+        let r_scale = a.position.norm();
+        let v_scale = a.velocity.norm();
+        testing::assert_array_eq_atol(&a.position.elem, &b.position.elem, rtol * r_scale);
+        testing::assert_array_eq_atol(&a.velocity.elem, &b.velocity.elem, rtol * v_scale);
+    }
+
+    #[test]
+    fn test_stumpff_continuity() {
+        for &z in &[-1e-3, 1e-3] {
+            let below = z * (1. - 1e-9);
+            let above = z * (1. + 1e-9);
+            assert_relative_eq!(stumpff_c2(below), stumpff_c2(above), max_relative = 1e-12);
+            assert_relative_eq!(stumpff_c3(below), stumpff_c3(above), max_relative = 1e-12);
+        }
+        assert_eq!(stumpff_c2(0.), 0.5);
+        assert_eq!(stumpff_c3(0.), 1. / 6.);
+    }
+
+    #[test]
+    /// Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., example 2-4
+    fn test_universal_vallado() {
+        let state = CartesianState::new(
+            Vector3::new([1_131_340., -2_282_343., 6_672_423.]),
+            Vector3::new([-5_643.05, 4_303.33, 2_428.79]),
+        );
+        let propagated = Keplerian.propagate_cartesian(&state, 40. * 60.);
+        testing::assert_array_eq_atol(
+            &propagated.position.elem,
+            &[-4_219_752.7, 4_363_029.2, -3_958_766.6],
+            1e1,
+        );
+        testing::assert_array_eq_atol(
+            &propagated.velocity.elem,
+            &[3_689.866, -1_916.735, -6_112.511],
+            1e-2,
+        );
+    }
+
+    #[test]
+    /// The element and universal variable propagators agree for every conic
+    fn test_elements_match_universal() {
+        let coes = [
+            COE::new(7_000_000., 0.01, 1., 2., 3., 4.),
+            COE::new(26_600_000., 0.74, 1.1, 4.7, 0.5, 0.),
+            COE::new(-20_000_000., 1.5, 0.5, 1., 2., -0.3),
+        ];
+        for coe in coes.iter() {
+            for &dt in &[-5_000., 1., 3_600., 86_400.] {
+                let from_elements = CartesianState::from(&Keplerian.propagate_coe(coe, dt));
+                let from_state = Keplerian.propagate_cartesian(&coe.into(), dt);
+                assert_state_eq(&from_elements, &from_state, 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    /// A parabolic orbit can only be held as modified equinoctial elements or a state vector
+    fn test_parabolic() {
+        let mee = MEE::new(10_000_000., 0.6, 0.8, 0.1, 0.2, 0.5);
+        let orbit = Orbit::MEE(mee);
+        for &dt in &[-3_600., 600., 86_400.] {
+            let from_elements = Keplerian.propagate(&orbit, dt).to_cartesian();
+            let from_state = Keplerian.propagate_cartesian(&orbit.to_cartesian(), dt);
+            assert_state_eq(&from_elements, &from_state, 1e-9);
+        }
+    }
+
+    #[test]
+    /// Propagating by a whole number of periods returns the starting orbit
+    fn test_period_round_trip() {
+        let coe = COE::new(7_000_000., 0.1, 1., 2., 3., 4.);
+        let period = coe.period();
+
+        let propagated = Keplerian.propagate_coe(&coe, 3. * period);
+        let diff = angle_ops::wrap_negpi_pi(propagated.true_anomaly - coe.true_anomaly);
+        assert_relative_eq!(diff, 0., epsilon = 1e-12);
+
+        let state = CartesianState::from(&coe);
+        let propagated = Keplerian.propagate_cartesian(&state, 3. * period);
+        assert_state_eq(&propagated, &state, 1e-12);
+    }
+
+    #[test]
+    /// Lunar orbits go around in a lunar period
+    fn test_period_round_trip_moon() {
+        let coe = COE::new(2_000_000., 0.05, 1., 2., 3., 4.).with_central_body(bodies::MOON);
+        let period = coe.period();
+        let state = CartesianState::from(&coe);
+        let propagated = Keplerian.propagate_cartesian(&state, period);
+        assert_state_eq(&propagated, &state, 1e-12);
+        let propagated = Keplerian.propagate_cartesian(&state, period / 2.);
+        assert!(propagated.position.dot(&state.position) < 0.);
+    }
+
+    #[test]
+    /// Every representation comes back out the way it went in
+    fn test_preserves_representation() {
+        let coe = COE::new(42_164_000., 0., 0., 0., 0., 1.);
+        let orbits = [
+            Orbit::COE(coe.clone()),
+            Orbit::Cartesian((&coe).into()),
+            Orbit::EOE((&coe).into()),
+            Orbit::MEE((&coe).into()),
+        ];
+        let expected = Keplerian.propagate_coe(&coe, 3_600.);
+        for orbit in orbits.iter() {
+            let propagated = Keplerian.propagate(orbit, 3_600.);
+            assert_eq!(
+                std::mem::discriminant(&propagated),
+                std::mem::discriminant(orbit)
+            );
+            assert_state_eq(&propagated.to_cartesian(), &(&expected).into(), 1e-12);
+        }
+    }
+
+    #[test]
+    /// Orbits with an epoch carry it through every representation, advanced by the time of flight
+    fn test_advances_epoch() {
+        let epoch: Epoch = "2024-03-20T03:06:00Z".parse().unwrap();
+        let orbit = Builder::new()
+            .semi_major_axis(7_000_000.)
+            .eccentricity(0.01)
+            .epoch(epoch)
+            .build()
+            .unwrap();
+        assert_eq!(orbit.epoch(), Some(epoch));
+        let coe = orbit.to_coe();
+        let orbits = [
+            orbit.clone(),
+            Orbit::Cartesian(orbit.to_cartesian()),
+            Orbit::EOE((&coe).into()),
+            Orbit::MEE((&coe).into()),
+        ];
+        for orbit in orbits.iter() {
+            assert_eq!(orbit.epoch(), Some(epoch));
+            let propagated = Keplerian.propagate(orbit, -90.);
+            assert_eq!(propagated.epoch(), Some(epoch - 90.));
+        }
+        let unknown = Orbit::COE(COE::new(7_000_000., 0.01, 0., 0., 0., 0.));
+        assert_eq!(Keplerian.propagate(&unknown, 60.).epoch(), None);
+    }
+}
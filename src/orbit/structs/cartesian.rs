@@ -0,0 +1,423 @@
+use std::f64::consts::PI;
+
+use crate::angle_ops;
+use crate::bodies::{self, CentralBody};
+use crate::constants;
+use crate::orbit::structs::{CIRCULAR_TOL, COE, EQUATORIAL_TOL};
+use crate::orbit::traits::*;
+use crate::orbit::{BuildError, Representation};
+use crate::time::Epoch;
+use crate::vector::Vector3;
+
+/// Inertial position (m) and velocity (m/s) of an orbiting body
+#[derive(Clone, Debug, PartialEq)]
+pub struct CartesianState {
+    pub position: Vector3,
+    pub velocity: Vector3,
+    pub central_body: CentralBody,
+    /// Instant the state is at, if known
+    pub epoch: Option<Epoch>,
+}
+
+impl CartesianState {
+    /// State of an orbit about the Earth, see `with_central_body` for other bodies
+    pub fn new(position: Vector3, velocity: Vector3) -> Self {
+        Self {
+            position,
+            velocity,
+            central_body: bodies::EARTH,
+            epoch: None,
+        }
+    }
+
+    pub fn with_central_body(self, central_body: CentralBody) -> Self {
+        Self {
+            central_body,
+            ..self
+        }
+    }
+
+    pub fn with_epoch(self, epoch: Epoch) -> Self {
+        Self {
+            epoch: Some(epoch),
+            ..self
+        }
+    }
+
+    /// Classical elements of the state, or `Unrepresentable` for a parabolic orbit, which has no
+    /// finite semi-major axis for `COE` to hold
+    pub fn try_to_coe(&self) -> Result<COE, BuildError> {
+        let coe = COE::from(self);
+        if coe.semi_major_axis.is_finite() {
+            Ok(coe)
+        } else {
+            Err(BuildError::Unrepresentable(Representation::COE))
+        }
+    }
+
+    /// Specific angular momentum vector
+    fn angular_momentum(&self) -> Vector3 {
+        self.position.cross(&self.velocity)
+    }
+
+    /// Eccentricity vector, pointing from the focus towards periapsis
+    fn eccentricity_vector(&self) -> Vector3 {
+        let mu = self.central_body.mu;
+        let r = self.position.norm();
+        let v_sq = self.velocity.dot(&self.velocity);
+        let r_dot_v = self.position.dot(&self.velocity);
+        (self.position.clone() * (v_sq - mu / r) - self.velocity.clone() * r_dot_v) * (1. / mu)
+    }
+}
+
+/// Angle swept from `from` to `to`, measured positive about `axis`, on the domain [0, 2pi)
+fn angle_about(from: &Vector3, to: &Vector3, axis: &Vector3) -> f64 {
+    let sin_angle = axis.dot(&from.cross(to));
+    let cos_angle = from.dot(to);
+    angle_ops::wrap_0_2pi(f64::atan2(sin_angle, cos_angle))
+}
+
+impl From<&COE> for CartesianState {
+    fn from(coe: &COE) -> Self {
+        let mu = coe.central_body.mu;
+        let p = coe.semi_latus_rectum();
+        let e = coe.eccentricity;
+        let (sin_nu, cos_nu) = coe.true_anomaly.sin_cos();
+
+        // Perifocal frame: x towards periapsis, z along the angular momentum
+        let r = p / (1. + e * cos_nu);
+        let position_pqw = [r * cos_nu, r * sin_nu, 0.];
+        let speed_scale = (mu / p).sqrt();
+        let velocity_pqw = [-speed_scale * sin_nu, speed_scale * (e + cos_nu), 0.];
+
+        // Rotate perifocal -> inertial through R3(-raan) R1(-inc) R3(-arg_peri)
+        let (sin_raan, cos_raan) = coe.raan.sin_cos();
+        let (sin_inc, cos_inc) = coe.inclination.sin_cos();
+        let (sin_argp, cos_argp) = coe.arg_peri.sin_cos();
+        let p_hat = [
+            cos_raan * cos_argp - sin_raan * sin_argp * cos_inc,
+            sin_raan * cos_argp + cos_raan * sin_argp * cos_inc,
+            sin_argp * sin_inc,
+        ];
+        let q_hat = [
+            -cos_raan * sin_argp - sin_raan * cos_argp * cos_inc,
+            -sin_raan * sin_argp + cos_raan * cos_argp * cos_inc,
+            cos_argp * sin_inc,
+        ];
+        let to_inertial =
+            |pqw: [f64; 3]| Vector3::new([0, 1, 2].map(|i| p_hat[i] * pqw[0] + q_hat[i] * pqw[1]));
+
+        Self {
+            position: to_inertial(position_pqw),
+            velocity: to_inertial(velocity_pqw),
+            central_body: coe.central_body,
+            epoch: coe.epoch,
+        }
+    }
+}
+
+impl From<&CartesianState> for COE {
+    /// Recovers the classical elements from a state vector.
+    ///
+    /// For circular orbits `arg_peri` is set to zero and `true_anomaly` holds the argument of
+    /// latitude. For equatorial orbits `raan` is set to zero and `arg_peri` holds the longitude of
+    /// periapsis. For circular equatorial orbits both are zero and `true_anomaly` holds the true
+    /// longitude.
+    ///
+    /// A parabolic state gets an infinite semi-major axis, which leaves the elements without a
+    /// size; see `try_to_coe` to catch this, or `MEE` to keep the orbit whole.
+    fn from(state: &CartesianState) -> Self {
+        let mu = state.central_body.mu;
+        let r = state.position.norm();
+        let v_sq = state.velocity.dot(&state.velocity);
+
+        let h_vec = state.angular_momentum();
+        let h = h_vec.norm();
+        let mut h_hat = h_vec.clone();
+        h_hat.safe_normalize();
+
+        let e_vec = state.eccentricity_vector();
+        let eccentricity = e_vec.norm();
+        let energy = v_sq / 2. - mu / r;
+        // Unsigned, rather than taking whichever sign the zero energy was rounded to
+        let semi_major_axis = if energy == 0. || eccentricity == 1. {
+            f64::INFINITY
+        } else {
+            -mu / (2. * energy)
+        };
+        let inclination = f64::acos((h_vec.elem[2] / h).clamp(-1., 1.));
+
+        // Line of nodes, replaced by the inertial x axis when it is undefined
+        let node_vec = Vector3::from(constants::Z_AXIS).cross(&h_vec);
+        let equatorial = node_vec.norm() / h < EQUATORIAL_TOL;
+        let node_hat = if equatorial {
+            Vector3::from(constants::X_AXIS)
+        } else {
+            let mut node_hat = node_vec;
+            node_hat.safe_normalize();
+            node_hat
+        };
+        let raan = if equatorial {
+            0.
+        } else {
+            angle_ops::wrap_0_2pi(f64::atan2(node_hat.elem[1], node_hat.elem[0]))
+        };
+
+        // Periapsis direction, replaced by the line of nodes when it is undefined
+        let circular = eccentricity < CIRCULAR_TOL;
+        let peri_hat = if circular {
+            node_hat.clone()
+        } else {
+            let mut peri_hat = e_vec;
+            peri_hat.safe_normalize();
+            peri_hat
+        };
+        let arg_peri = if circular {
+            0.
+        } else {
+            angle_about(&node_hat, &peri_hat, &h_hat)
+        };
+
+        let mut r_hat = state.position.clone();
+        r_hat.safe_normalize();
+        let true_anomaly = angle_about(&peri_hat, &r_hat, &h_hat);
+
+        COE {
+            semi_major_axis,
+            eccentricity,
+            inclination: inclination.clamp(0., PI),
+            arg_peri,
+            raan,
+            true_anomaly,
+            central_body: state.central_body,
+            epoch: state.epoch,
+        }
+    }
+}
+
+impl Scaled for CartesianState {
+    fn central_body(&self) -> &CentralBody {
+        &self.central_body
+    }
+
+    fn semi_major_axis(&self) -> f64 {
+        COE::from(self).semi_major_axis()
+    }
+
+    fn semi_latus_rectum(&self) -> f64 {
+        let h = self.angular_momentum().norm();
+        h.powi(2) / self.central_body.mu
+    }
+}
+
+impl Shaped for CartesianState {
+    fn eccentricity(&self) -> f64 {
+        self.eccentricity_vector().norm()
+    }
+}
+
+impl Oriented for CartesianState {
+    fn inclination(&self) -> f64 {
+        COE::from(self).inclination()
+    }
+
+    fn arg_peri(&self) -> f64 {
+        COE::from(self).arg_peri()
+    }
+
+    fn raan(&self) -> f64 {
+        COE::from(self).raan()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing;
+
+    /// Asserts two sets of elements match, comparing angles modulo 2pi
+    fn assert_coe_eq(a: &COE, b: &COE, rtol: f64, atol_angle: f64) {
+        assert_relative_eq!(a.semi_major_axis, b.semi_major_axis, max_relative = rtol);
+        assert_relative_eq!(a.eccentricity, b.eccentricity, epsilon = rtol);
+        let angles_a = [a.inclination, a.arg_peri, a.raan, a.true_anomaly];
+        let angles_b = [b.inclination, b.arg_peri, b.raan, b.true_anomaly];
+        for (angle_a, angle_b) in angles_a.iter().zip(angles_b.iter()) {
+            let diff = angle_ops::wrap_negpi_pi(angle_a - angle_b);
+            assert!(diff.abs() < atol_angle, "left = {a:?}\nright = {b:?}");
+        }
+    }
+
+    fn assert_state_eq(a: &CartesianState, b: &CartesianState) {
+        let r_scale = a.position.norm();
+        let v_scale = a.velocity.norm();
+        testing::assert_array_eq_atol(&a.position.elem, &b.position.elem, 1e-12 * r_scale);
+        testing::assert_array_eq_atol(&a.velocity.elem, &b.velocity.elem, 1e-12 * v_scale);
+    }
+
+    #[test]
+    /// Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., example 2-5
+    fn test_rv_to_coe_vallado() {
+        let state = CartesianState::new(
+            Vector3::new([6_524_834., 6_862_875., 6_448_296.]),
+            Vector3::new([4_901.327, 5_533.756, -1_976.341]),
+        );
+        let coe = COE::from(&state);
+        assert_relative_eq!(coe.semi_major_axis, 36_127_343., max_relative = 1e-6);
+        assert_relative_eq!(coe.eccentricity, 0.832_853, epsilon = 1e-6);
+        assert_relative_eq!(coe.inclination.to_degrees(), 87.870, epsilon = 1e-3);
+        assert_relative_eq!(coe.raan.to_degrees(), 227.898, epsilon = 1e-3);
+        assert_relative_eq!(coe.arg_peri.to_degrees(), 53.38, epsilon = 1e-2);
+        assert_relative_eq!(coe.true_anomaly.to_degrees(), 92.335, epsilon = 1e-3);
+    }
+
+    #[test]
+    /// Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., example 2-6
+    fn test_coe_to_rv_vallado() {
+        let coe = COE::new(
+            36_127_343.,
+            0.832_853,
+            87.87_f64.to_radians(),
+            53.38_f64.to_radians(),
+            227.89_f64.to_radians(),
+            92.335_f64.to_radians(),
+        );
+        let state = CartesianState::from(&coe);
+        testing::assert_array_eq_atol(
+            &state.position.elem,
+            &[6_525_344., 6_861_535., 6_449_125.],
+            2e3,
+        );
+        testing::assert_array_eq_atol(
+            &state.velocity.elem,
+            &[4_902.276, 5_533.124, -1_975.709],
+            2.,
+        );
+    }
+
+    #[test]
+    fn test_round_trip_general() {
+        let coe = COE::new(7_000_000., 0.1, 1., 2., 3., 4.);
+        let state = CartesianState::from(&coe);
+        let coe_round_trip = COE::from(&state);
+        assert_coe_eq(&coe, &coe_round_trip, 1e-12, 1e-12);
+        assert_state_eq(&state, &CartesianState::from(&coe_round_trip));
+    }
+
+    #[test]
+    fn test_round_trip_hyperbolic() {
+        let coe = COE::new(-20_000_000., 1.5, 0.5, 1., 2., 0.3);
+        let state = CartesianState::from(&coe);
+        let coe_round_trip = COE::from(&state);
+        assert_coe_eq(&coe, &coe_round_trip, 1e-12, 1e-12);
+    }
+
+    #[test]
+    /// Circular orbits fold the argument of periapsis into the true anomaly
+    fn test_circular() {
+        let coe = COE::new(7_000_000., 0., 1., 2., 3., 0.5);
+        let state = CartesianState::from(&coe);
+        let coe_round_trip = COE::from(&state);
+        let expected = COE::new(7_000_000., 0., 1., 0., 3., 2.5);
+        assert_coe_eq(&coe_round_trip, &expected, 1e-12, 1e-12);
+        assert_state_eq(&state, &CartesianState::from(&coe_round_trip));
+    }
+
+    #[test]
+    /// Equatorial orbits fold the RAAN into the argument of periapsis
+    fn test_equatorial() {
+        let coe = COE::new(7_000_000., 0.2, 0., 2., 3., 0.5);
+        let state = CartesianState::from(&coe);
+        let coe_round_trip = COE::from(&state);
+        let expected = COE::new(7_000_000., 0.2, 0., 5., 0., 0.5);
+        assert_coe_eq(&coe_round_trip, &expected, 1e-12, 1e-12);
+        assert_state_eq(&state, &CartesianState::from(&coe_round_trip));
+    }
+
+    #[test]
+    /// Retrograde equatorial orbits measure the longitude of periapsis in the direction of motion
+    fn test_retrograde_equatorial() {
+        let coe = COE::new(7_000_000., 0.2, PI, 2., 3., 0.5);
+        let state = CartesianState::from(&coe);
+        let coe_round_trip = COE::from(&state);
+        assert_relative_eq!(coe_round_trip.inclination, PI);
+        assert_eq!(coe_round_trip.raan, 0.);
+        assert_state_eq(&state, &CartesianState::from(&coe_round_trip));
+    }
+
+    #[test]
+    /// Circular equatorial orbits fold everything into the true longitude
+    fn test_circular_equatorial() {
+        let coe = COE::new(42_164_000., 0., 0., 2., 3., 0.5);
+        let state = CartesianState::from(&coe);
+        let coe_round_trip = COE::from(&state);
+        let expected = COE::new(42_164_000., 0., 0., 0., 0., 5.5);
+        assert_coe_eq(&coe_round_trip, &expected, 1e-12, 1e-12);
+        assert_state_eq(&state, &CartesianState::from(&coe_round_trip));
+    }
+
+    #[test]
+    /// Conversions use, and carry through, the gravitational parameter of the central body
+    fn test_round_trip_mars() {
+        let coe = COE::new(4_000_000., 0.1, 1., 2., 3., 4.).with_central_body(bodies::MARS);
+        let state = CartesianState::from(&coe);
+        assert_eq!(state.central_body, bodies::MARS);
+        let speed = state.velocity.norm();
+        let r = state.position.norm();
+        let expected_speed = (bodies::MARS.mu * (2. / r - 1. / coe.semi_major_axis)).sqrt();
+        assert_relative_eq!(speed, expected_speed, max_relative = 1e-12);
+
+        let coe_round_trip = COE::from(&state);
+        assert_coe_eq(&coe, &coe_round_trip, 1e-12, 1e-12);
+        assert_eq!(coe_round_trip.central_body, bodies::MARS);
+    }
+
+    #[test]
+    fn test_traits_match_coe() {
+        let coe = COE::new(7_000_000., 0.1, 1., 2., 3., 4.);
+        let state = CartesianState::from(&coe);
+        assert_relative_eq!(
+            state.semi_major_axis(),
+            coe.semi_major_axis(),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            state.semi_latus_rectum(),
+            coe.semi_latus_rectum(),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(state.eccentricity(), coe.eccentricity(), epsilon = 1e-12);
+        assert_relative_eq!(state.inclination(), coe.inclination(), epsilon = 1e-12);
+        assert_relative_eq!(state.arg_peri(), coe.arg_peri(), epsilon = 1e-12);
+        assert_relative_eq!(state.raan(), coe.raan(), epsilon = 1e-12);
+    }
+
+    #[test]
+    /// A state with exactly the escape speed has an infinite semi-major axis rather than a NaN
+    /// one, and can't be held as classical elements
+    fn test_parabolic() {
+        let central_body = bodies::EARTH;
+        let state = CartesianState::new(
+            Vector3::new([central_body.mu, 0., 0.]),
+            Vector3::new([1., 1., 0.]),
+        );
+        let coe = COE::from(&state);
+        assert_eq!(coe.eccentricity, 1.);
+        assert_eq!(coe.semi_major_axis, f64::INFINITY);
+        assert!([coe.inclination, coe.arg_peri, coe.raan, coe.true_anomaly]
+            .iter()
+            .all(|angle| angle.is_finite()));
+        assert_relative_eq!(
+            state.semi_latus_rectum(),
+            central_body.mu,
+            max_relative = 1e-15
+        );
+        assert_eq!(
+            state.try_to_coe(),
+            Err(BuildError::Unrepresentable(Representation::COE))
+        );
+
+        let elliptic = CartesianState::from(&COE::new(7_000_000., 0.1, 1., 2., 3., 4.));
+        assert!(elliptic.try_to_coe().is_ok());
+    }
+}
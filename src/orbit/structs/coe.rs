@@ -1,11 +1,13 @@
-use crate::constants;
+use crate::anomaly;
+use crate::bodies::{self, CentralBody};
 use crate::orbit::traits::*;
+use crate::time::Epoch;
 
 pub use coe_canonical::COE;
 mod coe_canonical {
     use super::*;
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Clone, Debug, PartialEq)]
     pub struct COE {
         pub semi_major_axis: f64,
         pub eccentricity: f64,
@@ -13,9 +15,13 @@ mod coe_canonical {
         pub arg_peri: f64,
         pub raan: f64,
         pub true_anomaly: f64,
+        pub central_body: CentralBody,
+        /// Instant the orbit is at, if known
+        pub epoch: Option<Epoch>,
     }
 
     impl COE {
+        /// Elements of an orbit about the Earth, see `with_central_body` for other bodies
         pub fn new(
             semi_major_axis: f64,
             eccentricity: f64,
@@ -31,11 +37,44 @@ mod coe_canonical {
                 arg_peri,
                 raan,
                 true_anomaly,
+                central_body: bodies::EARTH,
+                epoch: None,
+            }
+        }
+
+        pub fn with_central_body(self, central_body: CentralBody) -> Self {
+            Self {
+                central_body,
+                ..self
+            }
+        }
+
+        pub fn with_epoch(self, epoch: Epoch) -> Self {
+            Self {
+                epoch: Some(epoch),
+                ..self
+            }
+        }
+
+        /// Mean anomaly, or the equivalent hyperbolic/parabolic quantity for open orbits
+        pub fn mean_anomaly(&self) -> f64 {
+            anomaly::true_to_mean(self.true_anomaly, self.eccentricity)
+        }
+
+        /// Returns a copy of these elements placed at the given mean anomaly
+        pub fn with_mean_anomaly(&self, mean_anomaly: f64) -> Self {
+            Self {
+                true_anomaly: anomaly::mean_to_true(mean_anomaly, self.eccentricity),
+                ..self.clone()
             }
         }
     }
 
-    impl Sized for COE {
+    impl Scaled for COE {
+        fn central_body(&self) -> &CentralBody {
+            &self.central_body
+        }
+
         fn semi_major_axis(&self) -> f64 {
             self.semi_major_axis
         }
@@ -43,15 +82,6 @@ mod coe_canonical {
         fn semi_latus_rectum(&self) -> f64 {
             self.semi_major_axis * (1. - self.eccentricity.powi(2))
         }
-
-        fn period(&self) -> f64 {
-            let semi_major_axis = self.semi_major_axis();
-            if semi_major_axis > 0. {
-                (constants::MU_EARTH / self.semi_major_axis().powi(3)).sqrt()
-            } else {
-                0.
-            }
-        }
     }
 
     impl Shaped for COE {
@@ -89,6 +119,8 @@ mod coe_canonical {
                 arg_peri: 2.,
                 raan: 3.,
                 true_anomaly: 4.,
+                central_body: bodies::EARTH,
+                epoch: None,
             };
             assert_eq!(coe.semi_major_axis(), coe.semi_major_axis);
             assert_eq!(coe.inclination(), coe.inclination);
@@ -97,12 +129,20 @@ mod coe_canonical {
             assert_eq!(coe.raan(), coe.raan);
         }
 
+        #[test]
+        fn mean_anomaly_test() {
+            let coe = COE::new(42_164_000., 0.1, 1., 2., 3., 4.);
+            let moved = coe.with_mean_anomaly(coe.mean_anomaly());
+            assert_relative_eq!(moved.true_anomaly, coe.true_anomaly, epsilon = 1e-14);
+            assert_eq!(moved.semi_major_axis, coe.semi_major_axis);
+        }
+
         #[test]
         fn builder_test() {
             let sma = 42_164_000.;
             let mut builder = Builder::new();
             builder.semi_major_axis(sma);
-            let orbit = builder.build();
+            let orbit = builder.build().unwrap();
             if let Orbit::COE(coe) = orbit {
                 assert_eq!(coe.semi_major_axis, sma);
             } else {
@@ -116,7 +156,7 @@ pub use coe_slr::COESlr;
 mod coe_slr {
     use super::*;
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Clone, Debug, PartialEq)]
     pub struct COESlr {
         pub semi_latus_rectum: f64,
         pub eccentricity: f64,
@@ -124,9 +164,48 @@ mod coe_slr {
         pub arg_peri: f64,
         pub raan: f64,
         pub true_anomaly: f64,
+        pub central_body: CentralBody,
+        /// Instant the orbit is at, if known
+        pub epoch: Option<Epoch>,
+    }
+
+    impl From<&COE> for COESlr {
+        fn from(coe: &COE) -> Self {
+            Self {
+                semi_latus_rectum: coe.semi_latus_rectum(),
+                eccentricity: coe.eccentricity,
+                inclination: coe.inclination,
+                arg_peri: coe.arg_peri,
+                raan: coe.raan,
+                true_anomaly: coe.true_anomaly,
+                central_body: coe.central_body,
+                epoch: coe.epoch,
+            }
+        }
     }
 
-    impl Sized for COESlr {
+    impl From<&COESlr> for COE {
+        fn from(coe: &COESlr) -> Self {
+            COE {
+                epoch: coe.epoch,
+                ..COE::new(
+                    coe.semi_major_axis(),
+                    coe.eccentricity,
+                    coe.inclination,
+                    coe.arg_peri,
+                    coe.raan,
+                    coe.true_anomaly,
+                )
+                .with_central_body(coe.central_body)
+            }
+        }
+    }
+
+    impl Scaled for COESlr {
+        fn central_body(&self) -> &CentralBody {
+            &self.central_body
+        }
+
         fn semi_major_axis(&self) -> f64 {
             self.semi_latus_rectum / (1. - self.eccentricity.powi(2))
         }
@@ -134,14 +213,25 @@ mod coe_slr {
         fn semi_latus_rectum(&self) -> f64 {
             self.semi_latus_rectum
         }
+    }
 
-        fn period(&self) -> f64 {
-            let semi_major_axis = self.semi_major_axis();
-            if semi_major_axis > 0. {
-                (constants::MU_EARTH / self.semi_major_axis().powi(3)).sqrt()
-            } else {
-                0.
-            }
+    impl Shaped for COESlr {
+        fn eccentricity(&self) -> f64 {
+            self.eccentricity
+        }
+    }
+
+    impl Oriented for COESlr {
+        fn inclination(&self) -> f64 {
+            self.inclination
+        }
+
+        fn arg_peri(&self) -> f64 {
+            self.arg_peri
+        }
+
+        fn raan(&self) -> f64 {
+            self.raan
         }
     }
 }
@@ -159,6 +249,8 @@ mod comparison_tests {
             arg_peri: 2.,
             raan: 3.,
             true_anomaly: 4.,
+            central_body: bodies::EARTH,
+            epoch: None,
         };
         let coe_slr = COESlr {
             semi_latus_rectum: 1234.,
@@ -167,6 +259,8 @@ mod comparison_tests {
             arg_peri: 2.,
             raan: 3.,
             true_anomaly: 4.,
+            central_body: bodies::EARTH,
+            epoch: None,
         };
         assert_eq!(coe_canonical.semi_major_axis(), coe_slr.semi_major_axis());
         assert_eq!(
@@ -174,4 +268,14 @@ mod comparison_tests {
             coe_slr.semi_latus_rectum()
         );
     }
+
+    #[test]
+    fn coe_slr_round_trip_test() {
+        let coe = COE::new(7_000_000., 0.1, 1., 2., 3., 4.);
+        let coe_slr = COESlr::from(&coe);
+        assert_relative_eq!(coe_slr.semi_latus_rectum, 6_930_000., max_relative = 1e-15);
+        let round_trip = COE::from(&coe_slr);
+        assert_relative_eq!(round_trip.semi_major_axis, 7_000_000., max_relative = 1e-15);
+        assert_eq!(round_trip.true_anomaly, coe.true_anomaly);
+    }
 }
@@ -0,0 +1,614 @@
+//! Equinoctial elements, which stay well defined for circular and equatorial orbits.
+//!
+//! Both forms share the eccentricity components `f = e cos(arg_peri + raan)` and
+//! `g = e sin(arg_peri + raan)`, the inclination components `h = tan(i/2) cos(raan)` and
+//! `k = tan(i/2) sin(raan)`, and the true longitude `L = raan + arg_peri + true_anomaly`. The
+//! classical form is sized by the semi-major axis and the modified form by the semi-latus rectum,
+//! so only the latter can describe parabolic orbits. Both are singular for retrograde equatorial
+//! orbits (`i = pi`).
+
+use crate::angle_ops;
+use crate::bodies::{self, CentralBody};
+use crate::orbit::structs::coe::COESlr;
+use crate::orbit::structs::{CartesianState, CIRCULAR_TOL, COE, EQUATORIAL_TOL};
+use crate::orbit::traits::*;
+use crate::time::Epoch;
+use crate::vector::Vector3;
+
+/// Equinoctial `(f, g, h, k, L)` of a set of classical elements
+fn equinoctial_angles(coe: &COE) -> (f64, f64, f64, f64, f64) {
+    let longitude_of_peri = coe.raan + coe.arg_peri;
+    let f = coe.eccentricity * longitude_of_peri.cos();
+    let g = coe.eccentricity * longitude_of_peri.sin();
+    let tan_half_inc = (coe.inclination / 2.).tan();
+    let h = tan_half_inc * coe.raan.cos();
+    let k = tan_half_inc * coe.raan.sin();
+    let true_longitude = angle_ops::wrap_0_2pi(longitude_of_peri + coe.true_anomaly);
+    (f, g, h, k, true_longitude)
+}
+
+fn eccentricity(f: f64, g: f64) -> f64 {
+    f.hypot(g)
+}
+
+fn inclination(h: f64, k: f64) -> f64 {
+    2. * h.hypot(k).atan()
+}
+
+/// Classical `(inclination, arg_peri, raan, true_anomaly)` from the equinoctial angles, following
+/// the same circular and equatorial conventions as `COE::from(&CartesianState)`
+fn classical_angles(f: f64, g: f64, h: f64, k: f64, true_longitude: f64) -> (f64, f64, f64, f64) {
+    let inclination = inclination(h, k);
+    let raan = if inclination < EQUATORIAL_TOL {
+        0.
+    } else {
+        angle_ops::wrap_0_2pi(k.atan2(h))
+    };
+    let arg_peri = if eccentricity(f, g) < CIRCULAR_TOL {
+        0.
+    } else {
+        angle_ops::wrap_0_2pi(g.atan2(f) - raan)
+    };
+    let true_anomaly = angle_ops::wrap_0_2pi(true_longitude - raan - arg_peri);
+    (inclination, arg_peri, raan, true_anomaly)
+}
+
+fn arg_peri(f: f64, g: f64, h: f64, k: f64) -> f64 {
+    classical_angles(f, g, h, k, 0.).1
+}
+
+fn raan(h: f64, k: f64) -> f64 {
+    classical_angles(0., 0., h, k, 0.).2
+}
+
+pub use eoe_classical::EOE;
+mod eoe_classical {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct EOE {
+        pub semi_major_axis: f64,
+        pub f: f64,
+        pub g: f64,
+        pub h: f64,
+        pub k: f64,
+        pub true_longitude: f64,
+        pub central_body: CentralBody,
+        /// Instant the orbit is at, if known
+        pub epoch: Option<Epoch>,
+    }
+
+    impl EOE {
+        /// Elements of an orbit about the Earth, see `with_central_body` for other bodies
+        pub fn new(
+            semi_major_axis: f64,
+            f: f64,
+            g: f64,
+            h: f64,
+            k: f64,
+            true_longitude: f64,
+        ) -> Self {
+            Self {
+                semi_major_axis,
+                f,
+                g,
+                h,
+                k,
+                true_longitude,
+                central_body: bodies::EARTH,
+                epoch: None,
+            }
+        }
+
+        pub fn with_central_body(self, central_body: CentralBody) -> Self {
+            Self {
+                central_body,
+                ..self
+            }
+        }
+
+        pub fn with_epoch(self, epoch: Epoch) -> Self {
+            Self {
+                epoch: Some(epoch),
+                ..self
+            }
+        }
+    }
+
+    impl From<&COE> for EOE {
+        fn from(coe: &COE) -> Self {
+            let (f, g, h, k, true_longitude) = equinoctial_angles(coe);
+            Self {
+                epoch: coe.epoch,
+                ..Self::new(coe.semi_major_axis, f, g, h, k, true_longitude)
+                    .with_central_body(coe.central_body)
+            }
+        }
+    }
+
+    impl From<&EOE> for COE {
+        fn from(eoe: &EOE) -> Self {
+            let (inclination, arg_peri, raan, true_anomaly) =
+                classical_angles(eoe.f, eoe.g, eoe.h, eoe.k, eoe.true_longitude);
+            COE {
+                epoch: eoe.epoch,
+                ..COE::new(
+                    eoe.semi_major_axis,
+                    eoe.eccentricity(),
+                    inclination,
+                    arg_peri,
+                    raan,
+                    true_anomaly,
+                )
+                .with_central_body(eoe.central_body)
+            }
+        }
+    }
+
+    impl Scaled for EOE {
+        fn central_body(&self) -> &CentralBody {
+            &self.central_body
+        }
+
+        fn semi_major_axis(&self) -> f64 {
+            self.semi_major_axis
+        }
+
+        fn semi_latus_rectum(&self) -> f64 {
+            self.semi_major_axis * (1. - self.f.powi(2) - self.g.powi(2))
+        }
+    }
+
+    impl Shaped for EOE {
+        fn eccentricity(&self) -> f64 {
+            eccentricity(self.f, self.g)
+        }
+    }
+
+    impl Oriented for EOE {
+        fn inclination(&self) -> f64 {
+            inclination(self.h, self.k)
+        }
+
+        fn arg_peri(&self) -> f64 {
+            arg_peri(self.f, self.g, self.h, self.k)
+        }
+
+        fn raan(&self) -> f64 {
+            raan(self.h, self.k)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn eoe_round_trip_test() {
+            let coe = COE::new(7_000_000., 0.1, 1., 2., 3., 4.);
+            let eoe = EOE::from(&coe);
+            let coe_round_trip = COE::from(&eoe);
+            assert_eq!(coe_round_trip.semi_major_axis, coe.semi_major_axis);
+            assert_relative_eq!(
+                coe_round_trip.eccentricity,
+                coe.eccentricity,
+                epsilon = 1e-15
+            );
+            assert_relative_eq!(coe_round_trip.inclination, coe.inclination, epsilon = 1e-15);
+            assert_relative_eq!(coe_round_trip.arg_peri, coe.arg_peri, epsilon = 1e-14);
+            assert_relative_eq!(coe_round_trip.raan, coe.raan, epsilon = 1e-15);
+            assert_relative_eq!(
+                coe_round_trip.true_anomaly,
+                coe.true_anomaly,
+                epsilon = 1e-14
+            );
+        }
+
+        #[test]
+        fn eoe_traits_test() {
+            let coe = COE::new(7_000_000., 0.1, 1., 2., 3., 4.);
+            let eoe = EOE::from(&coe);
+            assert_eq!(eoe.semi_major_axis(), coe.semi_major_axis());
+            assert_relative_eq!(
+                eoe.semi_latus_rectum(),
+                coe.semi_latus_rectum(),
+                max_relative = 1e-15
+            );
+            assert_eq!(eoe.period(), coe.period());
+            assert_relative_eq!(eoe.eccentricity(), coe.eccentricity(), epsilon = 1e-15);
+            assert_relative_eq!(eoe.inclination(), coe.inclination(), epsilon = 1e-15);
+            assert_relative_eq!(eoe.arg_peri(), coe.arg_peri(), epsilon = 1e-14);
+            assert_relative_eq!(eoe.raan(), coe.raan(), epsilon = 1e-15);
+        }
+    }
+}
+
+pub use eoe_modified::MEE;
+mod eoe_modified {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct MEE {
+        pub semi_latus_rectum: f64,
+        pub f: f64,
+        pub g: f64,
+        pub h: f64,
+        pub k: f64,
+        pub true_longitude: f64,
+        pub central_body: CentralBody,
+        /// Instant the orbit is at, if known
+        pub epoch: Option<Epoch>,
+    }
+
+    impl MEE {
+        /// Elements of an orbit about the Earth, see `with_central_body` for other bodies
+        pub fn new(
+            semi_latus_rectum: f64,
+            f: f64,
+            g: f64,
+            h: f64,
+            k: f64,
+            true_longitude: f64,
+        ) -> Self {
+            Self {
+                semi_latus_rectum,
+                f,
+                g,
+                h,
+                k,
+                true_longitude,
+                central_body: bodies::EARTH,
+                epoch: None,
+            }
+        }
+
+        pub fn with_central_body(self, central_body: CentralBody) -> Self {
+            Self {
+                central_body,
+                ..self
+            }
+        }
+
+        pub fn with_epoch(self, epoch: Epoch) -> Self {
+            Self {
+                epoch: Some(epoch),
+                ..self
+            }
+        }
+    }
+
+    impl From<&COE> for MEE {
+        fn from(coe: &COE) -> Self {
+            let (f, g, h, k, true_longitude) = equinoctial_angles(coe);
+            Self {
+                epoch: coe.epoch,
+                ..Self::new(coe.semi_latus_rectum(), f, g, h, k, true_longitude)
+                    .with_central_body(coe.central_body)
+            }
+        }
+    }
+
+    impl From<&MEE> for COE {
+        fn from(mee: &MEE) -> Self {
+            let (inclination, arg_peri, raan, true_anomaly) =
+                classical_angles(mee.f, mee.g, mee.h, mee.k, mee.true_longitude);
+            COE {
+                epoch: mee.epoch,
+                ..COE::new(
+                    mee.semi_major_axis(),
+                    mee.eccentricity(),
+                    inclination,
+                    arg_peri,
+                    raan,
+                    true_anomaly,
+                )
+                .with_central_body(mee.central_body)
+            }
+        }
+    }
+
+    impl From<&COESlr> for MEE {
+        /// Keeps the semi-latus rectum as given, so parabolic elements convert too
+        fn from(coe: &COESlr) -> Self {
+            let (f, g, h, k, true_longitude) = equinoctial_angles(&COE::from(coe));
+            Self {
+                epoch: coe.epoch,
+                ..Self::new(coe.semi_latus_rectum, f, g, h, k, true_longitude)
+                    .with_central_body(coe.central_body)
+            }
+        }
+    }
+
+    impl From<&MEE> for COESlr {
+        fn from(mee: &MEE) -> Self {
+            let (inclination, arg_peri, raan, true_anomaly) =
+                classical_angles(mee.f, mee.g, mee.h, mee.k, mee.true_longitude);
+            COESlr {
+                semi_latus_rectum: mee.semi_latus_rectum,
+                eccentricity: mee.eccentricity(),
+                inclination,
+                arg_peri,
+                raan,
+                true_anomaly,
+                central_body: mee.central_body,
+                epoch: mee.epoch,
+            }
+        }
+    }
+
+    impl From<&EOE> for MEE {
+        fn from(eoe: &EOE) -> Self {
+            Self {
+                epoch: eoe.epoch,
+                ..Self::new(
+                    eoe.semi_latus_rectum(),
+                    eoe.f,
+                    eoe.g,
+                    eoe.h,
+                    eoe.k,
+                    eoe.true_longitude,
+                )
+                .with_central_body(eoe.central_body)
+            }
+        }
+    }
+
+    impl From<&MEE> for EOE {
+        fn from(mee: &MEE) -> Self {
+            EOE {
+                epoch: mee.epoch,
+                ..EOE::new(
+                    mee.semi_major_axis(),
+                    mee.f,
+                    mee.g,
+                    mee.h,
+                    mee.k,
+                    mee.true_longitude,
+                )
+                .with_central_body(mee.central_body)
+            }
+        }
+    }
+
+    impl From<&MEE> for CartesianState {
+        /// Direct conversion which, unlike going through `COE`, also handles parabolic orbits
+        fn from(mee: &MEE) -> Self {
+            let MEE {
+                semi_latus_rectum: p,
+                f,
+                g,
+                h,
+                k,
+                true_longitude,
+                central_body,
+                epoch,
+            } = *mee;
+            let (sin_l, cos_l) = true_longitude.sin_cos();
+            let alpha_sq = h.powi(2) - k.powi(2);
+            let s_sq = 1. + h.powi(2) + k.powi(2);
+            let w = 1. + f * cos_l + g * sin_l;
+            let r = p / w;
+
+            let position = Vector3::new([
+                cos_l + alpha_sq * cos_l + 2. * h * k * sin_l,
+                sin_l - alpha_sq * sin_l + 2. * h * k * cos_l,
+                2. * (h * sin_l - k * cos_l),
+            ]) * (r / s_sq);
+            let velocity = Vector3::new([
+                sin_l + alpha_sq * sin_l - 2. * h * k * cos_l + g - 2. * f * h * k + alpha_sq * g,
+                -cos_l + alpha_sq * cos_l + 2. * h * k * sin_l - f + 2. * g * h * k + alpha_sq * f,
+                -2. * (h * cos_l + k * sin_l + f * h + g * k),
+            ]) * (-(central_body.mu / p).sqrt() / s_sq);
+            Self {
+                position,
+                velocity,
+                central_body,
+                epoch,
+            }
+        }
+    }
+
+    impl From<&CartesianState> for MEE {
+        /// Direct conversion which, unlike going through `COE`, also handles parabolic orbits
+        fn from(state: &CartesianState) -> Self {
+            let CartesianState {
+                position,
+                velocity,
+                central_body,
+                epoch,
+            } = state;
+            let mu = central_body.mu;
+            let momentum = position.cross(velocity);
+            let semi_latus_rectum = momentum.dot(&momentum) / mu;
+            let [wx, wy, wz] = (momentum.clone() * (1. / momentum.norm())).elem;
+            let h = -wy / (1. + wz);
+            let k = wx / (1. + wz);
+
+            // Unit vectors of the equinoctial frame in the orbit plane
+            let s_sq = 1. + h.powi(2) + k.powi(2);
+            let f_hat =
+                Vector3::new([1. + h.powi(2) - k.powi(2), 2. * h * k, -2. * k]) * (1. / s_sq);
+            let g_hat =
+                Vector3::new([2. * h * k, 1. - h.powi(2) + k.powi(2), 2. * h]) * (1. / s_sq);
+            let r = position.norm();
+            let eccentricity_vector = (position.clone() * (velocity.dot(velocity) - mu / r)
+                - velocity.clone() * position.dot(velocity))
+                * (1. / mu);
+            let true_longitude = position.dot(&g_hat).atan2(position.dot(&f_hat));
+            Self {
+                epoch: *epoch,
+                ..Self::new(
+                    semi_latus_rectum,
+                    eccentricity_vector.dot(&f_hat),
+                    eccentricity_vector.dot(&g_hat),
+                    h,
+                    k,
+                    angle_ops::wrap_0_2pi(true_longitude),
+                )
+                .with_central_body(*central_body)
+            }
+        }
+    }
+
+    impl Scaled for MEE {
+        fn central_body(&self) -> &CentralBody {
+            &self.central_body
+        }
+
+        fn semi_major_axis(&self) -> f64 {
+            self.semi_latus_rectum / (1. - self.f.powi(2) - self.g.powi(2))
+        }
+
+        fn semi_latus_rectum(&self) -> f64 {
+            self.semi_latus_rectum
+        }
+    }
+
+    impl Shaped for MEE {
+        fn eccentricity(&self) -> f64 {
+            eccentricity(self.f, self.g)
+        }
+    }
+
+    impl Oriented for MEE {
+        fn inclination(&self) -> f64 {
+            inclination(self.h, self.k)
+        }
+
+        fn arg_peri(&self) -> f64 {
+            arg_peri(self.f, self.g, self.h, self.k)
+        }
+
+        fn raan(&self) -> f64 {
+            raan(self.h, self.k)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        use crate::testing;
+
+        #[test]
+        fn mee_round_trip_test() {
+            let coe = COE::new(-20_000_000., 1.5, 0.5, 1., 2., 0.3);
+            let mee = MEE::from(&coe);
+            let coe_round_trip = COE::from(&mee);
+            assert_relative_eq!(
+                coe_round_trip.semi_major_axis,
+                coe.semi_major_axis,
+                max_relative = 1e-14
+            );
+            assert_relative_eq!(
+                coe_round_trip.eccentricity,
+                coe.eccentricity,
+                epsilon = 1e-15
+            );
+            assert_relative_eq!(coe_round_trip.inclination, coe.inclination, epsilon = 1e-15);
+            assert_relative_eq!(coe_round_trip.arg_peri, coe.arg_peri, epsilon = 1e-14);
+            assert_relative_eq!(coe_round_trip.raan, coe.raan, epsilon = 1e-15);
+            assert_relative_eq!(
+                coe_round_trip.true_anomaly,
+                coe.true_anomaly,
+                epsilon = 1e-14
+            );
+        }
+
+        #[test]
+        fn mee_to_cartesian_test() {
+            let coe = COE::new(7_000_000., 0.1, 1., 2., 3., 4.);
+            let expected = CartesianState::from(&coe);
+            let state = CartesianState::from(&MEE::from(&coe));
+            testing::assert_array_eq_atol(&state.position.elem, &expected.position.elem, 1e-8);
+            testing::assert_array_eq_atol(&state.velocity.elem, &expected.velocity.elem, 1e-11);
+        }
+
+        #[test]
+        /// A circular equatorial GEO orbit has small, well-behaved equinoctial elements
+        fn mee_geo_test() {
+            let coe = COE::new(42_164_000., 1e-4, 1e-4, 1., 2., 3.);
+            let mee = MEE::from(&coe);
+            assert_relative_eq!(mee.f.hypot(mee.g), 1e-4, epsilon = 1e-16);
+            assert_relative_eq!(mee.h.hypot(mee.k), (0.5e-4_f64).tan(), epsilon = 1e-16);
+            assert_relative_eq!(mee.true_longitude, 6., epsilon = 1e-15);
+
+            // Exactly circular and equatorial elements fold into the true longitude
+            let mee = MEE::new(42_164_000., 0., 0., 0., 0., 6.);
+            let coe = COE::from(&mee);
+            assert_eq!(coe, COE::new(42_164_000., 0., 0., 0., 0., 6.));
+        }
+
+        #[test]
+        /// Parabolic elements go through the semi-latus rectum without passing an infinite
+        /// semi-major axis
+        fn mee_coe_slr_parabolic_test() {
+            let coe_slr = COESlr {
+                semi_latus_rectum: 10_000_000.,
+                eccentricity: 1.,
+                inclination: 0.5,
+                arg_peri: 1.,
+                raan: 2.,
+                true_anomaly: 0.3,
+                central_body: bodies::EARTH,
+                epoch: None,
+            };
+            let mee = MEE::from(&coe_slr);
+            assert_eq!(mee.semi_latus_rectum, coe_slr.semi_latus_rectum);
+            let round_trip = COESlr::from(&mee);
+            assert_eq!(round_trip.semi_latus_rectum, coe_slr.semi_latus_rectum);
+            assert_relative_eq!(round_trip.eccentricity, 1., epsilon = 1e-15);
+            assert_relative_eq!(round_trip.arg_peri, coe_slr.arg_peri, epsilon = 1e-14);
+            assert_relative_eq!(round_trip.raan, coe_slr.raan, epsilon = 1e-15);
+            assert_relative_eq!(round_trip.true_anomaly, 0.3, epsilon = 1e-14);
+        }
+
+        #[test]
+        /// State vectors convert straight to modified equinoctial elements and back, parabolic
+        /// ones included
+        fn mee_cartesian_round_trip_test() {
+            for mee in [
+                MEE::new(10_000_000., 0.6, 0.8, 0.1, 0.2, 0.5),
+                MEE::new(7_000_000., 0.01, -0.02, 0.3, -0.4, 4.),
+                MEE::from(&COE::new(-9_000_000., 1.4, 2.5, 1., 2., 0.3)),
+            ] {
+                let state = CartesianState::from(&mee);
+                let round_trip = MEE::from(&state);
+                assert_relative_eq!(
+                    round_trip.semi_latus_rectum,
+                    mee.semi_latus_rectum,
+                    max_relative = 1e-12
+                );
+                let expected = [mee.f, mee.g, mee.h, mee.k];
+                let actual = [round_trip.f, round_trip.g, round_trip.h, round_trip.k];
+                testing::assert_array_eq_atol(&actual, &expected, 1e-12);
+                let longitude =
+                    angle_ops::wrap_negpi_pi(round_trip.true_longitude - mee.true_longitude);
+                assert_relative_eq!(longitude, 0., epsilon = 1e-12);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod comparison_tests {
+    use super::*;
+
+    #[test]
+    fn eoe_mee_test() {
+        let coe = COE::new(7_000_000., 0.1, 1., 2., 3., 4.);
+        let eoe = EOE::from(&coe);
+        let mee = MEE::from(&coe);
+        assert_relative_eq!(
+            MEE::from(&eoe).semi_latus_rectum,
+            mee.semi_latus_rectum,
+            max_relative = 1e-15
+        );
+        assert_relative_eq!(
+            EOE::from(&mee).semi_major_axis,
+            eoe.semi_major_axis,
+            max_relative = 1e-15
+        );
+        assert_eq!(eoe.true_longitude, mee.true_longitude);
+    }
+}
@@ -1,8 +1,74 @@
+use crate::bodies::CentralBody;
+use crate::time::Epoch;
+
+pub mod cartesian;
 pub mod coe;
+pub mod eoe;
+
+pub use cartesian::CartesianState;
+pub use coe::{COESlr, COE};
+pub use eoe::{EOE, MEE};
+
+/// Eccentricity below which an orbit is treated as circular, leaving `arg_peri` undefined
+pub(crate) const CIRCULAR_TOL: f64 = 1e-11;
 
-pub use coe::COE;
+/// `sin(inclination)` below which an orbit is treated as equatorial, leaving `raan` undefined
+pub(crate) const EQUATORIAL_TOL: f64 = 1e-11;
 
+#[derive(Clone, Debug, PartialEq)]
 pub enum Orbit {
     COE(coe::COE),
-    EOE,
+    Cartesian(cartesian::CartesianState),
+    EOE(eoe::EOE),
+    MEE(eoe::MEE),
+}
+
+impl Orbit {
+    pub fn central_body(&self) -> &CentralBody {
+        match self {
+            Self::COE(coe) => &coe.central_body,
+            Self::Cartesian(state) => &state.central_body,
+            Self::EOE(eoe) => &eoe.central_body,
+            Self::MEE(mee) => &mee.central_body,
+        }
+    }
+
+    /// Instant the orbit is at, if known
+    pub fn epoch(&self) -> Option<Epoch> {
+        match self {
+            Self::COE(coe) => coe.epoch,
+            Self::Cartesian(state) => state.epoch,
+            Self::EOE(eoe) => eoe.epoch,
+            Self::MEE(mee) => mee.epoch,
+        }
+    }
+
+    pub fn with_epoch(self, epoch: Epoch) -> Self {
+        match self {
+            Self::COE(coe) => Self::COE(coe.with_epoch(epoch)),
+            Self::Cartesian(state) => Self::Cartesian(state.with_epoch(epoch)),
+            Self::EOE(eoe) => Self::EOE(eoe.with_epoch(epoch)),
+            Self::MEE(mee) => Self::MEE(mee.with_epoch(epoch)),
+        }
+    }
+
+    /// Position and velocity of this orbit, whichever representation it holds
+    pub fn to_cartesian(&self) -> CartesianState {
+        match self {
+            Self::COE(coe) => coe.into(),
+            Self::Cartesian(state) => state.clone(),
+            Self::EOE(eoe) => (&MEE::from(eoe)).into(),
+            Self::MEE(mee) => mee.into(),
+        }
+    }
+
+    /// Classical elements of this orbit, whichever representation it holds
+    pub fn to_coe(&self) -> COE {
+        match self {
+            Self::COE(coe) => coe.clone(),
+            Self::Cartesian(state) => state.into(),
+            Self::EOE(eoe) => eoe.into(),
+            Self::MEE(mee) => mee.into(),
+        }
+    }
 }
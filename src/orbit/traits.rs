@@ -1,11 +1,87 @@
-pub trait Sized {
+use std::f64::consts::PI;
+
+use crate::bodies::CentralBody;
+
+pub trait Scaled {
+    fn central_body(&self) -> &CentralBody;
     fn semi_major_axis(&self) -> f64;
     fn semi_latus_rectum(&self) -> f64;
-    fn period(&self) -> f64;
+
+    /// Mean angular rate `sqrt(mu / |a|^3)`, which also sets the rate of the hyperbolic mean
+    /// anomaly for open orbits
+    fn mean_motion(&self) -> f64 {
+        (self.central_body().mu / self.semi_major_axis().abs().powi(3)).sqrt()
+    }
+
+    /// Orbital period, or zero for orbits which never return
+    fn period(&self) -> f64 {
+        if self.semi_major_axis() > 0. {
+            2. * PI / self.mean_motion()
+        } else {
+            0.
+        }
+    }
+
+    /// Specific orbital energy `-mu / 2a`
+    fn specific_energy(&self) -> f64 {
+        -self.central_body().mu / (2. * self.semi_major_axis())
+    }
+
+    /// Magnitude of the specific angular momentum `sqrt(mu p)`
+    fn specific_angular_momentum(&self) -> f64 {
+        (self.central_body().mu * self.semi_latus_rectum()).sqrt()
+    }
 }
 
-pub trait Shaped {
+pub trait Shaped: Scaled {
     fn eccentricity(&self) -> f64;
+
+    /// Distance from the focus at the given true anomaly
+    fn radius(&self, true_anomaly: f64) -> f64 {
+        self.semi_latus_rectum() / (1. + self.eccentricity() * true_anomaly.cos())
+    }
+
+    fn periapsis_radius(&self) -> f64 {
+        self.semi_latus_rectum() / (1. + self.eccentricity())
+    }
+
+    /// Apoapsis radius, or infinity for orbits which never return
+    fn apoapsis_radius(&self) -> f64 {
+        let eccentricity = self.eccentricity();
+        if eccentricity < 1. {
+            self.semi_latus_rectum() / (1. - eccentricity)
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    /// Periapsis altitude above the equatorial radius of the central body
+    fn periapsis_altitude(&self) -> f64 {
+        self.periapsis_radius() - self.central_body().equatorial_radius
+    }
+
+    /// Apoapsis altitude above the equatorial radius of the central body
+    fn apoapsis_altitude(&self) -> f64 {
+        self.apoapsis_radius() - self.central_body().equatorial_radius
+    }
+
+    /// Angle of the velocity above the local horizontal at the given true anomaly
+    fn flight_path_angle(&self, true_anomaly: f64) -> f64 {
+        let (sin_nu, cos_nu) = true_anomaly.sin_cos();
+        let eccentricity = self.eccentricity();
+        f64::atan2(eccentricity * sin_nu, 1. + eccentricity * cos_nu)
+    }
+
+    /// Orbital speed at the given true anomaly, from the vis-viva equation
+    fn vis_viva_speed(&self, true_anomaly: f64) -> f64 {
+        let r = self.radius(true_anomaly);
+        (self.central_body().mu * (2. / r - 1. / self.semi_major_axis())).sqrt()
+    }
+
+    /// Speed needed to escape the central body from the radius at the given true anomaly
+    fn escape_velocity(&self, true_anomaly: f64) -> f64 {
+        (2. * self.central_body().mu / self.radius(true_anomaly)).sqrt()
+    }
 }
 
 pub trait Oriented {
@@ -13,3 +89,100 @@ pub trait Oriented {
     fn arg_peri(&self) -> f64;
     fn raan(&self) -> f64;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::bodies;
+    use crate::orbit::structs::COE;
+
+    /// Curtis, Orbital Mechanics for Engineering Students, 3rd ed., example 2.7: perigee and
+    /// apogee altitudes of 400 km and 4000 km above a 6378 km Earth
+    fn curtis_2_7() -> COE {
+        let central_body = bodies::CentralBody {
+            mu: 3.986e14,
+            equatorial_radius: 6_378_000.,
+            ..bodies::EARTH
+        };
+        let periapsis = 6_778_000.;
+        let apoapsis = 10_378_000.;
+        let semi_major_axis = (periapsis + apoapsis) / 2.;
+        let eccentricity = (apoapsis - periapsis) / (apoapsis + periapsis);
+        COE::new(semi_major_axis, eccentricity, 0., 0., 0., 0.).with_central_body(central_body)
+    }
+
+    #[test]
+    fn test_curtis_example() {
+        let coe = curtis_2_7();
+        assert_relative_eq!(coe.eccentricity(), 0.2098, epsilon = 1e-4);
+        assert_relative_eq!(
+            coe.specific_angular_momentum(),
+            57.172e9,
+            max_relative = 1e-4
+        );
+        assert_relative_eq!(coe.vis_viva_speed(0.), 8_435., max_relative = 1e-3);
+        assert_relative_eq!(coe.vis_viva_speed(PI), 5_509., max_relative = 1e-3);
+        assert_relative_eq!(coe.period(), 2.1963 * 3600., max_relative = 1e-4);
+        assert_relative_eq!(coe.specific_energy(), -23.234e6, max_relative = 1e-4);
+        assert_relative_eq!(coe.periapsis_altitude(), 400_000., max_relative = 1e-12);
+        assert_relative_eq!(coe.apoapsis_altitude(), 4_000_000., max_relative = 1e-12);
+    }
+
+    #[test]
+    /// A geostationary orbit goes around once per sidereal day
+    fn test_geostationary_period() {
+        let coe = COE::new(42_164_172., 0., 0., 0., 0., 0.);
+        assert_relative_eq!(coe.period(), 86_164.09, max_relative = 1e-6);
+        assert_relative_eq!(
+            coe.mean_motion(),
+            bodies::EARTH.rotation_rate,
+            max_relative = 1e-6
+        );
+        assert_relative_eq!(coe.vis_viva_speed(1.), 3_074.66, max_relative = 1e-5);
+    }
+
+    #[test]
+    /// Escape velocity from the Earth's surface is about 11.18 km/s
+    fn test_escape_velocity() {
+        let coe = COE::new(bodies::EARTH.equatorial_radius, 0., 0., 0., 0., 0.);
+        assert_relative_eq!(coe.escape_velocity(0.), 11_180., max_relative = 1e-3);
+        assert_relative_eq!(
+            coe.escape_velocity(0.),
+            2_f64.sqrt() * coe.vis_viva_speed(0.),
+            max_relative = 1e-15
+        );
+    }
+
+    #[test]
+    fn test_flight_path_angle() {
+        let coe = curtis_2_7();
+        let e = coe.eccentricity;
+        assert_eq!(coe.flight_path_angle(0.), 0.);
+        assert_relative_eq!(coe.flight_path_angle(PI), 0., epsilon = 1e-15);
+
+        // Largest at the ends of the minor axis, where tan(gamma) = e / sqrt(1 - e^2)
+        let nu = (-e).acos();
+        let expected = (e / (1. - e.powi(2)).sqrt()).atan();
+        assert_relative_eq!(coe.flight_path_angle(nu), expected, epsilon = 1e-15);
+        assert_relative_eq!(coe.flight_path_angle(-nu), -expected, epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_hyperbolic() {
+        let coe = COE::new(-20_000_000., 1.5, 0., 0., 0., 0.);
+        assert_eq!(coe.period(), 0.);
+        assert_eq!(coe.apoapsis_radius(), f64::INFINITY);
+        assert!(coe.specific_energy() > 0.);
+        assert_relative_eq!(coe.periapsis_radius(), 10_000_000., max_relative = 1e-15);
+
+        // Hyperbolic excess speed
+        let v_infinity = (bodies::EARTH.mu / 20_000_000_f64).sqrt();
+        let nu_infinity = (-1. / 1.5_f64).acos();
+        assert_relative_eq!(
+            coe.vis_viva_speed(nu_infinity * (1. - 1e-9)),
+            v_infinity,
+            max_relative = 1e-3
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! Passes of an orbiting body over ground stations: when it rises above each station's elevation
+//! mask, culminates and sets again.
+//!
+//! The elevation is sampled on a coarse grid to bracket each pass, whose rise and set are then
+//! found with Brent's method and whose culmination with a golden-section search, so that the
+//! times come out to `tolerance` however coarse the grid.
+//!
+//! ```
+//! use orbitrs::frames::{Geodetic, GroundStation};
+//! use orbitrs::passes::PassPredictor;
+//! use orbitrs::prelude::*;
+//!
+//! let start: Epoch = "2024-01-01T00:00:00Z".parse().unwrap();
+//! let orbit = Builder::new()
+//!     .periapsis_altitude(500e3)
+//!     .apoapsis_altitude(500e3)
+//!     .inclination(1.7)
+//!     .epoch(start)
+//!     .build()
+//!     .unwrap();
+//! let station = GroundStation::new("Svalbard", Geodetic::new(1.36, 0.27, 500.))
+//!     .with_minimum_elevation(5_f64.to_radians());
+//! let end = start + 86_400.;
+//! let windows = PassPredictor::new().predict(&orbit, &Keplerian, &[station], start, end);
+//! assert!(windows[0].len() > 5);
+//! ```
+
+use crate::frames::GroundStation;
+use crate::orbit::{Orbit, Propagator};
+use crate::roots::{brent_root, golden_section_maximum};
+use crate::time::Epoch;
+
+/// Time a body spends above a station's elevation mask
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pass {
+    /// Instant the body rises above the mask, or the start of the search if it was already up
+    pub rise: Epoch,
+    /// Instant of highest elevation
+    pub culmination: Epoch,
+    /// Instant the body sets below the mask, or the end of the search if it is still up
+    pub set: Epoch,
+    /// Highest elevation in radians
+    pub max_elevation: f64,
+    /// Azimuths in radians at rise, culmination and set
+    pub rise_azimuth: f64,
+    pub culmination_azimuth: f64,
+    pub set_azimuth: f64,
+}
+
+impl Pass {
+    /// Length of the pass in seconds
+    pub fn duration(&self) -> f64 {
+        self.set - self.rise
+    }
+}
+
+/// Finds the passes of an orbit over ground stations
+#[derive(Clone, Debug, PartialEq)]
+pub struct PassPredictor {
+    /// Spacing in seconds of the grid that brackets passes. Passes can be missed if two rises
+    /// or two culminations fall within one step.
+    pub step: f64,
+    /// Accuracy in seconds of the rise, culmination and set times
+    pub tolerance: f64,
+}
+
+impl Default for PassPredictor {
+    /// A 60 s grid, fine enough for low orbits, and times to a millisecond
+    fn default() -> Self {
+        Self {
+            step: 60.,
+            tolerance: 1e-3,
+        }
+    }
+}
+
+impl PassPredictor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_step(self, step: f64) -> Self {
+        Self { step, ..self }
+    }
+
+    pub fn with_tolerance(self, tolerance: f64) -> Self {
+        Self { tolerance, ..self }
+    }
+
+    /// Passes of `orbit`, in GCRF, over each of `stations` between `start` and `end`, as one list
+    /// per station in the order given. An orbit without an epoch is taken to be at `start`.
+    pub fn predict(
+        &self,
+        orbit: &Orbit,
+        propagator: &impl Propagator,
+        stations: &[GroundStation],
+        start: Epoch,
+        end: Epoch,
+    ) -> Vec<Vec<Pass>> {
+        let epoch = orbit.epoch().unwrap_or(start);
+        let span = end - start;
+        let count = (span / self.step).ceil().max(1.) as usize;
+        let offsets: Vec<f64> = (0..=count)
+            .map(|index| span * index as f64 / count as f64)
+            .collect();
+        let states: Vec<_> = offsets
+            .iter()
+            .map(|&offset| {
+                propagator
+                    .propagate(orbit, start + offset - epoch)
+                    .to_cartesian()
+            })
+            .collect();
+
+        stations
+            .iter()
+            .map(|station| {
+                let observe = |offset: f64| {
+                    let time = start + offset;
+                    let state = propagator.propagate(orbit, time - epoch).to_cartesian();
+                    station.observe(&state, time)
+                };
+                let heights: Vec<f64> = offsets
+                    .iter()
+                    .zip(&states)
+                    .map(|(&offset, state)| {
+                        station.observe(state, start + offset).elevation - station.minimum_elevation
+                    })
+                    .collect();
+                let height = |offset| observe(offset).elevation - station.minimum_elevation;
+                self.find_passes(&offsets, &heights, height)
+                    .into_iter()
+                    .map(|(rise, culmination, set)| {
+                        let (at_rise, at_culmination, at_set) =
+                            (observe(rise), observe(culmination), observe(set));
+                        Pass {
+                            rise: start + rise,
+                            culmination: start + culmination,
+                            set: start + set,
+                            max_elevation: at_culmination.elevation,
+                            rise_azimuth: at_rise.azimuth,
+                            culmination_azimuth: at_culmination.azimuth,
+                            set_azimuth: at_set.azimuth,
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Rise, culmination and set offsets of each pass, given the height above the mask on the
+    /// grid of `offsets`
+    fn find_passes(
+        &self,
+        offsets: &[f64],
+        heights: &[f64],
+        height: impl Fn(f64) -> f64,
+    ) -> Vec<(f64, f64, f64)> {
+        let culmination = |rise, set| golden_section_maximum(&height, rise, set, self.tolerance);
+        let last = offsets.len() - 1;
+        let mut passes = vec![];
+        let mut rise = (heights[0] >= 0.).then_some(offsets[0]);
+        for index in 1..=last {
+            let (before, after) = (offsets[index - 1], offsets[index]);
+            match (rise, heights[index] >= 0.) {
+                (None, true) => {
+                    rise = Some(brent_root(&height, before, after, self.tolerance));
+                }
+                (Some(start), false) => {
+                    let set = brent_root(&height, before, after, self.tolerance);
+                    passes.push((start, culmination(start, set), set));
+                    rise = None;
+                }
+                // A pass shorter than the grid shows up as a peak in the heights below the mask
+                (None, false)
+                    if index < last
+                        && heights[index] > heights[index - 1]
+                        && heights[index] >= heights[index + 1] =>
+                {
+                    let next = offsets[index + 1];
+                    let peak = golden_section_maximum(&height, before, next, self.tolerance);
+                    if height(peak) >= 0. {
+                        let rise = brent_root(&height, before, peak, self.tolerance);
+                        let set = brent_root(&height, peak, next, self.tolerance);
+                        passes.push((rise, peak, set));
+                    }
+                }
+                _ => (),
+            }
+        }
+        if let Some(start) = rise {
+            passes.push((start, culmination(start, offsets[last]), offsets[last]));
+        }
+        passes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::frames::{Ellipsoid, Frame, FrameTransform, Geodetic};
+    use crate::orbit::{Builder, Keplerian};
+
+    fn low_orbit(epoch: Epoch) -> Orbit {
+        Builder::new()
+            .periapsis_altitude(550e3)
+            .apoapsis_altitude(600e3)
+            .inclination(97.6_f64.to_radians())
+            .epoch(epoch)
+            .build()
+            .unwrap()
+    }
+
+    fn stations() -> [GroundStation; 2] {
+        [
+            GroundStation::new(
+                "Svalbard",
+                Geodetic::new(78.23_f64.to_radians(), 15.39_f64.to_radians(), 500.),
+            )
+            .with_minimum_elevation(5_f64.to_radians()),
+            GroundStation::new(
+                "Hartebeesthoek",
+                Geodetic::new(-25.89_f64.to_radians(), 27.69_f64.to_radians(), 1_415.),
+            )
+            .with_minimum_elevation(10_f64.to_radians()),
+        ]
+    }
+
+    #[test]
+    /// Every pass sits exactly on the masks at rise and set, and a fine scan of the day finds the
+    /// body above a mask exactly when it is inside a pass
+    fn pass_test() {
+        let start: Epoch = "2023-03-01T00:00:00Z".parse().unwrap();
+        let end = start + 86_400.;
+        let orbit = low_orbit(start);
+        let stations = stations();
+        let windows = PassPredictor::new().predict(&orbit, &Keplerian, &stations, start, end);
+        assert_eq!(windows.len(), 2);
+        // Svalbard sees most orbits of a polar satellite, the far south station a few
+        assert!(windows[0].len() >= 10, "{}", windows[0].len());
+        assert!((2..=6).contains(&windows[1].len()), "{}", windows[1].len());
+
+        for (station, passes) in stations.iter().zip(&windows) {
+            let observe = |time: Epoch| {
+                let state = Keplerian.propagate(&orbit, time - start).to_cartesian();
+                station.observe(&state, time)
+            };
+            for pass in passes {
+                assert!(pass.rise < pass.culmination && pass.culmination < pass.set);
+                assert!(pass.duration() < 900.);
+                for time in [pass.rise, pass.set] {
+                    assert_relative_eq!(
+                        observe(time).elevation,
+                        station.minimum_elevation,
+                        epsilon = 1e-5
+                    );
+                }
+                assert!(pass.max_elevation >= station.minimum_elevation);
+                for offset in [-1., 1.] {
+                    assert!(observe(pass.culmination + offset).elevation < pass.max_elevation);
+                }
+                assert_relative_eq!(
+                    observe(pass.rise).azimuth,
+                    pass.rise_azimuth,
+                    epsilon = 1e-12
+                );
+            }
+
+            for step in 0..8_640 {
+                let time = start + 10. * step as f64;
+                let visible = observe(time).elevation > station.minimum_elevation;
+                let in_pass = passes
+                    .iter()
+                    .any(|pass| pass.rise <= time && time <= pass.set);
+                assert_eq!(visible, in_pass, "{} at {time}", station.name);
+            }
+        }
+    }
+
+    #[test]
+    /// A coarse grid still finds short, low passes, which never rise above the mask on the grid
+    fn grazing_pass_test() {
+        let start: Epoch = "2023-03-01T00:00:00Z".parse().unwrap();
+        let end = start + 86_400.;
+        let orbit = low_orbit(start);
+        let stations = stations();
+        let fine = PassPredictor::new().with_step(10.);
+        let coarse = PassPredictor::new().with_step(300.);
+        let expected = fine.predict(&orbit, &Keplerian, &stations, start, end);
+        let found = coarse.predict(&orbit, &Keplerian, &stations, start, end);
+        assert!(found.iter().flatten().any(|pass| pass.duration() < 300.));
+        for (expected, found) in expected.iter().zip(&found) {
+            assert_eq!(expected.len(), found.len());
+            for (expected, found) in expected.iter().zip(found) {
+                assert!((expected.rise - found.rise).abs() < 2e-3);
+                assert!((expected.set - found.set).abs() < 2e-3);
+            }
+        }
+    }
+
+    #[test]
+    /// A geostationary satellite never sets, which gives one pass over the whole search
+    fn geostationary_test() {
+        let start: Epoch = "2023-03-01T00:00:00Z".parse().unwrap();
+        let orbit = Builder::new()
+            .periapsis_altitude(35_786e3)
+            .apoapsis_altitude(35_786e3)
+            .build()
+            .unwrap();
+        // Below the satellite
+        let position = FrameTransform::new(Frame::GCRF, Frame::ITRF, start)
+            .position(&orbit.to_cartesian().position);
+        let below = Geodetic {
+            height: 0.,
+            ..Ellipsoid::WGS84.geodetic(&position)
+        };
+        let station = GroundStation::new("Below", below);
+        let windows =
+            PassPredictor::new().predict(&orbit, &Keplerian, &[station], start, start + 3_600.);
+        assert_eq!(windows[0].len(), 1);
+        let pass = windows[0][0];
+        assert_eq!(pass.rise, start);
+        assert_eq!(pass.duration(), 3_600.);
+    }
+}
@@ -4,9 +4,9 @@ use crate::vector::Vector3;
 use crate::vector_ops;
 
 #[derive(Clone, Debug, PartialEq)]
-struct Quaternion {
-    scalar: f64,
-    vector: Vector3,
+pub struct Quaternion {
+    pub scalar: f64,
+    pub vector: Vector3,
 }
 
 impl Default for Quaternion {
@@ -16,14 +16,14 @@ impl Default for Quaternion {
 }
 
 impl Quaternion {
-    fn identity() -> Self {
+    pub fn identity() -> Self {
         Quaternion {
             scalar: 1.0,
             vector: Vector3::new([0.0, 0.0, 0.0]),
         }
     }
 
-    fn from_angle_axis(angle: f64, axis: &[f64; 3]) -> Self {
+    pub fn from_angle_axis(angle: f64, axis: &[f64; 3]) -> Self {
         // TODO: Improve this API so axis can be a Vector3 as well
         let half_angle = angle / 2.0;
         let scalar = f64::cos(half_angle);
@@ -35,7 +35,70 @@ impl Quaternion {
         Self { scalar, vector }
     }
 
-    fn to_angle_axis(&self) -> (f64, [f64; 3]) {
+    /// Quaternion whose alias rotation is the direction cosine matrix `dcm`, that is which takes
+    /// vectors into the frame whose axes are the rows of `dcm`.
+    ///
+    /// This is Shepperd's method, which divides by the largest of the four components so stays
+    /// accurate for any rotation. The scalar part comes out non-negative.
+    pub fn from_dcm(dcm: &[[f64; 3]; 3]) -> Self {
+        let m = dcm;
+        let trace = m[0][0] + m[1][1] + m[2][2];
+        let (scalar, vector) = if trace > m[0][0].max(m[1][1]).max(m[2][2]) {
+            let scalar = (1. + trace).sqrt() / 2.;
+            let d = 4. * scalar;
+            let vector = [
+                (m[1][2] - m[2][1]) / d,
+                (m[2][0] - m[0][2]) / d,
+                (m[0][1] - m[1][0]) / d,
+            ];
+            (scalar, vector)
+        } else if m[0][0] >= m[1][1] && m[0][0] >= m[2][2] {
+            let x = (1. + m[0][0] - m[1][1] - m[2][2]).sqrt() / 2.;
+            let d = 4. * x;
+            let vector = [x, (m[0][1] + m[1][0]) / d, (m[0][2] + m[2][0]) / d];
+            ((m[1][2] - m[2][1]) / d, vector)
+        } else if m[1][1] >= m[2][2] {
+            let y = (1. - m[0][0] + m[1][1] - m[2][2]).sqrt() / 2.;
+            let d = 4. * y;
+            let vector = [(m[0][1] + m[1][0]) / d, y, (m[1][2] + m[2][1]) / d];
+            ((m[2][0] - m[0][2]) / d, vector)
+        } else {
+            let z = (1. - m[0][0] - m[1][1] + m[2][2]).sqrt() / 2.;
+            let d = 4. * z;
+            let vector = [(m[0][2] + m[2][0]) / d, (m[1][2] + m[2][1]) / d, z];
+            ((m[0][1] - m[1][0]) / d, vector)
+        };
+        let sign = if scalar < 0. { -1. } else { 1. };
+        Self {
+            scalar: sign * scalar,
+            vector: vector.map(|elem| sign * elem).into(),
+        }
+    }
+
+    /// Direction cosine matrix of the alias rotation, whose rows are the axes of the rotated frame
+    pub fn to_dcm(&self) -> [[f64; 3]; 3] {
+        let w = self.scalar;
+        let [x, y, z] = self.vector.elem;
+        [
+            [
+                1. - 2. * (y * y + z * z),
+                2. * (x * y + w * z),
+                2. * (x * z - w * y),
+            ],
+            [
+                2. * (x * y - w * z),
+                1. - 2. * (x * x + z * z),
+                2. * (y * z + w * x),
+            ],
+            [
+                2. * (x * z + w * y),
+                2. * (y * z - w * x),
+                1. - 2. * (x * x + y * y),
+            ],
+        ]
+    }
+
+    pub fn to_angle_axis(&self) -> (f64, [f64; 3]) {
         let half_angle = f64::acos(self.scalar);
         let sin_half_angle = f64::sin(half_angle);
         let axis = self.vector.elem.map(|elem| elem / sin_half_angle);
@@ -43,7 +106,7 @@ impl Quaternion {
     }
 
     /// Returns the inverse as a new Quaternion
-    fn inverted(&self) -> Self {
+    pub fn inverted(&self) -> Self {
         let vector = self.vector.elem.map(|elem| -elem).into();
         Self {
             scalar: self.scalar,
@@ -52,14 +115,14 @@ impl Quaternion {
     }
 
     /// Inverts the Quaternion in place
-    fn invert(&mut self) {
+    pub fn invert(&mut self) {
         self.vector = self.vector.elem.map(|elem| -elem).into();
     }
 
     /// Rotates a vector with an alias (passive) convention.
     ///
     /// This is equivalent to representing the same vector in a new rotated frame.
-    fn rotated_vec_alias(&self, vec: &Vector3) -> Vector3 {
+    pub fn rotated_vec_alias(&self, vec: &Vector3) -> Vector3 {
         let vec_cross_quat = vec.cross(&self.vector);
         let term1 = vec_cross_quat.clone() * 2.0 * self.scalar;
         let term2 = vec_cross_quat.cross(&self.vector) * 2.0;
@@ -69,7 +132,7 @@ impl Quaternion {
     /// Rotates a vector with an alibi (active) convention.
     ///
     /// This is equivalent to actively rotating the vector in space.
-    fn rotated_vec_alibi(&self, vec: &Vector3) -> Vector3 {
+    pub fn rotated_vec_alibi(&self, vec: &Vector3) -> Vector3 {
         let quat_cross_vec = self.vector.cross(vec);
         let term1 = quat_cross_vec.clone() * 2.0 * self.scalar;
         let term2 = self.vector.cross(&quat_cross_vec) * 2.0;
@@ -132,6 +195,32 @@ mod tests {
         testing::assert_array_eq(&axis, &original_axis);
     }
 
+    #[test]
+    /// A quaternion rebuilt from the direction cosine matrix of its alias rotation is the same
+    /// quaternion, whichever component is largest, and the matrix rotates vectors as it does
+    fn test_from_dcm() {
+        for (angle, axis) in [
+            (0.3, [1.0, 2.0, 3.0]),
+            (3.0, [1.0, 0.1, -0.2]),
+            (3.1, [0.2, -1.0, 0.1]),
+            (-3.0, [0.1, 0.2, 1.0]),
+        ] {
+            let q = Quaternion::from_angle_axis(angle, &axis);
+            let dcm = [constants::X_AXIS, constants::Y_AXIS, constants::Z_AXIS]
+                .map(|row| q.rotated_vec_alibi(&row.into()).elem);
+            let rebuilt = Quaternion::from_dcm(&dcm);
+            let sign = q.scalar.signum();
+            assert_relative_eq!(rebuilt.scalar, sign * q.scalar, epsilon = 1e-15);
+            let expected = q.vector.elem.map(|elem| sign * elem);
+            testing::assert_array_eq_atol(&rebuilt.vector.elem, &expected, 1e-15);
+
+            let vec = Vector3::new([1.0, -2.0, 3.0]);
+            let rotated = q.rotated_vec_alias(&vec);
+            let by_dcm = q.to_dcm().map(|row| Vector3::new(row).dot(&vec));
+            testing::assert_array_eq_atol(&by_dcm, &rotated.elem, 1e-14);
+        }
+    }
+
     #[test]
     /// Multiplying a quaternion by its inverse gives the identity quaternion
     fn test_inversion_gives_identity() {
@@ -0,0 +1,377 @@
+//! Relative motion of a deputy near a chief on a circular orbit, after Hill, Clohessy and
+//! Wiltshire.
+//!
+//! Relative states are given in the chief's [`LocalFrame::LVLH`] frame: x along the velocity
+//! (V-bar), y against the angular momentum and z down towards the central body (R-bar), with
+//! velocities seen from the rotating frame. They come exactly from the absolute states of both
+//! bodies, and are propagated with the linearised HCW equations, which hold while the separation
+//! is small next to the chief's radius and the chief's orbit is close to circular.
+//!
+//! ```
+//! use orbitrs::prelude::*;
+//! use orbitrs::relative::{self, ClohessyWiltshire, RelativeState};
+//!
+//! let chief: CartesianState = (&COE::new(6_778e3, 0., 0.9, 0., 0., 0.)).into();
+//! let deputy = RelativeState::new(Vector3::new([-500., 0., 100.]), Vector3::new([0.; 3]));
+//! let dock = RelativeState::new(Vector3::new([0.; 3]), Vector3::new([0.; 3]));
+//! let transfer = ClohessyWiltshire::from_chief(&chief)
+//!     .rendezvous(&deputy, &dock, 1_800.)
+//!     .unwrap();
+//! assert!(transfer.delta_v() < 1.);
+//!
+//! let inertial = relative::deputy_state(&chief, &deputy).unwrap();
+//! let recovered = relative::relative_state(&chief, &inertial).unwrap();
+//! assert!((recovered.position - deputy.position).norm() < 1e-6);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::frames::{LocalFrame, LocalOrientation};
+use crate::orbit::structs::CartesianState;
+use crate::orbit::Scaled;
+use crate::vector::Vector3;
+
+/// Determinant, relative to its largest possible size, below which a transfer's final position
+/// doesn't pin down its initial velocity
+const SINGULAR_TOL: f64 = 1e-9;
+
+/// Position and velocity of a deputy relative to its chief, in the chief's LVLH frame
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelativeState {
+    /// Position in m
+    pub position: Vector3,
+    /// Velocity in m/s, as seen from the rotating frame
+    pub velocity: Vector3,
+}
+
+impl RelativeState {
+    pub fn new(position: Vector3, velocity: Vector3) -> Self {
+        Self { position, velocity }
+    }
+
+    fn to_array(&self) -> [f64; 6] {
+        let [x, y, z] = self.position.elem;
+        let [vx, vy, vz] = self.velocity.elem;
+        [x, y, z, vx, vy, vz]
+    }
+
+    fn from_array(state: [f64; 6]) -> Self {
+        let [x, y, z, vx, vy, vz] = state;
+        Self::new(Vector3::new([x, y, z]), Vector3::new([vx, vy, vz]))
+    }
+}
+
+/// Two burns, in the chief's LVLH frame, taking a deputy from one relative state to another
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rendezvous {
+    pub time_of_flight: f64,
+    /// Change of velocity in m/s at the start
+    pub departure_delta_v: Vector3,
+    /// Change of velocity in m/s on arrival, matching the target's velocity
+    pub arrival_delta_v: Vector3,
+}
+
+impl Rendezvous {
+    /// Total delta-v in m/s
+    pub fn delta_v(&self) -> f64 {
+        self.departure_delta_v.norm() + self.arrival_delta_v.norm()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RelativeError {
+    /// A chief with no angular momentum, whose LVLH frame is undefined
+    RectilinearChief,
+    /// A chief and deputy about different central bodies
+    DifferentBodies,
+    NonPositiveTimeOfFlight(f64),
+    /// A time of flight in s after which the final position can't be targeted, such as a whole
+    /// number of half periods
+    SingularTransfer(f64),
+}
+
+impl fmt::Display for RelativeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RectilinearChief => write!(f, "rectilinear chief orbit has no LVLH frame"),
+            Self::DifferentBodies => write!(f, "chief and deputy orbit different central bodies"),
+            Self::NonPositiveTimeOfFlight(time) => {
+                write!(f, "time of flight {time} s is not positive")
+            }
+            Self::SingularTransfer(time) => {
+                write!(
+                    f,
+                    "position after {time} s does not fix the departure velocity"
+                )
+            }
+        }
+    }
+}
+
+impl Error for RelativeError {}
+
+/// Linearised relative motion about a chief on a circular orbit
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClohessyWiltshire {
+    /// Mean motion of the chief in rad/s
+    pub mean_motion: f64,
+}
+
+impl ClohessyWiltshire {
+    pub fn new(mean_motion: f64) -> Self {
+        Self { mean_motion }
+    }
+
+    /// Motion about a chief at `state`, taken at its mean motion
+    pub fn from_chief(state: &CartesianState) -> Self {
+        Self::new(state.mean_motion())
+    }
+
+    /// Matrix taking a relative state, ordered position then velocity, to the state `time`
+    /// seconds later
+    pub fn state_transition_matrix(&self, time: f64) -> [[f64; 6]; 6] {
+        let n = self.mean_motion;
+        let nt = n * time;
+        let (s, c) = nt.sin_cos();
+        [
+            [
+                1.,
+                0.,
+                6. * (nt - s),
+                4. * s / n - 3. * time,
+                0.,
+                2. * (1. - c) / n,
+            ],
+            [0., c, 0., 0., s / n, 0.],
+            [0., 0., 4. - 3. * c, -2. * (1. - c) / n, 0., s / n],
+            [0., 0., 6. * n * (1. - c), 4. * c - 3., 0., 2. * s],
+            [0., -n * s, 0., 0., c, 0.],
+            [0., 0., 3. * n * s, -2. * s, 0., c],
+        ]
+    }
+
+    /// Relative state `time` seconds after `state`
+    pub fn propagate(&self, state: &RelativeState, time: f64) -> RelativeState {
+        let stm = self.state_transition_matrix(time);
+        let state = state.to_array();
+        RelativeState::from_array(stm.map(|row| dot(&row, &state)))
+    }
+
+    /// Burns taking a deputy at `from` to `to` in `time_of_flight` seconds, arriving with the
+    /// velocity of `to`. A target of zeros docks with the chief.
+    pub fn rendezvous(
+        &self,
+        from: &RelativeState,
+        to: &RelativeState,
+        time_of_flight: f64,
+    ) -> Result<Rendezvous, RelativeError> {
+        if time_of_flight <= 0. || time_of_flight.is_nan() {
+            return Err(RelativeError::NonPositiveTimeOfFlight(time_of_flight));
+        }
+        let stm = self.state_transition_matrix(time_of_flight);
+        let mut coast = from.to_array();
+        coast[3..].fill(0.);
+        let coast = stm.map(|row| dot(&row, &coast));
+        // Velocity to leave with, from the position rows of the matrix, in which the in-plane
+        // motion (x, z) and the out-of-plane motion (y) separate
+        let [bx, by, bz] = [0, 1, 2].map(|i| to.position.elem[i] - coast[i]);
+        let [[a, b], [c, d]] = [[stm[0][3], stm[0][5]], [stm[2][3], stm[2][5]]];
+        let in_plane = a * d - b * c;
+        let out_of_plane = stm[1][4];
+        let scale = [a, b, c, d].map(f64::abs).into_iter().fold(0., f64::max);
+        if in_plane.abs() < SINGULAR_TOL * scale.powi(2)
+            || (out_of_plane * self.mean_motion).abs() < SINGULAR_TOL
+        {
+            return Err(RelativeError::SingularTransfer(time_of_flight));
+        }
+        let departure_velocity = Vector3::new([
+            (d * bx - b * bz) / in_plane,
+            by / out_of_plane,
+            (a * bz - c * bx) / in_plane,
+        ]);
+        let departure = RelativeState::new(from.position.clone(), departure_velocity);
+        let arrival = self.propagate(&departure, time_of_flight);
+        Ok(Rendezvous {
+            time_of_flight,
+            departure_delta_v: departure.velocity - from.velocity.clone(),
+            arrival_delta_v: to.velocity.clone() - arrival.velocity,
+        })
+    }
+}
+
+/// State of `deputy` relative to `chief`, both inertial, in the chief's LVLH frame
+pub fn relative_state(
+    chief: &CartesianState,
+    deputy: &CartesianState,
+) -> Result<RelativeState, RelativeError> {
+    if chief.central_body != deputy.central_body {
+        return Err(RelativeError::DifferentBodies);
+    }
+    let (position, velocity) = lvlh(chief)?.state(
+        &(deputy.position.clone() - chief.position.clone()),
+        &(deputy.velocity.clone() - chief.velocity.clone()),
+    );
+    Ok(RelativeState::new(position, velocity))
+}
+
+/// Inertial state of a deputy at `relative` to `chief`, about the same body and at the same
+/// epoch as the chief
+pub fn deputy_state(
+    chief: &CartesianState,
+    relative: &RelativeState,
+) -> Result<CartesianState, RelativeError> {
+    let (position, velocity) = lvlh(chief)?.inertial_state(&relative.position, &relative.velocity);
+    Ok(CartesianState {
+        position: chief.position.clone() + position,
+        velocity: chief.velocity.clone() + velocity,
+        ..chief.clone()
+    })
+}
+
+fn lvlh(chief: &CartesianState) -> Result<LocalOrientation, RelativeError> {
+    LocalFrame::LVLH
+        .orientation(chief)
+        .ok_or(RelativeError::RectilinearChief)
+}
+
+fn dot(row: &[f64; 6], state: &[f64; 6]) -> f64 {
+    row.iter().zip(state).map(|(a, b)| a * b).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f64::consts::PI;
+
+    use crate::bodies;
+    use crate::orbit::structs::COE;
+    use crate::orbit::Keplerian;
+    use crate::testing;
+
+    fn chief() -> CartesianState {
+        (&COE::new(6_778e3, 0., 0.9, 0.3, 1.2, 0.5)).into()
+    }
+
+    fn deputy() -> RelativeState {
+        RelativeState::new(
+            Vector3::new([-200., 30., 50.]),
+            Vector3::new([0.05, -0.02, 0.1]),
+        )
+    }
+
+    #[test]
+    /// The matrix chains over successive times, and the states it gives obey the HCW equations
+    fn state_transition_matrix_test() {
+        let cw = ClohessyWiltshire::from_chief(&chief());
+        let n = cw.mean_motion;
+        let start = deputy();
+        let chained = cw.propagate(&cw.propagate(&start, 700.), 1_100.);
+        let direct = cw.propagate(&start, 1_800.);
+        testing::assert_array_eq_atol(&chained.to_array(), &direct.to_array(), 1e-10);
+
+        let step = 1e-2;
+        let [before, now, after] = [-step, 0., step].map(|dt| cw.propagate(&direct, dt));
+        let acceleration = (after.velocity - before.velocity) * (0.5 / step);
+        let [_, y, z] = now.position.elem;
+        let [vx, _, vz] = now.velocity.elem;
+        let expected = [2. * n * vz, -n * n * y, 3. * n * n * z - 2. * n * vx];
+        testing::assert_array_eq_atol(&acceleration.elem, &expected, 1e-12);
+    }
+
+    #[test]
+    /// A deputy drifting forward at twice the mean motion times its height has no secular drift
+    /// and comes back after one period
+    fn closed_orbit_test() {
+        let cw = ClohessyWiltshire::from_chief(&chief());
+        let n = cw.mean_motion;
+        let start = RelativeState::new(
+            Vector3::new([100., 20., 50.]),
+            Vector3::new([2. * n * 50., 0.01, 0.03]),
+        );
+        let end = cw.propagate(&start, 2. * PI / n);
+        testing::assert_array_eq_atol(&end.to_array(), &start.to_array(), 1e-9);
+    }
+
+    #[test]
+    /// Relative states go to absolute ones and back exactly, and a deputy just ahead on the
+    /// chief's orbit sits along +x, slightly below the chief
+    fn conversion_test() {
+        let chief = chief();
+        let deputy = deputy();
+        let inertial = deputy_state(&chief, &deputy).unwrap();
+        let recovered = relative_state(&chief, &inertial).unwrap();
+        testing::assert_array_eq_atol(&recovered.to_array(), &deputy.to_array(), 1e-9);
+
+        let coe = COE::from(&chief);
+        let ahead = COE {
+            true_anomaly: coe.true_anomaly + 1e-4,
+            ..coe
+        };
+        let ahead = relative_state(&chief, &(&ahead).into()).unwrap();
+        let [x, y, z] = ahead.position.elem;
+        assert_relative_eq!(x, 6_778e3 * 1e-4, max_relative = 1e-8);
+        assert_relative_eq!(y, 0., epsilon = 1e-6);
+        assert!(z > 0.);
+        testing::assert_array_eq_atol(&ahead.velocity.elem, &[0.; 3], 1e-9);
+
+        let lunar = CartesianState {
+            central_body: bodies::MOON,
+            ..inertial
+        };
+        assert_eq!(
+            relative_state(&chief, &lunar),
+            Err(RelativeError::DifferentBodies)
+        );
+    }
+
+    #[test]
+    /// For a close deputy the HCW equations follow the exact relative motion of two Keplerian
+    /// orbits
+    fn nonlinear_test() {
+        let chief = chief();
+        let deputy = deputy();
+        let cw = ClohessyWiltshire::from_chief(&chief);
+        let inertial = deputy_state(&chief, &deputy).unwrap();
+        let time = 1_500.;
+        let exact = relative_state(
+            &Keplerian.propagate_cartesian(&chief, time),
+            &Keplerian.propagate_cartesian(&inertial, time),
+        )
+        .unwrap();
+        let linear = cw.propagate(&deputy, time);
+        testing::assert_array_eq_atol(&linear.position.elem, &exact.position.elem, 1e-2);
+        testing::assert_array_eq_atol(&linear.velocity.elem, &exact.velocity.elem, 1e-5);
+    }
+
+    #[test]
+    /// The departure burn puts the deputy on a path to the target, which the arrival burn
+    /// matches, and targeting after a whole period is singular
+    fn rendezvous_test() {
+        let cw = ClohessyWiltshire::from_chief(&chief());
+        let from = deputy();
+        let to = RelativeState::new(Vector3::new([-10., 0., 0.]), Vector3::new([0.02, 0., 0.]));
+        let time = 2_000.;
+        let transfer = cw.rendezvous(&from, &to, time).unwrap();
+        let departure = RelativeState::new(
+            from.position.clone(),
+            from.velocity.clone() + transfer.departure_delta_v.clone(),
+        );
+        let arrival = cw.propagate(&departure, time);
+        testing::assert_array_eq_atol(&arrival.position.elem, &to.position.elem, 1e-9);
+        let matched = arrival.velocity + transfer.arrival_delta_v.clone();
+        testing::assert_array_eq_atol(&matched.elem, &to.velocity.elem, 1e-12);
+        assert!(transfer.delta_v() > 0.);
+
+        let period = 2. * PI / cw.mean_motion;
+        assert_eq!(
+            cw.rendezvous(&from, &to, period),
+            Err(RelativeError::SingularTransfer(period))
+        );
+        assert_eq!(
+            cw.rendezvous(&from, &to, 0.),
+            Err(RelativeError::NonPositiveTimeOfFlight(0.))
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Root finding and maximisation of scalar functions, shared by the searches for pass times
+//! and for optimal transfers.
+
+/// Iteration cap for Brent's method, which converges long before on any reasonable function
+const ROOT_MAX_ITER: usize = 100;
+
+/// Root of `function` between `lower` and `upper`, where it changes sign, to within `tolerance`
+/// (Brent, Algorithms for Minimization without Derivatives, chapter 4)
+pub(crate) fn brent_root(
+    function: impl Fn(f64) -> f64,
+    lower: f64,
+    upper: f64,
+    tolerance: f64,
+) -> f64 {
+    let (mut a, mut b) = (lower, upper);
+    let (mut fa, mut fb) = (function(a), function(b));
+    let (mut c, mut fc) = (b, fb);
+    let mut step = b - a;
+    let mut previous_step = step;
+    for _ in 0..ROOT_MAX_ITER {
+        if (fb > 0.) == (fc > 0.) {
+            (c, fc) = (a, fa);
+            step = b - a;
+            previous_step = step;
+        }
+        if fc.abs() < fb.abs() {
+            (a, fa) = (b, fb);
+            (b, fb) = (c, fc);
+            (c, fc) = (a, fa);
+        }
+        let tol = 2. * f64::EPSILON * b.abs() + 0.5 * tolerance;
+        let midpoint = 0.5 * (c - b);
+        if midpoint.abs() <= tol || fb == 0. {
+            return b;
+        }
+        if previous_step.abs() >= tol && fa.abs() > fb.abs() {
+            // Secant or inverse quadratic interpolation
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2. * midpoint * s, 1. - s)
+            } else {
+                let (q, r) = (fa / fc, fb / fc);
+                (
+                    s * (2. * midpoint * q * (q - r) - (b - a) * (r - 1.)),
+                    (q - 1.) * (r - 1.) * (s - 1.),
+                )
+            };
+            if p > 0. {
+                q = -q;
+            } else {
+                p = -p;
+            }
+            if 2. * p < (3. * midpoint * q - (tol * q).abs()).min((previous_step * q).abs()) {
+                previous_step = step;
+                step = p / q;
+            } else {
+                step = midpoint;
+                previous_step = step;
+            }
+        } else {
+            step = midpoint;
+            previous_step = step;
+        }
+        (a, fa) = (b, fb);
+        b += if step.abs() > tol {
+            step
+        } else {
+            tol.copysign(midpoint)
+        };
+        fb = function(b);
+    }
+    b
+}
+
+/// Maximum of `function` between `lower` and `upper`, where it has a single peak, to within
+/// `tolerance`
+pub(crate) fn golden_section_maximum(
+    function: impl Fn(f64) -> f64,
+    lower: f64,
+    upper: f64,
+    tolerance: f64,
+) -> f64 {
+    let ratio = (5_f64.sqrt() - 1.) / 2.;
+    let (mut a, mut b) = (lower, upper);
+    let (mut c, mut d) = (b - ratio * (b - a), a + ratio * (b - a));
+    let (mut fc, mut fd) = (function(c), function(d));
+    while b - a > tolerance {
+        if fc > fd {
+            (b, d, fd) = (d, c, fc);
+            c = b - ratio * (b - a);
+            fc = function(c);
+        } else {
+            (a, c, fc) = (c, d, fd);
+            d = a + ratio * (b - a);
+            fd = function(d);
+        }
+    }
+    (a + b) / 2.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Brent's method finds roots to the tolerance, and the golden section the peak
+    fn solver_test() {
+        let root = brent_root(|x: f64| x.powi(3) - 2., 0., 2., 1e-12);
+        assert_relative_eq!(root, 2_f64.cbrt(), epsilon = 1e-12);
+        let root = brent_root(f64::cos, 1., 3., 1e-14);
+        assert_relative_eq!(root, std::f64::consts::FRAC_PI_2, epsilon = 1e-14);
+        let peak = golden_section_maximum(|x: f64| -(x - 0.3).powi(2), -2., 5., 1e-9);
+        assert_relative_eq!(peak, 0.3, epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,570 @@
+use std::error::Error;
+use std::fmt;
+use std::ops;
+use std::str::FromStr;
+
+use super::calendar;
+use super::*;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const NANOS_PER_DAY: i128 = 86_400 * NANOS_PER_SECOND;
+
+/// `TT - TAI`, exactly
+const TT_MINUS_TAI: i128 = 32_184_000_000;
+
+/// `TAI - GPS`, exactly
+const TAI_MINUS_GPS: i128 = 19 * NANOS_PER_SECOND;
+
+/// Julian date and MJD of 2000-01-01 12:00:00 in any time scale
+const JD_2000: f64 = 2_451_545.;
+const MJD_2000: f64 = 51_544.5;
+
+/// An instant in time, held as a whole number of TAI nanoseconds since 2000-01-01 12:00:00 TAI
+/// so that it can be converted between time scales without losing precision over any span of
+/// interest.
+///
+/// Readings in a given scale, such as Julian dates or seconds since J2000, count from
+/// 2000-01-01 12:00:00 of that same scale, so `Epoch::J2000.seconds_since_j2000(TimeScale::TT)`
+/// is zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Epoch {
+    tai_nanoseconds: i128,
+}
+
+impl Epoch {
+    /// The J2000 reference epoch, 2000-01-01 12:00:00 TT
+    pub const J2000: Self = Self {
+        tai_nanoseconds: -TT_MINUS_TAI,
+    };
+
+    pub fn from_tai_nanoseconds(tai_nanoseconds: i128) -> Self {
+        Self { tai_nanoseconds }
+    }
+
+    /// Nanoseconds since 2000-01-01 12:00:00 TAI
+    pub fn tai_nanoseconds(&self) -> i128 {
+        self.tai_nanoseconds
+    }
+
+    /// Epoch at the given number of seconds since 2000-01-01 12:00:00 in the given scale
+    pub fn from_seconds_since_j2000(seconds: f64, scale: TimeScale) -> Self {
+        Self::from_reading(round_nanoseconds(seconds), scale)
+    }
+
+    /// Seconds since 2000-01-01 12:00:00 in the given scale
+    pub fn seconds_since_j2000(&self, scale: TimeScale) -> f64 {
+        self.reading(scale) as f64 / NANOS_PER_SECOND as f64
+    }
+
+    /// Epoch at a Julian date in the given scale. A single `f64` resolves a present-day Julian
+    /// date to about 40 µs.
+    pub fn from_jd(jd: f64, scale: TimeScale) -> Self {
+        Self::from_days_since_j2000(jd - JD_2000, scale)
+    }
+
+    pub fn to_jd(&self, scale: TimeScale) -> f64 {
+        JD_2000 + self.days_since_j2000(scale)
+    }
+
+    /// Epoch at a modified Julian date in the given scale
+    pub fn from_mjd(mjd: f64, scale: TimeScale) -> Self {
+        Self::from_days_since_j2000(mjd - MJD_2000, scale)
+    }
+
+    pub fn to_mjd(&self, scale: TimeScale) -> f64 {
+        MJD_2000 + self.days_since_j2000(scale)
+    }
+
+    /// Epoch at a date and time of the proleptic Gregorian calendar in the given scale. In UTC
+    /// the second may be 60 or more at 23:59, for leap seconds.
+    pub fn from_gregorian(
+        year: i64,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: f64,
+        scale: TimeScale,
+    ) -> Result<Self, EpochError> {
+        if !(0. ..61.).contains(&second) {
+            return Err(EpochError::InvalidTime);
+        }
+        let nanoseconds = round_nanoseconds(second);
+        Self::from_civil(
+            (year, month, day),
+            (hour, minute, nanoseconds / NANOS_PER_SECOND),
+            nanoseconds % NANOS_PER_SECOND,
+            scale,
+        )
+    }
+
+    /// Date and time in the given scale, as year, month, day, hour, minute and second
+    pub fn to_gregorian(&self, scale: TimeScale) -> (i64, u8, u8, u8, u8, f64) {
+        let civil = self.civil(scale);
+        let second = civil.second as f64 + civil.nanosecond as f64 / NANOS_PER_SECOND as f64;
+        (
+            civil.year,
+            civil.month,
+            civil.day,
+            civil.hour,
+            civil.minute,
+            second,
+        )
+    }
+
+    /// ISO 8601 date and time in the given scale, such as `2017-01-01T00:00:00.5Z` in UTC. Other
+    /// scales are named after the time, as in `2017-01-01T00:01:09.184 TT`, which is an
+    /// extension of ISO 8601.
+    pub fn to_iso(&self, scale: TimeScale) -> String {
+        let civil = self.civil(scale);
+        // Years outside 0000 to 9999 take the expanded form, signed and at least four digits
+        let year = if (0..=9999).contains(&civil.year) {
+            format!("{:04}", civil.year)
+        } else {
+            format!("{:+05}", civil.year)
+        };
+        let mut iso = format!(
+            "{year}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            civil.month, civil.day, civil.hour, civil.minute, civil.second
+        );
+        if civil.nanosecond != 0 {
+            let fraction = format!("{:09}", civil.nanosecond);
+            iso.push('.');
+            iso.push_str(fraction.trim_end_matches('0'));
+        }
+        match scale {
+            TimeScale::UTC => iso.push('Z'),
+            _ => iso.push_str(&format!(" {scale}")),
+        }
+        iso
+    }
+
+    fn days_since_j2000(&self, scale: TimeScale) -> f64 {
+        self.reading(scale) as f64 / NANOS_PER_DAY as f64
+    }
+
+    fn from_days_since_j2000(days: f64, scale: TimeScale) -> Self {
+        // Whole and fractional days apart, to keep what precision the input has
+        let whole = days.floor();
+        let fraction = round_nanoseconds((days - whole) * 86_400.);
+        Self::from_reading(whole as i128 * NANOS_PER_DAY + fraction, scale)
+    }
+
+    fn from_civil(
+        (year, month, day): (i64, u8, u8),
+        (hour, minute, second): (u8, u8, i128),
+        nanosecond: i128,
+        scale: TimeScale,
+    ) -> Result<Self, EpochError> {
+        if !(1..=12).contains(&month) || day == 0 || day > calendar::days_in_month(year, month) {
+            return Err(EpochError::InvalidDate);
+        }
+        let leap_second = scale == TimeScale::UTC && hour == 23 && minute == 59 && second == 60;
+        if hour > 23 || minute > 59 || (second > 59 && !leap_second) {
+            return Err(EpochError::InvalidTime);
+        }
+        let days = calendar::days_from_civil(year, month, day);
+        let time_of_day = (i128::from(hour) * 3_600 + i128::from(minute) * 60 + second)
+            * NANOS_PER_SECOND
+            + nanosecond;
+        let reading = i128::from(days) * NANOS_PER_DAY + time_of_day - NANOS_PER_DAY / 2;
+        if scale == TimeScale::UTC {
+            // The offset of the day itself, so a leap second at its end isn't counted twice
+            let offset = LeapSeconds::installed().offset_on_day(days);
+            Ok(Self::from_tai_nanoseconds(
+                reading + i128::from(offset) * NANOS_PER_SECOND,
+            ))
+        } else {
+            Ok(Self::from_reading(reading, scale))
+        }
+    }
+
+    /// Calendar date and time in the given scale, showing leap seconds as second 60 in UTC
+    fn civil(&self, scale: TimeScale) -> Civil {
+        let (reading, leap_second) = match scale {
+            TimeScale::UTC => {
+                let seconds = self.tai_nanoseconds.div_euclid(NANOS_PER_SECOND);
+                let (offset, leap_second) = LeapSeconds::installed().offset_at_tai(seconds);
+                let reading = self.tai_nanoseconds - i128::from(offset) * NANOS_PER_SECOND;
+                // Hold the clock at 23:59:59 through the leap second, then add it to the seconds
+                let held = if leap_second { NANOS_PER_SECOND } else { 0 };
+                (reading - held, leap_second)
+            }
+            _ => (self.reading(scale), false),
+        };
+        let since_midnight = reading + NANOS_PER_DAY / 2;
+        let days = since_midnight.div_euclid(NANOS_PER_DAY);
+        let time_of_day = since_midnight.rem_euclid(NANOS_PER_DAY);
+        let (year, month, day) = calendar::civil_from_days(days as i64);
+        let seconds = time_of_day / NANOS_PER_SECOND;
+        Civil {
+            year,
+            month,
+            day,
+            hour: (seconds / 3_600) as u8,
+            minute: (seconds / 60 % 60) as u8,
+            second: (seconds % 60) as u8 + u8::from(leap_second),
+            nanosecond: (time_of_day % NANOS_PER_SECOND) as u32,
+        }
+    }
+
+    /// Nanoseconds since 2000-01-01 12:00:00 in the given scale. UTC readings run on through leap
+    /// seconds into the first second of the next day.
+    fn reading(&self, scale: TimeScale) -> i128 {
+        let tai = self.tai_nanoseconds;
+        match scale {
+            TimeScale::TAI => tai,
+            TimeScale::TT => tai + TT_MINUS_TAI,
+            TimeScale::GPS => tai - TAI_MINUS_GPS,
+            TimeScale::TDB => {
+                let tt = tai + TT_MINUS_TAI;
+                tt + round_nanoseconds(tdb_minus_tt(tt))
+            }
+            TimeScale::UTC => utc_reading(tai),
+            TimeScale::UT1 => {
+                let utc = utc_reading(tai);
+                utc + round_nanoseconds(ut1_minus_utc(reading_to_mjd(utc)))
+            }
+        }
+    }
+
+    fn from_reading(reading: i128, scale: TimeScale) -> Self {
+        let tai = match scale {
+            TimeScale::TAI => reading,
+            TimeScale::TT => reading - TT_MINUS_TAI,
+            TimeScale::GPS => reading + TAI_MINUS_GPS,
+            TimeScale::TDB => {
+                // The difference changes so slowly that evaluating it at TDB instead of TT is
+                // good to well under a nanosecond
+                reading - round_nanoseconds(tdb_minus_tt(reading)) - TT_MINUS_TAI
+            }
+            TimeScale::UTC => utc_to_tai(reading),
+            TimeScale::UT1 => {
+                let utc = reading - round_nanoseconds(ut1_minus_utc(reading_to_mjd(reading)));
+                utc_to_tai(utc)
+            }
+        };
+        Self::from_tai_nanoseconds(tai)
+    }
+}
+
+/// Calendar date and time of day
+struct Civil {
+    year: i64,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+}
+
+fn round_nanoseconds(seconds: f64) -> i128 {
+    (seconds * NANOS_PER_SECOND as f64).round() as i128
+}
+
+fn reading_to_mjd(reading: i128) -> f64 {
+    MJD_2000 + reading as f64 / NANOS_PER_DAY as f64
+}
+
+fn utc_reading(tai: i128) -> i128 {
+    let (offset, _) = LeapSeconds::installed().offset_at_tai(tai.div_euclid(NANOS_PER_SECOND));
+    tai - i128::from(offset) * NANOS_PER_SECOND
+}
+
+fn utc_to_tai(utc: i128) -> i128 {
+    let day = (utc + NANOS_PER_DAY / 2).div_euclid(NANOS_PER_DAY);
+    let offset = LeapSeconds::installed().offset_on_day(day as i64);
+    utc + i128::from(offset) * NANOS_PER_SECOND
+}
+
+/// `TDB - TT` in seconds, from the main periodic terms (Astronomical Almanac), good to about
+/// 10 µs
+fn tdb_minus_tt(tt_nanoseconds: i128) -> f64 {
+    let days = tt_nanoseconds as f64 / NANOS_PER_DAY as f64;
+    let mean_anomaly = (357.53 + 0.985_600_28 * days).to_radians();
+    0.001_657 * mean_anomaly.sin() + 0.000_014 * (2. * mean_anomaly).sin()
+}
+
+impl ops::Add<f64> for Epoch {
+    type Output = Self;
+
+    /// Adds a number of SI seconds, rounded to the nanosecond
+    fn add(self, seconds: f64) -> Self {
+        Self::from_tai_nanoseconds(self.tai_nanoseconds + round_nanoseconds(seconds))
+    }
+}
+
+impl ops::Sub<f64> for Epoch {
+    type Output = Self;
+
+    fn sub(self, seconds: f64) -> Self {
+        self + -seconds
+    }
+}
+
+impl ops::Sub for Epoch {
+    type Output = f64;
+
+    /// SI seconds elapsed from `other` to `self`
+    fn sub(self, other: Self) -> f64 {
+        (self.tai_nanoseconds - other.tai_nanoseconds) as f64 / NANOS_PER_SECOND as f64
+    }
+}
+
+impl fmt::Display for Epoch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_iso(TimeScale::UTC))
+    }
+}
+
+impl FromStr for Epoch {
+    type Err = EpochError;
+
+    /// Parses an ISO 8601 date, or date and time, in UTC if it ends in `Z` or names no scale,
+    /// or in the scale named after it as written by `to_iso`. A space may stand in for the `T`.
+    fn from_str(text: &str) -> Result<Self, EpochError> {
+        let text = text.trim();
+        let (text, scale) = match text.rsplit_once(' ') {
+            Some((rest, scale)) if scale.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                (rest, scale.parse()?)
+            }
+            _ => (text.strip_suffix('Z').unwrap_or(text), TimeScale::UTC),
+        };
+        let (date, time) = match text.split_once(['T', ' ']) {
+            Some((date, time)) => (date, Some(time)),
+            None => (text, None),
+        };
+
+        // The year may carry a sign
+        let (sign, unsigned) = match date.strip_prefix('-') {
+            Some(rest) => (-1, rest),
+            None => (1, date.strip_prefix('+').unwrap_or(date)),
+        };
+        let [year, month, day] = unsigned.split('-').collect::<Vec<_>>()[..] else {
+            return Err(EpochError::Parse("expected a date as YYYY-MM-DD"));
+        };
+        let date = (sign * number::<i64>(year)?, number(month)?, number(day)?);
+
+        let (clock, nanosecond) = match time {
+            None => ((0, 0, 0), 0),
+            Some(time) => {
+                let (whole, fraction) = time.split_once(['.', ',']).unwrap_or((time, ""));
+                let [hour, minute, second] = whole.split(':').collect::<Vec<_>>()[..] else {
+                    return Err(EpochError::Parse("expected a time as hh:mm:ss"));
+                };
+                if fraction.len() > 9 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(EpochError::Parse("invalid fraction of a second"));
+                }
+                let nanosecond = format!("{fraction:0<9}").parse().unwrap_or(0);
+                let clock = (number(hour)?, number(minute)?, number(second)?);
+                (clock, nanosecond)
+            }
+        };
+        Self::from_civil(date, clock, nanosecond, scale)
+    }
+}
+
+fn number<T: FromStr>(text: &str) -> Result<T, EpochError> {
+    // Plain digits only, so that signs and spaces are rejected
+    if text.is_empty() || !text.chars().all(|c| c.is_ascii_digit()) {
+        return Err(EpochError::Parse("invalid number"));
+    }
+    text.parse()
+        .map_err(|_| EpochError::Parse("invalid number"))
+}
+
+impl FromStr for TimeScale {
+    type Err = EpochError;
+
+    fn from_str(text: &str) -> Result<Self, EpochError> {
+        TimeScale::ALL
+            .into_iter()
+            .find(|scale| scale.to_string().eq_ignore_ascii_case(text))
+            .ok_or(EpochError::Parse("unknown time scale"))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EpochError {
+    /// A month or day that doesn't exist
+    InvalidDate,
+    /// An hour, minute or second out of range
+    InvalidTime,
+    Parse(&'static str),
+}
+
+impl fmt::Display for EpochError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDate => write!(f, "no such date"),
+            Self::InvalidTime => write!(f, "time of day out of range"),
+            Self::Parse(what) => write!(f, "invalid epoch: {what}"),
+        }
+    }
+}
+
+impl Error for EpochError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(text: &str) -> Epoch {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    /// J2000 is 11:58:55.816 UTC, and 11:59:27.816 TAI
+    fn j2000_test() {
+        assert_eq!(
+            Epoch::J2000.to_iso(TimeScale::UTC),
+            "2000-01-01T11:58:55.816Z"
+        );
+        assert_eq!(
+            Epoch::J2000.to_iso(TimeScale::TAI),
+            "2000-01-01T11:59:27.816 TAI"
+        );
+        assert_eq!(Epoch::J2000.to_jd(TimeScale::TT), 2_451_545.);
+        assert_eq!(Epoch::J2000.seconds_since_j2000(TimeScale::TT), 0.);
+        assert_eq!(Epoch::J2000.to_mjd(TimeScale::TT), 51_544.5);
+        assert_eq!(utc("2000-01-01T11:58:55.816Z"), Epoch::J2000);
+        assert_eq!(utc("2000-01-01 12:00:00 TT"), Epoch::J2000);
+    }
+
+    #[test]
+    fn scales_test() {
+        let epoch = utc("2017-06-15T08:30:00Z");
+        assert_eq!(epoch.to_iso(TimeScale::TAI), "2017-06-15T08:30:37 TAI");
+        assert_eq!(epoch.to_iso(TimeScale::GPS), "2017-06-15T08:30:18 GPS");
+        assert_eq!(epoch.to_iso(TimeScale::TT), "2017-06-15T08:31:09.184 TT");
+        let tdb =
+            epoch.seconds_since_j2000(TimeScale::TDB) - epoch.seconds_since_j2000(TimeScale::TT);
+        assert!(tdb.abs() < 1.7e-3 && tdb != 0.);
+        for scale in TimeScale::ALL {
+            let reading = epoch.to_iso(scale);
+            assert_eq!(reading.parse::<Epoch>().unwrap(), epoch, "{reading}");
+            let seconds = epoch.seconds_since_j2000(scale);
+            let round_trip = Epoch::from_seconds_since_j2000(seconds, scale);
+            assert!((round_trip - epoch).abs() < 1e-7);
+        }
+        // The GPS epoch
+        assert_eq!(
+            Epoch::from_gregorian(1980, 1, 6, 0, 0, 0., TimeScale::GPS).unwrap(),
+            utc("1980-01-06T00:00:00Z")
+        );
+    }
+
+    #[test]
+    /// The leap second at the end of 2016 reads as 23:59:60 and lasts one SI second
+    fn leap_second_test() {
+        let before = utc("2016-12-31T23:59:59Z");
+        let leap = utc("2016-12-31T23:59:60.25Z");
+        let after = utc("2017-01-01T00:00:00Z");
+        assert_eq!(leap - before, 1.25);
+        assert_eq!(after - before, 2.);
+        assert_eq!(leap.to_iso(TimeScale::UTC), "2016-12-31T23:59:60.25Z");
+        assert_eq!(after.to_gregorian(TimeScale::UTC), (2017, 1, 1, 0, 0, 0.));
+        assert_eq!(
+            (before + 0.5).to_gregorian(TimeScale::UTC),
+            (2016, 12, 31, 23, 59, 59.5)
+        );
+        // Without a leap second, 23:59:60 runs into the next day
+        assert_eq!(
+            "2017-06-30T23:59:60Z".parse::<Epoch>(),
+            Ok(utc("2017-07-01T00:00:00Z"))
+        );
+        assert_eq!(
+            "2016-12-31T23:59:60 TAI".parse::<Epoch>(),
+            Err(EpochError::InvalidTime)
+        );
+    }
+
+    #[test]
+    /// Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., example 3-4: 1996 October
+    /// 26, 14:20 UT is JD 2450383.09722222
+    fn julian_date_test() {
+        let epoch = Epoch::from_gregorian(1996, 10, 26, 14, 20, 0., TimeScale::UTC).unwrap();
+        assert_relative_eq!(
+            epoch.to_jd(TimeScale::UTC),
+            2_450_383.097_222_22,
+            epsilon = 1e-8
+        );
+        assert_relative_eq!(
+            epoch.to_mjd(TimeScale::UTC),
+            50_382.597_222_22,
+            epsilon = 1e-8
+        );
+        let round_trip = Epoch::from_jd(epoch.to_jd(TimeScale::TT), TimeScale::TT);
+        assert!((round_trip - epoch).abs() < 1e-4);
+        let round_trip = Epoch::from_mjd(epoch.to_mjd(TimeScale::TAI), TimeScale::TAI);
+        assert!((round_trip - epoch).abs() < 1e-5);
+    }
+
+    #[test]
+    fn iso_test() {
+        let epoch = utc("2024-02-29T23:05:07.123456789Z");
+        assert_eq!(epoch.to_string(), "2024-02-29T23:05:07.123456789Z");
+        assert_eq!(epoch + 1e-9 - epoch, 1e-9);
+        assert_eq!(utc("2024-02-29"), utc("2024-02-29T00:00:00.000Z"));
+        assert_eq!(utc("2024-03-01 00:00:00,5"), utc("2024-03-01T00:00:00.5Z"));
+        assert_eq!(
+            utc("-0001-03-01").to_iso(TimeScale::UTC),
+            "-0001-03-01T00:00:00Z"
+        );
+        assert_eq!(
+            utc("+12345-06-07").to_iso(TimeScale::UTC),
+            "+12345-06-07T00:00:00Z"
+        );
+        assert_eq!(
+            utc("-0001-03-01").to_string().parse(),
+            Ok(utc("-0001-03-01"))
+        );
+        for bad in [
+            "2023-02-29",
+            "2024-13-01",
+            "2024-01-01T24:00:00",
+            "2024-01-01T12:00",
+            "2024-01-01T12:00:00.1234567891",
+            "2024-01-01T12:00:00 XYZ",
+            "yesterday",
+        ] {
+            assert!(bad.parse::<Epoch>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    /// Nanoseconds survive centuries away from J2000
+    fn precision_test() {
+        let epoch = utc("2250-06-01T00:00:00.000000001Z");
+        assert_eq!(
+            (epoch - 1e-9).to_iso(TimeScale::UTC),
+            "2250-06-01T00:00:00Z"
+        );
+        assert_eq!(Epoch::from_tai_nanoseconds(epoch.tai_nanoseconds()), epoch);
+        assert!(epoch > Epoch::J2000);
+    }
+
+    #[test]
+    /// Installing a table or `UT1 - UTC` changes later conversions. Both are global, so the
+    /// changes are kept to the year 3000, after the dates of every other test.
+    fn install_test() {
+        let midnight = Epoch::from_gregorian(3000, 1, 1, 0, 0, 0., TimeScale::TAI).unwrap();
+        let mjd = midnight.to_mjd(TimeScale::TAI) as i64;
+        let mut entries = LeapSeconds::default().entries().to_vec();
+        entries.push((mjd, 38));
+        LeapSeconds::new(entries).unwrap().install();
+        let before = utc("2999-12-31T23:59:59Z");
+        assert_eq!(utc("3000-01-01T00:00:00Z") - before, 2.);
+        assert_eq!((before + 1.).to_iso(TimeScale::UTC), "2999-12-31T23:59:60Z");
+        LeapSeconds::uninstall();
+        assert_eq!(utc("3000-01-01T00:00:00Z") - before, 1.);
+
+        set_ut1_minus_utc(move |utc_mjd| if utc_mjd >= mjd as f64 { -0.25 } else { 0. });
+        let epoch = utc("3000-06-01T00:00:00Z");
+        assert_eq!(epoch.to_iso(TimeScale::UT1), "3000-05-31T23:59:59.75 UT1");
+        assert_eq!("3000-05-31T23:59:59.75 UT1".parse(), Ok(epoch));
+        clear_ut1_minus_utc();
+        assert_eq!(epoch.to_iso(TimeScale::UT1), "3000-06-01T00:00:00 UT1");
+    }
+}
@@ -0,0 +1,315 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use super::calendar;
+
+/// MJD of 2000-01-01
+const MJD_2000: i64 = 51_544;
+
+/// `TAI - UTC` in whole seconds from the given UTC dates (as MJD) on, up to the leap second at the
+/// end of 2016
+const BUILT_IN: [(i64, i64); 28] = [
+    (41_317, 10),
+    (41_499, 11),
+    (41_683, 12),
+    (42_048, 13),
+    (42_413, 14),
+    (42_778, 15),
+    (43_144, 16),
+    (43_509, 17),
+    (43_874, 18),
+    (44_239, 19),
+    (44_786, 20),
+    (45_151, 21),
+    (45_516, 22),
+    (46_247, 23),
+    (47_161, 24),
+    (47_892, 25),
+    (48_257, 26),
+    (48_804, 27),
+    (49_169, 28),
+    (49_534, 29),
+    (50_083, 30),
+    (50_630, 31),
+    (51_179, 32),
+    (53_736, 33),
+    (54_832, 34),
+    (56_109, 35),
+    (57_204, 36),
+    (57_754, 37),
+];
+
+/// The table in use by every UTC conversion, or `None` for the built-in one
+static INSTALLED: RwLock<Option<Arc<LeapSeconds>>> = RwLock::new(None);
+
+/// Table of `TAI - UTC`, which steps by a leap second at the start of some UTC days.
+///
+/// UTC conversions use the built-in table, which is current up to its last leap second at the end
+/// of 2016, unless another one is installed with `install`. Before the first entry in 1972, when
+/// UTC didn't yet step by whole seconds, the first offset is used.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LeapSeconds {
+    /// UTC date as MJD and `TAI - UTC` in seconds from that date on, in order
+    entries: Vec<(i64, i64)>,
+}
+
+impl Default for LeapSeconds {
+    fn default() -> Self {
+        Self {
+            entries: BUILT_IN.to_vec(),
+        }
+    }
+}
+
+impl LeapSeconds {
+    /// Table from `(MJD, TAI - UTC)` pairs, which are sorted by date. A table needs at least one
+    /// entry.
+    pub fn new(mut entries: Vec<(i64, i64)>) -> Result<Self, LeapSecondsError> {
+        if entries.is_empty() {
+            return Err(LeapSecondsError::Empty);
+        }
+        entries.sort_unstable();
+        Ok(Self { entries })
+    }
+
+    /// Reads the IERS `Leap_Second.dat` format, with lines of MJD, day, month, year and
+    /// `TAI - UTC`, and comments starting with `#`
+    pub fn read_iers(reader: impl BufRead) -> Result<Self, LeapSecondsError> {
+        let mut entries = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = || LeapSecondsError::Parse { line: index + 1 };
+            let words: Vec<_> = line.split_whitespace().collect();
+            let [mjd, _, _, _, offset] = words[..] else {
+                return Err(error());
+            };
+            let mjd: f64 = mjd.parse().map_err(|_| error())?;
+            let offset = offset.parse().map_err(|_| error())?;
+            entries.push((mjd as i64, offset));
+        }
+        Self::new(entries)
+    }
+
+    /// Reads the `DELTA_AT` table of a NAIF leap seconds kernel (`.tls`), whose entries look like
+    /// `37, @2017-JAN-1`
+    pub fn read_naif(reader: impl BufRead) -> Result<Self, LeapSecondsError> {
+        let mut entries = Vec::new();
+        let mut in_table = false;
+        let mut offset = None;
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let error = || LeapSecondsError::Parse { line: index + 1 };
+            let mut rest = line.as_str();
+            if !in_table {
+                match line.trim_start().strip_prefix("DELTA_AT") {
+                    Some(after) => rest = after,
+                    None => continue,
+                }
+                in_table = true;
+            }
+            for word in
+                rest.split(|c: char| c.is_whitespace() || matches!(c, ',' | '(' | ')' | '='))
+            {
+                if let Some(date) = word.strip_prefix('@') {
+                    let offset = offset.take().ok_or_else(error)?;
+                    entries.push((naif_date(date).ok_or_else(error)?, offset));
+                } else if !word.is_empty() {
+                    offset = Some(word.parse().map_err(|_| error())?);
+                }
+            }
+            if rest.contains(')') {
+                break;
+            }
+        }
+        Self::new(entries)
+    }
+
+    /// Loads a NAIF kernel if the file ends in `.tls`, and the IERS format otherwise
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LeapSecondsError> {
+        let path = path.as_ref();
+        let reader = BufReader::new(File::open(path)?);
+        if path.extension().is_some_and(|extension| extension == "tls") {
+            Self::read_naif(reader)
+        } else {
+            Self::read_iers(reader)
+        }
+    }
+
+    /// UTC dates as MJD and `TAI - UTC` in seconds from each date on, in order
+    pub fn entries(&self) -> &[(i64, i64)] {
+        &self.entries
+    }
+
+    /// Makes this the table used by every UTC conversion
+    pub fn install(self) {
+        *INSTALLED.write().unwrap() = Some(Arc::new(self));
+    }
+
+    /// Goes back to the built-in table
+    pub fn uninstall() {
+        *INSTALLED.write().unwrap() = None;
+    }
+
+    /// The table in use
+    pub fn installed() -> Arc<Self> {
+        INSTALLED
+            .read()
+            .unwrap()
+            .clone()
+            .unwrap_or_else(|| Arc::new(Self::default()))
+    }
+
+    /// `TAI - UTC` in seconds on the UTC day of the given MJD
+    pub fn tai_minus_utc(&self, mjd: i64) -> i64 {
+        let index = self.entries.partition_point(|(start, _)| *start <= mjd);
+        self.entries[index.saturating_sub(1)].1
+    }
+
+    /// `TAI - UTC` in seconds on the UTC day starting the given number of days after 2000-01-01
+    pub(super) fn offset_on_day(&self, day: i64) -> i64 {
+        self.tai_minus_utc(day + MJD_2000)
+    }
+
+    /// `TAI - UTC` in seconds at an instant given as TAI seconds since 2000-01-01 12:00:00 TAI,
+    /// and whether that instant falls within a leap second
+    pub(super) fn offset_at_tai(&self, tai_seconds: i128) -> (i64, bool) {
+        let start = |(mjd, offset): (i64, i64)| {
+            i128::from(mjd - MJD_2000) * 86_400 - 43_200 + i128::from(offset)
+        };
+        let index = self
+            .entries
+            .partition_point(|entry| start(*entry) <= tai_seconds)
+            .max(1);
+        let offset = self.entries[index - 1].1;
+        let in_leap_second = self.entries.get(index).is_some_and(|next| {
+            let added = i128::from(next.1 - offset);
+            added > 0 && tai_seconds >= start(*next) - added
+        });
+        (offset, in_leap_second)
+    }
+}
+
+/// MJD of a NAIF date such as `2017-JAN-1`
+fn naif_date(date: &str) -> Option<i64> {
+    const MONTHS: [&str; 12] = [
+        "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+    ];
+    let mut parts = date.split('-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.to_ascii_uppercase();
+    let month = MONTHS.iter().position(|name| *name == month)? as u8 + 1;
+    let day = parts.next()?.parse().ok()?;
+    Some(calendar::days_from_civil(year, month, day) + MJD_2000)
+}
+
+#[derive(Debug)]
+pub enum LeapSecondsError {
+    Io(io::Error),
+    /// A malformed line, numbered from 1
+    Parse {
+        line: usize,
+    },
+    /// No entries were found
+    Empty,
+}
+
+impl fmt::Display for LeapSecondsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "reading leap seconds: {err}"),
+            Self::Parse { line } => write!(f, "leap seconds line {line} is malformed"),
+            Self::Empty => write!(f, "no leap seconds found"),
+        }
+    }
+}
+
+impl Error for LeapSecondsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LeapSecondsError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_test() {
+        let table = LeapSeconds::default();
+        let day = |year, month, day| calendar::days_from_civil(year, month, day);
+        assert_eq!(table.offset_on_day(day(1960, 1, 1)), 10);
+        assert_eq!(table.offset_on_day(day(1972, 6, 30)), 10);
+        assert_eq!(table.offset_on_day(day(1972, 7, 1)), 11);
+        assert_eq!(table.offset_on_day(day(2016, 12, 31)), 36);
+        assert_eq!(table.offset_on_day(day(2017, 1, 1)), 37);
+        for (mjd, _) in BUILT_IN {
+            let (_, month, day) = calendar::civil_from_days(mjd - MJD_2000);
+            assert!(matches!((month, day), (1 | 7, 1)), "{mjd}");
+        }
+
+        // 2017-01-01 00:00:00 UTC is 37 s after midnight in TAI
+        let new_year = i128::from(day(2017, 1, 1)) * 86_400 - 43_200;
+        assert_eq!(table.offset_at_tai(new_year + 37), (37, false));
+        assert_eq!(table.offset_at_tai(new_year + 36), (36, true));
+        assert_eq!(table.offset_at_tai(new_year + 35), (36, false));
+    }
+
+    #[test]
+    fn read_test() {
+        let iers = "\
+#  File expires on 28 December 2025
+#    MJD        Date        TAI-UTC (s)
+#           day month year
+    41317.0    1  1 1972       10
+    41499.0    1  7 1972       11
+    57754.0    1  1 2017       37
+";
+        let table = LeapSeconds::read_iers(iers.as_bytes()).unwrap();
+        assert_eq!(table.entries, [(41_317, 10), (41_499, 11), (57_754, 37)]);
+
+        let naif = "\
+\\begindata
+
+DELTA_T_A       =   32.184
+DELTA_AT        = ( 10,   @1972-JAN-1
+                    11,   @1972-JUL-1
+                    37,   @2017-JAN-1 )
+
+\\begintext
+";
+        assert_eq!(LeapSeconds::read_naif(naif.as_bytes()).unwrap(), table);
+        assert!(matches!(
+            LeapSeconds::read_iers("41317.0 1 1 1972\n".as_bytes()),
+            Err(LeapSecondsError::Parse { line: 1 })
+        ));
+        assert!(matches!(
+            LeapSeconds::read_naif("nothing here".as_bytes()),
+            Err(LeapSecondsError::Empty)
+        ));
+        assert!(matches!(
+            LeapSeconds::read_iers("# File expires on 28 June 2025\n\n".as_bytes()),
+            Err(LeapSecondsError::Empty)
+        ));
+        assert!(matches!(
+            LeapSeconds::new(vec![]),
+            Err(LeapSecondsError::Empty)
+        ));
+    }
+}
@@ -0,0 +1,68 @@
+//! Time scales and the [`Epoch`] type, which ties orbits to an instant.
+//!
+//! UTC conversions follow the leap second table in [`LeapSeconds`]. UT1 conversions follow
+//! whatever `UT1 - UTC` has been set with [`set_ut1_minus_utc`], and take UT1 as UTC until then,
+//! which is good to within 0.9 s.
+
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+mod calendar;
+mod epoch;
+mod leap_seconds;
+
+pub use epoch::{Epoch, EpochError};
+pub use leap_seconds::{LeapSeconds, LeapSecondsError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimeScale {
+    /// Coordinated Universal Time, which follows TAI with leap seconds to stay near UT1
+    UTC,
+    /// International Atomic Time
+    TAI,
+    /// Terrestrial Time, `TAI + 32.184 s`
+    TT,
+    /// Barycentric Dynamical Time, which differs from TT by periodic terms under 2 ms
+    TDB,
+    /// GPS time, `TAI - 19 s`
+    GPS,
+    /// Universal Time, which follows the rotation of the Earth
+    UT1,
+}
+
+impl TimeScale {
+    pub const ALL: [Self; 6] = [
+        Self::UTC,
+        Self::TAI,
+        Self::TT,
+        Self::TDB,
+        Self::GPS,
+        Self::UT1,
+    ];
+}
+
+impl fmt::Display for TimeScale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+type Ut1MinusUtc = dyn Fn(f64) -> f64 + Send + Sync;
+
+static UT1_MINUS_UTC: RwLock<Option<Arc<Ut1MinusUtc>>> = RwLock::new(None);
+
+/// Sets `UT1 - UTC` in seconds, as a function of the UTC modified Julian date, for every UT1
+/// conversion
+pub fn set_ut1_minus_utc(offset: impl Fn(f64) -> f64 + Send + Sync + 'static) {
+    *UT1_MINUS_UTC.write().unwrap() = Some(Arc::new(offset));
+}
+
+/// Goes back to taking UT1 as UTC
+pub fn clear_ut1_minus_utc() {
+    *UT1_MINUS_UTC.write().unwrap() = None;
+}
+
+fn ut1_minus_utc(mjd: f64) -> f64 {
+    let offset = UT1_MINUS_UTC.read().unwrap().clone();
+    offset.map_or(0., |offset| offset(mjd))
+}
@@ -52,6 +52,22 @@ impl ops::Add<Self> for Vector3 {
     }
 }
 
+impl ops::Sub<Self> for Vector3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl ops::Neg for Vector3 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(self.elem.map(|elem| -elem))
+    }
+}
+
 // TODO: How do I make this not consume the original?
 impl<T> ops::Mul<T> for Vector3
 where
@@ -85,6 +101,7 @@ impl From<[f64; 3]> for Vector3 {
 }
 
 #[cfg(test)]
+#[allow(clippy::unnecessary_cast)]
 mod tests {
     use super::*;
 
@@ -132,6 +149,20 @@ mod tests {
         testing::assert_array_eq(&res.elem, &[5.0, 3.0, 3.0]);
     }
 
+    #[test]
+    fn test_sub_vectors() {
+        let v1 = Vector3::new([1.0, -2.0, -3.0]);
+        let v2 = Vector3::new([4.0, 5.0, 6.0]);
+        let res = v1 - v2;
+        testing::assert_array_eq(&res.elem, &[-3.0, -7.0, -9.0]);
+    }
+
+    #[test]
+    fn test_neg() {
+        let v = Vector3::new([1.0, -2.0, 0.0]);
+        testing::assert_array_eq(&(-v).elem, &[-1.0, 2.0, 0.0]);
+    }
+
     #[test]
     fn test_mul_scalar() {
         let v = Vector3::new([10.0, 20.0, 5.0]);
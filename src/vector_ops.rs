@@ -15,6 +15,7 @@ pub fn vector_dot(vec_a: &[f64], vec_b: &[f64]) -> f64 {
 }
 
 #[cfg(test)]
+#[allow(clippy::unnecessary_cast, clippy::clone_on_copy)]
 mod tests {
     use super::*;
 